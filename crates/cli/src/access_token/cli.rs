@@ -0,0 +1,25 @@
+use {clap::Subcommand, smbcloud_model::access_token::TokenScope};
+
+#[derive(Subcommand)]
+pub enum Commands {
+    #[clap(about = "Create a personal access token, e.g. for CI. Shown once.")]
+    Create {
+        #[clap(short, long, required = true)]
+        name: String,
+        /// When the token stops working: a duration like 30d or 12h, a date
+        /// like 2026-12-31, or `never`.
+        #[clap(short, long, default_value = "90d")]
+        expires: String,
+        /// What the token may do: read, deploy, deploy:<project_id> or write.
+        /// Repeat for more than one.
+        #[clap(short, long = "scope", required = true, value_name = "SCOPE")]
+        scopes: Vec<TokenScope>,
+    },
+    #[clap(about = "List your personal access tokens.")]
+    List {},
+    #[clap(about = "Revoke a personal access token.")]
+    Revoke {
+        #[clap(short, long, required = true)]
+        id: i64,
+    },
+}
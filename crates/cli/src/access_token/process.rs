@@ -0,0 +1,190 @@
+use crate::{
+    access_token::{
+        cli::Commands,
+        render::{print_access_tokens, print_created_token},
+    },
+    cli::CommandResult,
+    client,
+    token::get_smb_token::{env_smb_token, get_session_token, SMB_TOKEN_ENV},
+    ui::{fail_message, fail_symbol, prompt::confirm_delete, succeed_message, succeed_symbol},
+};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use smbcloud_auth::access_token::{create_access_token, get_access_tokens, revoke_access_token};
+use smbcloud_model::access_token::{AccessTokenCreate, TokenScope};
+use smbcloud_network::environment::Environment;
+use spinners::Spinner;
+
+pub async fn process_access_token(env: Environment, commands: Commands) -> Result<CommandResult> {
+    match commands {
+        Commands::Create {
+            name,
+            expires,
+            scopes,
+        } => process_access_token_create(env, name, expires, scopes).await,
+        Commands::List {} => process_access_token_list(env).await,
+        Commands::Revoke { id } => process_access_token_revoke(env, id).await,
+    }
+}
+
+async fn process_access_token_create(
+    env: Environment,
+    name: String,
+    expires: String,
+    scopes: Vec<TokenScope>,
+) -> Result<CommandResult> {
+    let access_token = session_token(env)?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(anyhow!("name cannot be empty."));
+    }
+    let params = AccessTokenCreate {
+        name,
+        scopes,
+        expires_at: parse_expires(&expires, Utc::now())?,
+    };
+    let mut spinner = loading_spinner("Creating access token");
+
+    let created = create_access_token(env, client(), access_token, params)
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Created."));
+    print_created_token(&created.access_token, &created.token);
+
+    Ok(done_result("Access token created."))
+}
+
+async fn process_access_token_list(env: Environment) -> Result<CommandResult> {
+    let access_token = session_token(env)?;
+    let mut spinner = loading_spinner("Loading access tokens");
+
+    let access_tokens = get_access_tokens(env, client(), access_token)
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
+    print_access_tokens(&access_tokens);
+
+    Ok(done_result("Done."))
+}
+
+async fn process_access_token_revoke(env: Environment, id: i64) -> Result<CommandResult> {
+    let access_token = session_token(env)?;
+    let confirmed = confirm_delete(
+        "Access token revocation confirmation",
+        &format!("Revoke access token #{id}? Anything using it stops working"),
+    )?;
+    if !confirmed {
+        return Ok(done_result("Cancelled."));
+    }
+
+    let spinner = loading_spinner("Revoking access token");
+
+    match revoke_access_token(env, client(), access_token, id).await {
+        Ok(()) => Ok(CommandResult {
+            spinner,
+            symbol: succeed_symbol(),
+            msg: succeed_message("Done. Access token has been revoked."),
+        }),
+        Err(error) => Ok(CommandResult {
+            spinner,
+            symbol: fail_symbol(),
+            msg: fail_message(&error.to_string()),
+        }),
+    }
+}
+
+/// Access tokens are managed with a login session, never with another
+/// access token.
+fn session_token(env: Environment) -> Result<String> {
+    if env_smb_token().is_some() {
+        return Err(anyhow!(
+            "Managing access tokens needs a login session. Unset {SMB_TOKEN_ENV} and run `smb login`."
+        ));
+    }
+    get_session_token(env)
+}
+
+/// Reads `--expires`: `never`, a number of days/hours/weeks (`30d`, `12h`,
+/// `2w`), or a date, which expires at the start of that day in UTC.
+fn parse_expires(value: &str, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+    let value = value.trim().to_ascii_lowercase();
+    if value == "never" {
+        return Ok(None);
+    }
+    let invalid = || {
+        anyhow!("Invalid --expires value `{value}`. Use e.g. 30d, 12h, 2w, 2026-12-31 or never.")
+    };
+    if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+        let expires_at = date.and_hms_opt(0, 0, 0).ok_or_else(invalid)?.and_utc();
+        if expires_at <= now {
+            return Err(anyhow!("--expires must be in the future."));
+        }
+        return Ok(Some(expires_at));
+    }
+    let (split, unit) = value.char_indices().last().ok_or_else(invalid)?;
+    let amount: i64 = value[..split].parse().map_err(|_| invalid())?;
+    if amount <= 0 {
+        return Err(invalid());
+    }
+    let duration = match unit {
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => return Err(invalid()),
+    };
+    duration
+        .and_then(|duration| now.checked_add_signed(duration))
+        .map(Some)
+        .ok_or_else(invalid)
+}
+
+fn loading_spinner(message: &str) -> Spinner {
+    Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message(message),
+    )
+}
+
+fn done_result(message: &str) -> CommandResult {
+    CommandResult {
+        spinner: loading_spinner("Done"),
+        symbol: succeed_symbol(),
+        msg: succeed_message(message),
+    }
+}
+
+fn api_error(error: impl std::fmt::Display) -> anyhow::Error {
+    anyhow!(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::TimeZone};
+
+    #[test]
+    fn parses_expiry_values() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(parse_expires("never", now).unwrap(), None);
+        assert_eq!(
+            parse_expires("30d", now).unwrap(),
+            Some(now + Duration::days(30))
+        );
+        assert_eq!(
+            parse_expires("12h", now).unwrap(),
+            Some(now + Duration::hours(12))
+        );
+        assert_eq!(
+            parse_expires("2026-12-31", now).unwrap(),
+            Some(Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap())
+        );
+        assert!(parse_expires("2025-12-31", now).is_err());
+        assert!(parse_expires("0d", now).is_err());
+        assert!(parse_expires("soon", now).is_err());
+        assert!(parse_expires("", now).is_err());
+        assert!(parse_expires("30日", now).is_err());
+        assert!(parse_expires("99999999999999d", now).is_err());
+        assert!(parse_expires("9999999999w", now).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use {
+    chrono::{DateTime, Utc},
+    console::style,
+    smbcloud_model::access_token::AccessToken,
+};
+
+fn print_heading(title: &str) {
+    println!("\n{}", style(title).bold().underlined());
+}
+
+fn date(value: Option<DateTime<Utc>>, none: &str) -> String {
+    value
+        .map(|value| value.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| none.to_string())
+}
+
+fn scopes(access_token: &AccessToken) -> String {
+    access_token
+        .scopes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub(crate) fn print_access_tokens(access_tokens: &[AccessToken]) {
+    print_heading("Access tokens");
+
+    if access_tokens.is_empty() {
+        println!("No access tokens found.");
+        return;
+    }
+
+    let now = Utc::now();
+    for access_token in access_tokens {
+        let expires = if access_token.is_expired(now) {
+            style("expired".to_string()).red().to_string()
+        } else {
+            format!("expires={}", date(access_token.expires_at, "never"))
+        };
+        println!(
+            "#{} {} {}… scopes={} {} last_used={}",
+            access_token.id,
+            style(&access_token.name).bold(),
+            access_token.token_prefix,
+            scopes(access_token),
+            expires,
+            date(access_token.last_used_at, "never"),
+        );
+    }
+}
+
+/// The secret is only shown here, right after creating the token.
+pub(crate) fn print_created_token(access_token: &AccessToken, token: &str) {
+    print_heading("Access token");
+    println!("{} {}", style("ID:").cyan(), access_token.id);
+    println!("{} {}", style("Name:").cyan(), access_token.name);
+    println!("{} {}", style("Scopes:").cyan(), scopes(access_token));
+    println!(
+        "{} {}",
+        style("Expires:").cyan(),
+        date(access_token.expires_at, "never")
+    );
+    println!("\n{}", style(token).bold());
+    println!(
+        "{}",
+        style("Copy it now; it won't be shown again. Use it as SMB_TOKEN in CI.").yellow()
+    );
+}
@@ -5,9 +5,76 @@ pub enum Commands {
     #[clap(about = "Create an account with your email address.")]
     Signup {},
     #[clap(about = "Log in to your account. If you need one first, run `smb signup`.")]
-    Login {},
+    Login {
+        /// Log in with a code entered on another device, for SSH sessions and
+        /// containers without a browser.
+        #[arg(long)]
+        device: bool,
+        /// Log in through smbCloud Auth in the browser (OIDC with PKCE),
+        /// picking the tenant on the consent screen.
+        #[arg(long, conflicts_with = "device")]
+        sso: bool,
+        /// Code from your authenticator app (or a recovery code), for an
+        /// account with two-factor authentication on.
+        #[arg(long, value_name = "CODE", conflicts_with_all = ["device", "sso"])]
+        otp: Option<String>,
+    },
     #[clap(about = "Log out of your current session.")]
     Logout {},
     #[clap(about = "Start the password reset flow.")]
     Forgot {},
+    #[clap(about = "Change the password of your account.")]
+    Password {
+        /// Your current password. Prefer the environment variable over the
+        /// flag, which stays in your shell history.
+        #[arg(long, env = "SMB_CURRENT_PASSWORD", hide_env_values = true)]
+        current_password: Option<String>,
+        /// The password to change to.
+        #[arg(long, env = "SMB_NEW_PASSWORD", hide_env_values = true)]
+        new_password: Option<String>,
+    },
+    #[clap(about = "Change the email address of your account.")]
+    Email {
+        /// The email address to change to.
+        #[arg(long)]
+        email: Option<String>,
+        /// Your current password.
+        #[arg(long, env = "SMB_CURRENT_PASSWORD", hide_env_values = true)]
+        current_password: Option<String>,
+    },
+    #[clap(about = "Delete your account. This cannot be undone.")]
+    Delete {
+        /// Your account's email address, to confirm without the prompt.
+        #[arg(long, value_name = "EMAIL")]
+        confirm: Option<String>,
+    },
+    #[clap(
+        name = "2fa",
+        about = "Manage two-factor authentication with an authenticator app (TOTP)."
+    )]
+    TwoFactor {
+        #[clap(subcommand)]
+        command: TwoFactorCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TwoFactorCommands {
+    #[clap(about = "Turn on two-factor authentication by scanning a QR code.")]
+    Enable {},
+    #[clap(about = "Turn off two-factor authentication.")]
+    Disable {
+        /// Current code from your authenticator app, or a recovery code.
+        #[arg(long, value_name = "CODE")]
+        otp: Option<String>,
+    },
+    #[clap(
+        name = "recovery-codes",
+        about = "Replace your recovery codes with new ones."
+    )]
+    RecoveryCodes {
+        /// Current code from your authenticator app, or a recovery code.
+        #[arg(long, value_name = "CODE")]
+        otp: Option<String>,
+    },
 }
@@ -0,0 +1,78 @@
+use {
+    crate::{
+        account::lib::session_token,
+        cli::CommandResult,
+        client,
+        token::clear_smb_token::clear_smb_token,
+        ui::{fail_message, prompt::confirm_delete_typed, succeed_message, succeed_symbol},
+    },
+    anyhow::{anyhow, Result},
+    smbcloud_auth::{me::me, remove::remove},
+    smbcloud_model::error_codes::ErrorResponse,
+    smbcloud_network::environment::Environment,
+    spinners::Spinner,
+};
+
+/// Deletes the account after its email is typed back, or passed as
+/// `--confirm` under `--ci`, then forgets the login.
+pub async fn process_delete_account(
+    env: Environment,
+    confirm: Option<String>,
+) -> Result<CommandResult> {
+    let access_token = session_token(env, "Deleting your account")?;
+    let mut spinner = loading_spinner("Loading your account");
+
+    let user = me(env, client(), &access_token).await.map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
+
+    let confirmed = match confirm {
+        Some(email) if email.trim() == user.email => true,
+        Some(email) => {
+            return Err(anyhow!(fail_message(&format!(
+                "--confirm {email} doesn't match the email of this account."
+            ))))
+        }
+        None => confirm_delete_typed(
+            "Account deletion confirmation",
+            &format!(
+                "Your account {} and everything it owns will be deleted.",
+                user.email
+            ),
+            &user.email,
+        )?,
+    };
+    if !confirmed {
+        return Ok(done_result("Cancelled."));
+    }
+
+    let mut spinner = loading_spinner("Deleting your account");
+
+    remove(env, client(), &access_token)
+        .await
+        .map_err(api_error)?;
+    clear_smb_token(env)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Deleted."));
+    Ok(done_result("Done. Your account has been deleted."))
+}
+
+fn loading_spinner(message: &str) -> Spinner {
+    Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message(message),
+    )
+}
+
+fn done_result(message: &str) -> CommandResult {
+    CommandResult {
+        spinner: loading_spinner("Done"),
+        symbol: succeed_symbol(),
+        msg: succeed_message(message),
+    }
+}
+
+fn api_error(error: ErrorResponse) -> anyhow::Error {
+    let ErrorResponse::Error { message, .. } = error;
+    anyhow!(fail_message(&message))
+}
@@ -6,9 +6,9 @@ use crate::{
 };
 use anyhow::{anyhow, Result};
 use dialoguer::{theme::ColorfulTheme, Input, Password};
-use reqwest::{Client, StatusCode};
+use reqwest::StatusCode;
 use smbcloud_model::forgot::{Args, Email, Param, UserUpdatePassword};
-use smbcloud_network::environment::Environment;
+use smbcloud_network::{api_client::shared_client, environment::Environment};
 use smbcloud_networking::{constants::PATH_USERS_PASSWORD, smb_base_url_builder};
 use smbcloud_utils::email_validation;
 use spinners::Spinner;
@@ -36,7 +36,7 @@ pub async fn process_forgot(env: Environment) -> Result<CommandResult> {
         user: Email { email },
     };
 
-    let response = Client::new()
+    let response = shared_client()
         .post(build_smb_forgot_url(env))
         .json(&params)
         .send()
@@ -98,7 +98,7 @@ async fn input_code(env: Environment) -> Result<CommandResult> {
         succeed_message("Updating your password."),
     );
 
-    let response = Client::new()
+    let response = shared_client()
         .put(build_smb_forgot_url(env))
         .json(&params)
         .send()
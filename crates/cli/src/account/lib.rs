@@ -1,12 +1,28 @@
 use {
-    crate::token::smb_token_file_path::smb_token_file_path,
+    crate::{
+        account::loopback::Loopback,
+        token::{
+            get_smb_token::{env_smb_token, get_session_token, has_smb_token, SMB_TOKEN_ENV},
+            renewal::{self, Renewal},
+        },
+    },
     anyhow::{anyhow, Result},
+    base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
     console::style,
     log::debug,
     regex::Regex,
-    reqwest::{Client, Response, StatusCode},
-    smbcloud_model::account::SmbAuthorization,
-    smbcloud_network::environment::Environment,
+    reqwest::{Response, StatusCode, Url},
+    ring::rand::{SecureRandom, SystemRandom},
+    smbcloud_auth::oauth::{
+        get_consent_url::get_consent_url, get_profile::get_profile, get_token::get_token,
+        provider::Provider,
+    },
+    smbcloud_auth_sdk::oidc::OidcSession,
+    smbcloud_model::{
+        account::SmbAuthorization,
+        oauth::{OauthRedirect, UserInfo},
+    },
+    smbcloud_network::{api_client::shared_client, environment::Environment},
     smbcloud_networking::{
         constants::{
             GH_OAUTH_CLIENT_ID, GH_OAUTH_REDIRECT_HOST, GH_OAUTH_REDIRECT_PORT, PATH_AUTHORIZE,
@@ -16,7 +32,6 @@ use {
     },
     spinners::Spinner,
     std::{
-        fs::{create_dir_all, OpenOptions},
         io::{BufRead, BufReader, Write},
         net::{TcpListener, TcpStream},
         sync::mpsc::{self, Receiver, Sender},
@@ -68,6 +83,120 @@ pub async fn authorize_github(env: &Environment) -> Result<SmbAuthorization> {
     }
 }
 
+/// A finished Google sign-in. `profile` is only for showing the user who
+/// they signed in as; smbCloud trusts nothing but `id_token`, which it
+/// verifies with Google itself.
+pub struct GoogleSignIn {
+    pub profile: UserInfo,
+    pub id_token: String,
+}
+
+/// Signs in with Google in the browser.
+pub async fn authorize_google() -> Result<GoogleSignIn> {
+    let (client_id, client_secret) = google_oauth_client()?;
+    let loopback = Loopback::bind().await?;
+    let state = random_state()?;
+    let consent_url = get_consent_url(Provider::Google {
+        client_id: client_id.to_string(),
+        redirect_uri: loopback.redirect_uri().to_string(),
+        state: Some(state.clone()),
+    });
+
+    println!("Opening {} in your browser.", style(&consent_url).cyan());
+    if open::that(&consent_url).is_err() {
+        println!("Couldn't open a browser. Open the link above to continue.");
+    }
+
+    let mut spinner = Spinner::new(
+        spinners::Spinners::BouncingBall,
+        style("⌛ Waiting for the authorization...")
+            .green()
+            .bold()
+            .to_string(),
+    );
+    let redirect = match loopback
+        .wait(|url| parse_google_callback(url, &state))
+        .await
+    {
+        Ok(redirect) => redirect,
+        Err(e) => {
+            spinner.stop_and_persist("🥲", "Google sign-in failed.".into());
+            return Err(e);
+        }
+    };
+    let token = get_token(
+        redirect,
+        client_id.to_string(),
+        client_secret.to_string(),
+        loopback.redirect_uri(),
+    )
+    .await
+    .map_err(|e| anyhow!("{e}"))?;
+    let Some(id_token) = token.id_token else {
+        spinner.stop_and_persist("🥲", "Google sign-in failed.".into());
+        return Err(anyhow!("Google didn't return an ID token."));
+    };
+    let profile = get_profile(token.access_token)
+        .await
+        .map_err(|e| anyhow!("{e}"))?;
+    spinner.stop_and_persist("✅", format!("Signed in to Google as {}.", profile.email));
+    Ok(GoogleSignIn { profile, id_token })
+}
+
+/// The Google OAuth client, set at build time like `CLI_CLIENT_SECRET`.
+fn google_oauth_client() -> Result<(&'static str, &'static str)> {
+    match (
+        option_env!("GOOGLE_OAUTH_CLIENT_ID"),
+        option_env!("GOOGLE_OAUTH_CLIENT_SECRET"),
+    ) {
+        (Some(id), Some(secret)) if !id.is_empty() && !secret.is_empty() => Ok((id, secret)),
+        _ => Err(anyhow!(
+            "This build of the CLI has no Google sign-in configured."
+        )),
+    }
+}
+
+fn random_state() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| anyhow!("Failed to generate the OAuth state."))?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Reads Google's redirect, rejecting one that carries an error or another
+/// login's `state`.
+fn parse_google_callback(url: &str, expected_state: &str) -> Result<OauthRedirect> {
+    let url = Url::parse(url)?;
+    let mut redirect = OauthRedirect {
+        code: None,
+        scope: None,
+        authuser: 0,
+        prompt: String::new(),
+    };
+    let mut state = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "code" => redirect.code = Some(value.into_owned()),
+            "scope" => redirect.scope = Some(value.into_owned()),
+            "authuser" => redirect.authuser = value.parse().unwrap_or_default(),
+            "prompt" => redirect.prompt = value.into_owned(),
+            "state" => state = Some(value.into_owned()),
+            "error" => return Err(anyhow!("Google sign-in failed: {value}.")),
+            _ => {}
+        }
+    }
+    if state.as_deref() != Some(expected_state) {
+        return Err(anyhow!(
+            "The sign-in response doesn't belong to this login."
+        ));
+    }
+    if redirect.code.is_none() {
+        return Err(anyhow!("Google didn't return an authorization code."));
+    }
+    Ok(redirect)
+}
+
 fn setup_oauth_callback_server(tx: Sender<String>) {
     let listener = TcpListener::bind(format!("127.0.0.1:{}", GH_OAUTH_REDIRECT_PORT)).unwrap();
     for stream in listener.incoming() {
@@ -142,7 +271,7 @@ fn handle_connection(mut stream: TcpStream, tx: Sender<String>) {
 }
 
 pub async fn process_connect_github(env: Environment, code: String) -> Result<SmbAuthorization> {
-    let response = Client::new()
+    let response = shared_client()
         .post(build_authorize_smb_url(env))
         .body(format!("gh_code={}", code))
         .header("Accept", "application/json")
@@ -222,24 +351,57 @@ pub async fn save_token(env: Environment, response: &Response) -> Result<()> {
     }
 }
 
+/// Saves `token` to the keyring or the encrypted token file, see
+/// `token::credential_store`.
 pub async fn store_token(env: Environment, token: String) -> Result<()> {
-    match home::home_dir() {
-        Some(path) => {
-            debug!("{}", path.to_str().unwrap());
-            create_dir_all(path.join(env.smb_dir()))?;
-            let mut file = OpenOptions::new()
-                .create(true)
-                .truncate(true)
-                .write(true)
-                .open([path.to_str().unwrap(), "/", &env.smb_dir(), "/token"].join(""))?;
-            file.write_all(token.as_bytes())?;
-            Ok(())
-        }
-        None => Err(anyhow!("Failed to get home directory.")),
-    }
+    renewal::store(env, &token, None)
+}
+
+/// Saves a session from `smb login --sso`, along with what renewing it
+/// takes, see `token::renewal`.
+pub async fn store_session(env: Environment, session: &OidcSession) -> Result<()> {
+    renewal::store(
+        env,
+        &renewal::authorization(session),
+        Renewal::from_session(session).as_ref(),
+    )
 }
 
 pub fn is_logged_in(env: Environment) -> bool {
-    // Check if token file exists
-    smb_token_file_path(env).is_some()
+    has_smb_token(env)
+}
+
+/// The login session, for account changes an access token must never make.
+/// `what` names the change for the error, e.g. "Deleting your account".
+pub fn session_token(env: Environment, what: &str) -> Result<String> {
+    if env_smb_token().is_some() {
+        return Err(anyhow!(
+            "{what} needs a login session. Unset {SMB_TOKEN_ENV} and run `smb login`."
+        ));
+    }
+    get_session_token(env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn google_callback_must_carry_a_code_and_this_logins_state() {
+        let redirect = parse_google_callback(
+            "http://127.0.0.1/callback?state=s1&code=4%2F0A&scope=email&authuser=1",
+            "s1",
+        )
+        .unwrap();
+        assert_eq!(redirect.code.as_deref(), Some("4/0A"));
+        assert_eq!(redirect.authuser, 1);
+
+        assert!(parse_google_callback("http://127.0.0.1/callback?state=s2&code=c", "s1").is_err());
+        assert!(parse_google_callback("http://127.0.0.1/callback?state=s1", "s1").is_err());
+        assert!(parse_google_callback(
+            "http://127.0.0.1/callback?error=access_denied&state=s1",
+            "s1"
+        )
+        .is_err());
+    }
 }
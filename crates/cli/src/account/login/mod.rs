@@ -1,2 +1,4 @@
 pub mod process;
+pub mod sso;
 pub use process::*;
+pub use sso::process_login_sso;
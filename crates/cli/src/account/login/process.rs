@@ -1,22 +1,24 @@
 use {
     crate::{
         account::{
-            lib::{authorize_github, store_token},
+            lib::{authorize_github, authorize_google, store_token, GoogleSignIn},
             signup::{do_signup, signup_with_email, SignupMethod},
         },
         ci::{interactive_message, is_ci},
         cli::CommandResult,
         client,
         token::is_logged_in::is_logged_in as is_logged_in_async,
-        ui::{fail_message, fail_symbol, succeed_message, succeed_symbol},
+        ui::{fail_message, fail_symbol, prompt, succeed_message, succeed_symbol},
     },
     anyhow::{anyhow, Result},
     console::style,
     dialoguer::{console::Term, theme::ColorfulTheme, Confirm, Input, Password, Select},
     log::debug,
-    reqwest::Client,
     smbcloud_auth::{
-        check_email::check_email, login::login,
+        check_email::check_email,
+        device_authorization::{poll_device_token, request_device_code},
+        login::login_with_otp,
+        oauth::google_login::{google_login, google_signup},
         resend_email_verification::resend_email_verification as account_resend_email_verification,
         resend_reset_password_instruction::resend_reset_password_instruction as account_resend_reset_password_instruction,
         reset_password::reset_password as account_reset_password,
@@ -26,22 +28,39 @@ use {
             ErrorCode::{
                 self as AccountErrorCode, EmailAlreadyExist, EmailConfirmationFailed,
                 EmailNotFound, EmailUnverified, GithubNotLinked, HostedMailAccountUnverified,
-                InvalidPassword, PasswordNotSet,
+                InvalidOtp, InvalidPassword, OtpRequired, PasswordNotSet,
             },
             GithubInfo, SmbAuthorization, User,
         },
+        error_codes::ErrorResponse,
         login::{AccountStatus, LoginArgs},
         signup::{GithubEmail, Provider, SignupGithubParams, SignupUserGithub},
     },
-    smbcloud_network::environment::Environment,
+    smbcloud_network::{api_client::shared_client, environment::Environment},
     smbcloud_networking::{constants::PATH_LINK_GITHUB_ACCOUNT, smb_base_url_builder},
     smbcloud_utils::email_validation,
     spinners::Spinner,
 };
 
+/// Only the email login can take a TOTP code. The OAuth logins can't send
+/// one, so they stop here.
+const OTP_EMAIL_LOGIN_ONLY: &str =
+    "Two-factor authentication is on for this account. Log in with your email and password, or with `smb login --device`.";
+
 pub async fn process_login(env: Environment, is_logged_in: Option<bool>) -> Result<CommandResult> {
+    process_login_with_otp(env, is_logged_in, None).await
+}
+
+/// [`process_login`] with the code for an account with two-factor
+/// authentication on (`smb login --otp`). Without it, the email login asks
+/// for one when the account needs it.
+pub async fn process_login_with_otp(
+    env: Environment,
+    is_logged_in: Option<bool>,
+    otp: Option<String>,
+) -> Result<CommandResult> {
     // Login is an interactive flow (provider choice, credentials, OAuth). In CI
-    // the token must be provisioned ahead of time at ~/.smb/token.
+    // the token comes from SMB_TOKEN or is provisioned ahead of time.
     if is_ci() {
         return Err(anyhow!(fail_message(&interactive_message("Login"))));
     }
@@ -66,7 +85,11 @@ pub async fn process_login(env: Environment, is_logged_in: Option<bool>) -> Resu
         });
     }
 
-    let signup_methods = vec![SignupMethod::Email, SignupMethod::GitHub];
+    let signup_methods = vec![
+        SignupMethod::Email,
+        SignupMethod::GitHub,
+        SignupMethod::Google,
+    ];
     let selection = match Select::with_theme(&ColorfulTheme::default())
         .items(&signup_methods)
         .default(0)
@@ -81,11 +104,59 @@ pub async fn process_login(env: Environment, is_logged_in: Option<bool>) -> Resu
     };
 
     match selection {
-        SignupMethod::Email => login_with_email(env).await,
+        SignupMethod::Email => login_with_email(env, otp).await,
         SignupMethod::GitHub => login_with_github(env).await,
+        SignupMethod::Google => login_with_google(env).await,
     }
 }
 
+/// Device-code login (`smb login --device`) for SSH sessions and containers,
+/// where the GitHub flow's browser and localhost callback can't work. Nothing
+/// is prompted, so it also runs under `--ci`.
+pub async fn process_login_device(env: Environment) -> Result<CommandResult> {
+    if is_logged_in_async(env).await? {
+        return Ok(CommandResult {
+            spinner: Spinner::new(
+                spinners::Spinners::SimpleDotsScrolling,
+                succeed_message("Loading"),
+            ),
+            symbol: fail_symbol(),
+            msg: fail_message("You are already logged in. Please logout first."),
+        });
+    }
+
+    let authorization = request_device_code(env, client())
+        .await
+        .map_err(|e| anyhow!(fail_message(&e.to_string())))?;
+    println!(
+        "Open {} and enter the code {}",
+        style(&authorization.verification_uri).cyan(),
+        style(&authorization.user_code).bold()
+    );
+    if let Some(uri) = &authorization.verification_uri_complete {
+        println!("or open {} directly.", style(uri).cyan());
+    }
+
+    let mut spinner = Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message("Waiting for you to approve the login"),
+    );
+    let token = match poll_device_token(env, client(), &authorization).await {
+        Ok(token) => token,
+        Err(e) => {
+            spinner.stop_and_persist(&fail_symbol(), fail_message("Login failed."));
+            return Err(anyhow!(fail_message(&e.to_string())));
+        }
+    };
+    store_token(env, token.authorization()).await?;
+
+    Ok(CommandResult {
+        spinner,
+        symbol: succeed_symbol(),
+        msg: succeed_message("You are logged in!"),
+    })
+}
+
 async fn login_with_github(env: Environment) -> Result<CommandResult> {
     match authorize_github(&env).await {
         Ok(result) => process_authorization(env, result).await,
@@ -96,22 +167,126 @@ async fn login_with_github(env: Environment) -> Result<CommandResult> {
     }
 }
 
+/// Google login, which also signs up a Google account that has no smbCloud
+/// account yet. smbCloud verifies the Google ID token and picks the account
+/// by the email inside it.
+pub async fn login_with_google(env: Environment) -> Result<CommandResult> {
+    let sign_in = authorize_google()
+        .await
+        .map_err(|e| anyhow!("Failed to authorize your Google account. {e}"))?;
+    if !sign_in.profile.verified_email {
+        return Err(anyhow!(fail_message(
+            "Your Google account's email address is not verified."
+        )));
+    }
+
+    let mut spinner = Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message("Checking your account"),
+    );
+    let account_status = google_login(env, client(), &sign_in.id_token)
+        .await
+        .map_err(|e| anyhow!(fail_message(&e.to_string())))?;
+
+    match account_status {
+        AccountStatus::NotFound => {
+            spinner.stop_and_persist(&succeed_symbol(), succeed_message("Account not found."));
+            create_google_account(env, sign_in).await
+        }
+        account_status => google_result(env, spinner, account_status, sign_in.profile.email).await,
+    }
+}
+
+async fn create_google_account(env: Environment, sign_in: GoogleSignIn) -> Result<CommandResult> {
+    let confirm = match Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "Do you want to create a new account for {}?",
+            sign_in.profile.email
+        ))
+        .interact()
+    {
+        Ok(confirm) => confirm,
+        Err(_) => {
+            let error = anyhow!("Invalid input.");
+            return Err(error);
+        }
+    };
+
+    if !confirm {
+        let spinner = Spinner::new(
+            spinners::Spinners::SimpleDotsScrolling,
+            succeed_message("Cancelled."),
+        );
+        return Ok(CommandResult {
+            spinner,
+            symbol: succeed_symbol(),
+            msg: succeed_message("Cancelled."),
+        });
+    }
+
+    let spinner = Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message("Creating your account"),
+    );
+    let account_status = google_signup(env, client(), &sign_in.id_token)
+        .await
+        .map_err(|e| anyhow!(fail_message(&e.to_string())))?;
+    google_result(env, spinner, account_status, sign_in.profile.email).await
+}
+
+/// Stores the session from a Google login or signup, or explains why there
+/// is none.
+async fn google_result(
+    env: Environment,
+    mut spinner: Spinner,
+    account_status: AccountStatus,
+    email: String,
+) -> Result<CommandResult> {
+    match account_status {
+        AccountStatus::Ready { access_token } => {
+            store_token(env, access_token).await?;
+            Ok(CommandResult {
+                spinner,
+                symbol: succeed_symbol(),
+                msg: succeed_message(&format!("You are logged in with Google as {email}.")),
+            })
+        }
+        AccountStatus::NotFound => {
+            spinner.stop_and_persist(&fail_symbol(), fail_message("Account not found."));
+            Err(anyhow!(fail_message(
+                "No account was created. Please try again."
+            )))
+        }
+        AccountStatus::Incomplete { status } => {
+            action_on_account_status(&env, spinner, status, Some(email), None).await
+        }
+        AccountStatus::OtpRequired => {
+            spinner.stop_and_persist(&fail_symbol(), fail_message("Two-factor code required."));
+            Err(anyhow!(fail_message(OTP_EMAIL_LOGIN_ONLY)))
+        }
+    }
+}
+
 async fn process_authorization(env: Environment, auth: SmbAuthorization) -> Result<CommandResult> {
     // Handle the account state returned by the OAuth flow before treating it as a login.
     if let Some(error_code) = auth.error_code {
         debug!("{}", error_code);
         match error_code {
             EmailNotFound => return create_new_account(env, auth.user_email, auth.user_info).await,
-            EmailUnverified => return send_email_verification(env, auth.user).await,
+            EmailUnverified => {
+                return send_email_verification(env, auth.user.map(|user| user.email)).await
+            }
             PasswordNotSet => {
                 let error = anyhow!("Password not set.");
                 return Err(error);
             }
             GithubNotLinked => return connect_github_account(env, auth).await,
+            OtpRequired => return Err(anyhow!(fail_message(OTP_EMAIL_LOGIN_ONLY))),
             EmailConfirmationFailed
             | EmailAlreadyExist
             | InvalidPassword
-            | HostedMailAccountUnverified => {
+            | HostedMailAccountUnverified
+            | InvalidOtp => {
                 let error = anyhow!(error_code.to_string());
                 return Err(error);
             }
@@ -169,7 +344,7 @@ async fn create_new_account(
                 email: email.email,
                 authorizations_attributes: vec![Provider {
                     uid: info.id.to_string(),
-                    provider: 0,
+                    provider: Provider::GITHUB,
                 }],
             },
         };
@@ -180,8 +355,8 @@ async fn create_new_account(
     Err(anyhow!("GitHub returned incomplete account details."))
 }
 
-async fn send_email_verification(env: Environment, user: Option<User>) -> Result<CommandResult> {
-    if let Some(user) = user {
+async fn send_email_verification(env: Environment, email: Option<String>) -> Result<CommandResult> {
+    if let Some(email) = email {
         let confirm = match Confirm::with_theme(&ColorfulTheme::default())
             .with_prompt("Do you want to send a new verification email?")
             .interact()
@@ -204,14 +379,14 @@ async fn send_email_verification(env: Environment, user: Option<User>) -> Result
                 msg: succeed_message("Cancelled."),
             });
         }
-        resend_email_verification(env, user).await
+        resend_email_verification(env, email).await
     } else {
         let error = anyhow!("Failed to get user.");
         Err(error)
     }
 }
 
-async fn resend_email_verification(env: Environment, user: User) -> Result<CommandResult> {
+async fn resend_email_verification(env: Environment, email: String) -> Result<CommandResult> {
     let spinner = Spinner::new(
         spinners::Spinners::SimpleDotsScrolling,
         style("Sending verification email...")
@@ -219,7 +394,7 @@ async fn resend_email_verification(env: Environment, user: User) -> Result<Comma
             .bold()
             .to_string(),
     );
-    match account_resend_email_verification(env, client(), user.email).await {
+    match account_resend_email_verification(env, client(), email).await {
         Ok(_) => Ok(CommandResult {
             spinner,
             symbol: succeed_symbol(),
@@ -261,7 +436,7 @@ async fn connect_github_account(env: Environment, auth: SmbAuthorization) -> Res
         succeed_message("Linking your GitHub account..."),
     );
 
-    let response = Client::new()
+    let response = shared_client()
         .post(build_smb_connect_github_url(env))
         .json(&auth)
         .header("Accept", "application/json")
@@ -282,7 +457,7 @@ async fn connect_github_account(env: Environment, auth: SmbAuthorization) -> Res
     }
 }
 
-async fn login_with_email(env: Environment) -> Result<CommandResult> {
+async fn login_with_email(env: Environment, otp: Option<String>) -> Result<CommandResult> {
     println!("Enter your login details.");
     let username = match Input::<String>::with_theme(&ColorfulTheme::default())
         .with_prompt("Email")
@@ -315,7 +490,7 @@ async fn login_with_email(env: Environment) -> Result<CommandResult> {
                         return Err(error);
                     }
                 };
-                do_process_login(env, LoginArgs { username, password }).await
+                do_process_login(env, LoginArgs { username, password }, otp).await
             }
         }
         Err(_) => Err(anyhow!(fail_message(
@@ -324,16 +499,54 @@ async fn login_with_email(env: Environment) -> Result<CommandResult> {
     }
 }
 
-async fn do_process_login(env: Environment, args: LoginArgs) -> Result<CommandResult> {
-    let spinner = Spinner::new(
+async fn do_process_login(
+    env: Environment,
+    args: LoginArgs,
+    otp: Option<String>,
+) -> Result<CommandResult> {
+    let mut spinner = Spinner::new(
         spinners::Spinners::SimpleDotsScrolling,
         succeed_message("Loading"),
     );
-    let account_status = match login(env, client(), args.username, args.password).await {
+    let has_otp = otp.is_some();
+    let mut account_status = match login_with_otp(
+        env,
+        client(),
+        args.username.clone(),
+        args.password.clone(),
+        otp,
+    )
+    .await
+    {
         Ok(response) => response,
+        // A wrong `--otp` comes back as an error with the reason.
+        Err(ErrorResponse::Error { message, .. }) if has_otp => {
+            return Err(anyhow!(fail_message(&message)))
+        }
         Err(_) => return Err(anyhow!(fail_message("Check your internet connection."))),
     };
 
+    // Without `--otp`, ask for the code once the password is known to be right.
+    if matches!(account_status, AccountStatus::OtpRequired) && !has_otp {
+        spinner.stop_and_persist(
+            &succeed_symbol(),
+            succeed_message("Two-factor authentication is on for this account."),
+        );
+        let otp = prompt::input_required("Code from your authenticator app, or a recovery code")?;
+        spinner = Spinner::new(
+            spinners::Spinners::SimpleDotsScrolling,
+            succeed_message("Loading"),
+        );
+        account_status =
+            match login_with_otp(env, client(), args.username, args.password, Some(otp)).await {
+                Ok(response) => response,
+                Err(ErrorResponse::Error { message, .. }) => {
+                    spinner.stop_and_persist(&fail_symbol(), fail_message("Login failed."));
+                    return Err(anyhow!(fail_message(&message)));
+                }
+            };
+    }
+
     match account_status {
         AccountStatus::Ready { access_token } => {
             store_token(env, access_token).await?;
@@ -347,6 +560,10 @@ async fn do_process_login(env: Environment, args: LoginArgs) -> Result<CommandRe
         AccountStatus::Incomplete { status } => {
             action_on_account_status(&env, spinner, status, None, None).await
         }
+        AccountStatus::OtpRequired => {
+            spinner.stop_and_persist(&fail_symbol(), fail_message("Login failed."));
+            Err(anyhow!(fail_message(&InvalidOtp.to_string())))
+        }
     }
 }
 
@@ -374,14 +591,14 @@ async fn after_checking_email_step(
                         &succeed_symbol(),
                         succeed_message("Email not verified. Please verify your email."),
                     );
-                    send_email_verification(*env, result.user).await
+                    send_email_verification(*env, result.user.map(|user| user.email)).await
                 }
                 PasswordNotSet => {
                     spinner.stop_and_persist(
                         &succeed_symbol(),
                         succeed_message("Password not set. Please reset your password."),
                     );
-                    send_reset_password(*env, result.user).await
+                    send_reset_password(*env, result.user.map(|user| user.email)).await
                 }
                 _ => {
                     spinner.stop_and_persist(&fail_symbol(), fail_message("An error occurred."));
@@ -400,6 +617,8 @@ async fn action_on_account_status(
     username: Option<String>,
     user: Option<User>,
 ) -> Result<CommandResult> {
+    // Without the user record (a Google login), the username is the email.
+    let email = user.map(|user| user.email).or_else(|| username.clone());
     match error_code {
         EmailNotFound => {
             spinner.stop_and_persist(
@@ -413,14 +632,14 @@ async fn action_on_account_status(
                 &succeed_symbol(),
                 succeed_message("Email not verified. Please verify your email."),
             );
-            send_email_verification(*env, user).await
+            send_email_verification(*env, email).await
         }
         PasswordNotSet => {
             spinner.stop_and_persist(
                 &succeed_symbol(),
                 succeed_message("Password not set. Please reset your password."),
             );
-            send_reset_password(*env, user).await
+            send_reset_password(*env, email).await
         }
         _ => {
             spinner.stop_and_persist(&fail_symbol(), fail_message("An error occurred."));
@@ -429,8 +648,8 @@ async fn action_on_account_status(
     }
 }
 
-async fn send_reset_password(env: Environment, user: Option<User>) -> Result<CommandResult> {
-    if let Some(user) = user {
+async fn send_reset_password(env: Environment, email: Option<String>) -> Result<CommandResult> {
+    if let Some(email) = email {
         let confirm = match Confirm::with_theme(&ColorfulTheme::default())
             .with_prompt("Do you want to reset your password?")
             .interact()
@@ -453,20 +672,23 @@ async fn send_reset_password(env: Environment, user: Option<User>) -> Result<Com
                 msg: succeed_message("Cancelled."),
             });
         }
-        resend_reset_password_instruction(env, user).await
+        resend_reset_password_instruction(env, email).await
     } else {
         let error = anyhow!("Failed to get user.");
         Err(error)
     }
 }
 
-async fn resend_reset_password_instruction(env: Environment, user: User) -> Result<CommandResult> {
+async fn resend_reset_password_instruction(
+    env: Environment,
+    email: String,
+) -> Result<CommandResult> {
     let mut spinner = Spinner::new(
         spinners::Spinners::SimpleDotsScrolling,
         succeed_message("Sending reset password instruction..."),
     );
 
-    match account_resend_reset_password_instruction(env, client(), user.email).await {
+    match account_resend_reset_password_instruction(env, client(), email).await {
         Ok(_) => {
             spinner.stop_and_persist(
                 "✅",
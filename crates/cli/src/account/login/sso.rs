@@ -0,0 +1,168 @@
+//! Browser login through smbCloud Auth (`smb login --sso`): the OIDC
+//! authorization code flow with PKCE, redirected to the loopback listener in
+//! [`crate::account::loopback`].
+
+use {
+    crate::{
+        account::{lib::store_session, loopback::Loopback},
+        ci::{interactive_message, is_ci},
+        cli::CommandResult,
+        client, session_config,
+        tenant::tenant_client::get_tenant,
+        token::{is_logged_in::is_logged_in, renewal::authorization},
+        ui::{description, fail_message, fail_symbol, succeed_message, succeed_symbol},
+    },
+    anyhow::{anyhow, Result},
+    chrono::Utc,
+    console::style,
+    log::debug,
+    smbcloud_auth_sdk::oidc::{
+        build_authorization_request, exchange_code, parse_callback_url, AuthorizationRequest,
+        CallbackPayload, OidcSession,
+    },
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::constants::CLI_OIDC_CLIENT_ID,
+    spinners::Spinner,
+};
+
+pub async fn process_login_sso(env: Environment) -> Result<CommandResult> {
+    // The consent screen needs a browser on this machine; `--device` covers
+    // the rest.
+    if is_ci() {
+        return Err(anyhow!(fail_message(&interactive_message("SSO login"))));
+    }
+    if is_logged_in(env).await? {
+        return Ok(CommandResult {
+            spinner: Spinner::new(
+                spinners::Spinners::SimpleDotsScrolling,
+                succeed_message("Loading"),
+            ),
+            symbol: fail_symbol(),
+            msg: fail_message("You are already logged in. Please logout first."),
+        });
+    }
+
+    let loopback = Loopback::bind().await?;
+    let request =
+        build_authorization_request(env, CLI_OIDC_CLIENT_ID, loopback.redirect_uri().to_string())
+            .map_err(|e| anyhow!(fail_message(&e.to_string())))?;
+
+    println!(
+        "Opening {} in your browser.",
+        style(&request.authorize_url).cyan()
+    );
+    if open::that(&request.authorize_url).is_err() {
+        println!("Couldn't open a browser. Open the link above to continue.");
+    }
+
+    let mut spinner = Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message("Waiting for you to sign in"),
+    );
+    let callback = match loopback.wait(|url| check_callback(url, &request)).await {
+        Ok(callback) => callback,
+        Err(e) => {
+            spinner.stop_and_persist(&fail_symbol(), fail_message("Login failed."));
+            return Err(anyhow!(fail_message(&format!(
+                "{e} Run `smb login --sso` again."
+            ))));
+        }
+    };
+
+    let token = exchange_code(
+        env,
+        CLI_OIDC_CLIENT_ID,
+        &request.redirect_uri,
+        &callback.code,
+        &request.code_verifier,
+    )
+    .await
+    .map_err(|e| {
+        spinner.stop_and_persist(&fail_symbol(), fail_message("Login failed."));
+        anyhow!(fail_message(&e.to_string()))
+    })?;
+    let session = OidcSession::from_token_response(token, Utc::now().timestamp());
+    store_session(env, &session).await?;
+    let access_token = authorization(&session);
+
+    let msg = match callback.tenant_id {
+        Some(tenant_id) => use_tenant(env, access_token, tenant_id).await,
+        None => "You are logged in!".to_string(),
+    };
+    Ok(CommandResult {
+        spinner,
+        symbol: succeed_symbol(),
+        msg: succeed_message(&msg),
+    })
+}
+
+/// Makes the tenant picked on the consent screen the session's tenant. The
+/// login itself already succeeded, so a failure here only warns.
+async fn use_tenant(env: Environment, access_token: String, tenant_id: String) -> String {
+    let result = match get_tenant(env, client(), access_token, tenant_id.clone()).await {
+        Ok(tenant) => {
+            let name = tenant.name.clone();
+            session_config::set_current_tenant(env, tenant).map(|_| name)
+        }
+        Err(e) => Err(anyhow!(e.to_string())),
+    };
+    match result {
+        Ok(name) => format!("You are logged in to tenant \"{name}\"."),
+        Err(e) => {
+            debug!("Failed to select tenant {tenant_id}: {e}");
+            println!(
+                "{}",
+                description(&format!(
+                    "Couldn't select tenant #{tenant_id}. Run `smb tenant use --id {tenant_id}`."
+                ))
+            );
+            "You are logged in!".to_string()
+        }
+    }
+}
+
+/// Parses the redirect and checks it answers this login: a callback with
+/// another `state` was not started here and must not be exchanged.
+fn check_callback(url: &str, request: &AuthorizationRequest) -> Result<CallbackPayload> {
+    let callback = parse_callback_url(url).map_err(|e| anyhow!(e.to_string()))?;
+    if callback.state != request.state {
+        return Err(anyhow!(
+            "The sign-in response doesn't belong to this login."
+        ));
+    }
+    Ok(callback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> AuthorizationRequest {
+        AuthorizationRequest {
+            authorize_url: "https://api.smbcloud.xyz/v1/client/oauth/authorize".to_string(),
+            redirect_uri: "http://127.0.0.1:4000/callback".to_string(),
+            state: "expected".to_string(),
+            code_verifier: "verifier".to_string(),
+        }
+    }
+
+    #[test]
+    fn callback_must_carry_this_logins_state() {
+        let callback = check_callback(
+            "http://127.0.0.1/callback?code=c&state=expected&tenant_id=7",
+            &request(),
+        )
+        .unwrap();
+        assert_eq!(callback.code, "c");
+        assert_eq!(callback.tenant_id.as_deref(), Some("7"));
+
+        assert!(
+            check_callback("http://127.0.0.1/callback?code=c&state=forged", &request()).is_err()
+        );
+        assert!(check_callback(
+            "http://127.0.0.1/callback?error=access_denied&state=expected",
+            &request()
+        )
+        .is_err());
+    }
+}
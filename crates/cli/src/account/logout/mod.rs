@@ -2,18 +2,19 @@ use {
     crate::{
         cli::CommandResult,
         client,
-        token::{get_smb_token::get_smb_token, smb_token_file_path::smb_token_file_path},
+        token::{clear_smb_token::clear_smb_token, get_smb_token::get_session_token},
         ui::{fail_message, fail_symbol, prompt, succeed_message, succeed_symbol},
     },
     anyhow::{anyhow, Result},
     smbcloud_auth::logout::logout,
     smbcloud_network::environment::Environment,
     spinners::Spinner,
-    std::fs,
 };
 
+/// Logs out of the login stored for the active profile. A token in
+/// `SMB_TOKEN` is left alone; revoke it with `smb token revoke`.
 pub async fn process_logout(env: Environment) -> Result<CommandResult> {
-    if let Some(token_path) = smb_token_file_path(env) {
+    if get_session_token(env).is_ok() {
         // In --ci mode this defaults to "yes": running `smb --ci logout` is an
         // explicit request, so proceed without prompting.
         let confirm = prompt::confirm("Do you want to logout? y/n", true)?;
@@ -35,7 +36,7 @@ pub async fn process_logout(env: Environment) -> Result<CommandResult> {
 
         match do_process_logout(env).await {
             Ok(_) => {
-                fs::remove_file(token_path)?;
+                clear_smb_token(env)?;
                 Ok(CommandResult {
                     spinner,
                     symbol: succeed_symbol(),
@@ -57,7 +58,7 @@ pub async fn process_logout(env: Environment) -> Result<CommandResult> {
 }
 
 async fn do_process_logout(env: Environment) -> Result<()> {
-    let token = get_smb_token(env)?;
+    let token = get_session_token(env)?;
     match logout(env, client(), token).await {
         Ok(_) => Ok(()),
         Err(e) => {
@@ -0,0 +1,117 @@
+//! One-shot HTTP listener on the loopback interface for browser logins
+//! (`smb login --sso`, Google): the provider sends the browser back to it
+//! with the authorization code.
+
+use {
+    anyhow::{anyhow, Result},
+    log::debug,
+    std::{net::Ipv4Addr, time::Duration},
+    tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::{TcpListener, TcpStream},
+        time::timeout,
+    },
+};
+
+const CALLBACK_PATH: &str = "/callback";
+/// How long to wait for the browser before giving up.
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub(crate) struct Loopback {
+    listener: TcpListener,
+    redirect_uri: String,
+}
+
+impl Loopback {
+    /// Binds port 0, so the OS picks a free port, which the redirect URI then
+    /// names.
+    pub(crate) async fn bind() -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let redirect_uri = format!(
+            "http://{}:{}{CALLBACK_PATH}",
+            Ipv4Addr::LOCALHOST,
+            listener.local_addr()?.port()
+        );
+        Ok(Self {
+            listener,
+            redirect_uri,
+        })
+    }
+
+    pub(crate) fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// Serves until the browser comes back to the redirect URI, and hands
+    /// the full callback URL to `check`. Other requests (a favicon, say) get
+    /// a 404.
+    pub(crate) async fn wait<T>(&self, check: impl Fn(&str) -> Result<T>) -> Result<T> {
+        timeout(CALLBACK_TIMEOUT, self.serve(check))
+            .await
+            .map_err(|_| anyhow!("Timed out waiting for the browser."))?
+    }
+
+    async fn serve<T>(&self, check: impl Fn(&str) -> Result<T>) -> Result<T> {
+        loop {
+            let (mut stream, _) = self.listener.accept().await?;
+            let mut request_line = String::new();
+            BufReader::new(&mut stream)
+                .read_line(&mut request_line)
+                .await?;
+            debug!("Request: {:#?}", request_line);
+
+            let Some(target) = callback_target(&request_line) else {
+                respond(&mut stream, "404 Not Found", "Not found.").await;
+                continue;
+            };
+            let result = check(&format!("http://{}{target}", Ipv4Addr::LOCALHOST));
+            let page = match &result {
+                Ok(_) => "You are logged in. Return to the terminal.",
+                Err(_) => "Login failed. Return to the terminal for details.",
+            };
+            respond(&mut stream, "200 OK", page).await;
+            return result;
+        }
+    }
+}
+
+/// The path and query of a `GET /callback?...` request line.
+fn callback_target(request_line: &str) -> Option<&str> {
+    let mut parts = request_line.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    let target = parts.next()?;
+    let path = target.split('?').next()?;
+    (path == CALLBACK_PATH).then_some(target)
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, message: &str) {
+    let body = format!(
+        "<!DOCTYPE html><html><head><meta charset='utf-8'><title>smbCloud</title></head>\
+         <body><p>{message}</p></body></html>"
+    );
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        debug!("Failed to answer the browser: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_callback_path_is_accepted() {
+        assert_eq!(
+            callback_target("GET /callback?code=c&state=s HTTP/1.1\r\n"),
+            Some("/callback?code=c&state=s")
+        );
+        assert_eq!(callback_target("GET /favicon.ico HTTP/1.1\r\n"), None);
+        assert_eq!(callback_target("POST /callback?code=c HTTP/1.1\r\n"), None);
+        assert_eq!(callback_target(""), None);
+    }
+}
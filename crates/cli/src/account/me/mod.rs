@@ -1,5 +1,6 @@
 use crate::account::lib::is_logged_in;
 use crate::client;
+use crate::profile;
 use crate::token::get_smb_token::get_smb_token;
 use crate::{
     cli::CommandResult,
@@ -34,9 +35,9 @@ pub async fn process_me(env: Environment) -> Result<CommandResult> {
         Ok(user) => {
             spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
             if is_tui() {
-                show_user_tui(&user).map_err(|e| anyhow!(e))?;
+                show_user_tui(&user, &profile::current()).map_err(|e| anyhow!(e))?;
             } else {
-                plain::render_user(&user);
+                plain::render_user(&user, &profile::current());
             }
             Ok(CommandResult {
                 spinner: Spinner::new(
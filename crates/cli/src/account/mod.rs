@@ -1,12 +1,24 @@
 pub mod cli;
+pub mod delete;
 pub mod forgot;
 pub mod lib;
 pub mod login;
 pub mod logout;
+mod loopback;
 pub mod me;
 pub mod signup;
+pub mod two_factor;
+pub mod update;
 
-use self::{cli::Commands, forgot::process_forgot, login::process_login, signup::process_signup};
+use self::{
+    cli::Commands,
+    delete::process_delete_account,
+    forgot::process_forgot,
+    login::{process_login_device, process_login_sso, process_login_with_otp},
+    signup::process_signup,
+    two_factor::process_two_factor,
+    update::{process_change_email, process_change_password},
+};
 use crate::cli::CommandResult;
 use anyhow::Result;
 use logout::process_logout;
@@ -15,8 +27,20 @@ use smbcloud_network::environment::Environment;
 pub async fn process_account(env: Environment, commands: Commands) -> Result<CommandResult> {
     match commands {
         Commands::Signup {} => process_signup(env).await,
-        Commands::Login {} => process_login(env, None).await,
+        Commands::Login { sso: true, .. } => process_login_sso(env).await,
+        Commands::Login { device: true, .. } => process_login_device(env).await,
+        Commands::Login { otp, .. } => process_login_with_otp(env, None, otp).await,
         Commands::Logout {} => process_logout(env).await,
         Commands::Forgot {} => process_forgot(env).await,
+        Commands::Password {
+            current_password,
+            new_password,
+        } => process_change_password(env, current_password, new_password).await,
+        Commands::Email {
+            email,
+            current_password,
+        } => process_change_email(env, email, current_password).await,
+        Commands::Delete { confirm } => process_delete_account(env, confirm).await,
+        Commands::TwoFactor { command } => process_two_factor(env, command).await,
     }
 }
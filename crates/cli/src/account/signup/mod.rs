@@ -7,6 +7,7 @@ pub use process::*;
 pub enum SignupMethod {
     Email,
     GitHub,
+    Google,
 }
 
 impl Display for SignupMethod {
@@ -14,6 +15,7 @@ impl Display for SignupMethod {
         match self {
             Self::Email => write!(f, "Email and password"),
             Self::GitHub => write!(f, "GitHub Account (beta)"),
+            Self::Google => write!(f, "Google Account (beta)"),
         }
     }
 }
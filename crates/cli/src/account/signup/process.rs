@@ -1,21 +1,21 @@
 use {
     super::SignupMethod,
     crate::{
-        account::lib::authorize_github,
+        account::{lib::authorize_github, login::login_with_google},
         ci::{interactive_message, is_ci},
         cli::CommandResult,
         client,
-        token::smb_token_file_path::smb_token_file_path,
+        token::get_smb_token::has_smb_token,
         ui::{fail_message, fail_symbol, succeed_message, succeed_symbol},
     },
     anyhow::{anyhow, Result},
     dialoguer::{console::Term, theme::ColorfulTheme, Input, Password, Select},
     log::debug,
-    reqwest::{Client, StatusCode},
+    reqwest::StatusCode,
     serde::Serialize,
     smbcloud_auth::signup::signup,
     smbcloud_model::signup::SignupResult,
-    smbcloud_network::environment::Environment,
+    smbcloud_network::{api_client::shared_client, environment::Environment},
     smbcloud_networking::{constants::PATH_USERS, smb_base_url_builder},
     smbcloud_utils::email_validation,
     spinners::Spinner,
@@ -27,8 +27,7 @@ pub async fn process_signup(env: Environment) -> Result<CommandResult> {
         return Err(anyhow!(fail_message(&interactive_message("Sign-up"))));
     }
 
-    // Check if token file exists
-    if smb_token_file_path(env).is_some() {
+    if has_smb_token(env) {
         return Ok(CommandResult {
             spinner: Spinner::new(
                 spinners::Spinners::SimpleDotsScrolling,
@@ -39,7 +38,11 @@ pub async fn process_signup(env: Environment) -> Result<CommandResult> {
         });
     }
 
-    let signup_methods = vec![SignupMethod::Email, SignupMethod::GitHub];
+    let signup_methods = vec![
+        SignupMethod::Email,
+        SignupMethod::GitHub,
+        SignupMethod::Google,
+    ];
     let selection = Select::with_theme(&ColorfulTheme::default())
         .items(&signup_methods)
         .default(0)
@@ -50,6 +53,8 @@ pub async fn process_signup(env: Environment) -> Result<CommandResult> {
     match selection {
         SignupMethod::Email => signup_with_email(env, None).await,
         SignupMethod::GitHub => signup_with_github(env).await,
+        // A Google account that's new to smbCloud is signed up on the way.
+        SignupMethod::Google => login_with_google(env).await,
     }
 }
 
@@ -126,7 +131,7 @@ pub async fn do_signup<T: Serialize + ?Sized>(env: Environment, args: &T) -> Res
         succeed_message("Signing you up"),
     );
 
-    let response = Client::new()
+    let response = shared_client()
         .post(build_smb_signup_url(env))
         .json(&args)
         .send()
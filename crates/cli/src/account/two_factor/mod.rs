@@ -0,0 +1,168 @@
+use {
+    crate::{
+        account::{cli::TwoFactorCommands, lib::session_token},
+        ci::{interactive_message, is_ci},
+        cli::CommandResult,
+        client,
+        ui::{fail_message, prompt, succeed_message, succeed_symbol},
+    },
+    anyhow::{anyhow, Result},
+    console::style,
+    qrcode::{render::unicode::Dense1x2, QrCode},
+    smbcloud_auth::two_factor::{
+        disable_two_factor, enable_two_factor, regenerate_recovery_codes, setup_two_factor,
+    },
+    smbcloud_model::{error_codes::ErrorResponse, two_factor::RecoveryCodes},
+    smbcloud_network::environment::Environment,
+    spinners::Spinner,
+};
+
+const SESSION_NEEDED_FOR: &str = "Managing two-factor authentication";
+
+pub async fn process_two_factor(
+    env: Environment,
+    command: TwoFactorCommands,
+) -> Result<CommandResult> {
+    match command {
+        TwoFactorCommands::Enable {} => process_two_factor_enable(env).await,
+        TwoFactorCommands::Disable { otp } => process_two_factor_disable(env, otp).await,
+        TwoFactorCommands::RecoveryCodes { otp } => process_recovery_codes(env, otp).await,
+    }
+}
+
+/// Enrolment needs the QR code scanned and a code typed back, so it can't
+/// run under `--ci`.
+async fn process_two_factor_enable(env: Environment) -> Result<CommandResult> {
+    if is_ci() {
+        return Err(anyhow!(fail_message(&interactive_message(
+            "Two-factor enrolment"
+        ))));
+    }
+    let access_token = session_token(env, SESSION_NEEDED_FOR)?;
+    let mut spinner = loading_spinner("Creating a two-factor secret");
+
+    let setup = setup_two_factor(env, client(), access_token.clone())
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Created."));
+    println!("Scan this QR code with your authenticator app:");
+    println!("{}", render_qr_code(&setup.provisioning_uri)?);
+    println!(
+        "Or enter this secret by hand: {}",
+        style(&setup.secret).bold()
+    );
+
+    let otp = prompt::input_required("Code from your authenticator app")?;
+    let mut spinner = loading_spinner("Turning on two-factor authentication");
+
+    let codes = enable_two_factor(env, client(), access_token, otp.trim().to_string())
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(
+        &succeed_symbol(),
+        succeed_message("Two-factor authentication is on."),
+    );
+    print_recovery_codes(&codes);
+
+    Ok(done_result("Done. Logins now ask for a code."))
+}
+
+async fn process_two_factor_disable(
+    env: Environment,
+    otp: Option<String>,
+) -> Result<CommandResult> {
+    let access_token = session_token(env, SESSION_NEEDED_FOR)?;
+    let otp = otp_or_prompt(otp)?;
+    let mut spinner = loading_spinner("Turning off two-factor authentication");
+
+    disable_two_factor(env, client(), access_token, otp)
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Turned off."));
+    Ok(done_result("Done. Two-factor authentication is off."))
+}
+
+async fn process_recovery_codes(env: Environment, otp: Option<String>) -> Result<CommandResult> {
+    let access_token = session_token(env, SESSION_NEEDED_FOR)?;
+    let otp = otp_or_prompt(otp)?;
+    let mut spinner = loading_spinner("Generating recovery codes");
+
+    let codes = regenerate_recovery_codes(env, client(), access_token, otp)
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Generated."));
+    print_recovery_codes(&codes);
+
+    Ok(done_result("Done. Your old recovery codes no longer work."))
+}
+
+/// `--otp`, or a prompt for it. Fails under `--ci` without the flag.
+fn otp_or_prompt(otp: Option<String>) -> Result<String> {
+    let otp = match otp {
+        Some(otp) => otp,
+        None => prompt::input_required("Code from your authenticator app, or a recovery code")?,
+    };
+    Ok(otp.trim().to_string())
+}
+
+/// Light modules are drawn as blocks so the code scans on dark terminals.
+fn render_qr_code(uri: &str) -> Result<String> {
+    let code =
+        QrCode::new(uri.as_bytes()).map_err(|e| anyhow!("Failed to render the QR code: {e}"))?;
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build())
+}
+
+fn print_recovery_codes(codes: &RecoveryCodes) {
+    println!(
+        "Recovery codes. Each one works once in place of a code from your app. Store them somewhere safe; they are not shown again:"
+    );
+    for code in &codes.recovery_codes {
+        println!("  {}", style(code).bold());
+    }
+}
+
+fn loading_spinner(message: &str) -> Spinner {
+    Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message(message),
+    )
+}
+
+fn done_result(message: &str) -> CommandResult {
+    CommandResult {
+        spinner: loading_spinner("Done"),
+        symbol: succeed_symbol(),
+        msg: succeed_message(message),
+    }
+}
+
+fn api_error(error: ErrorResponse) -> anyhow::Error {
+    let ErrorResponse::Error { message, .. } = error;
+    anyhow!(fail_message(&message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qr_code_renders_as_a_square_block() {
+        let rendered = render_qr_code(
+            "otpauth://totp/smbCloud:a%40b.c?secret=JBSWY3DPEHPK3PXP&issuer=smbCloud",
+        )
+        .unwrap();
+        let lines: Vec<_> = rendered.lines().collect();
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+        // Two modules per character row.
+        assert!(lines.len() * 2 >= width && lines.len() * 2 <= width + 1);
+    }
+}
@@ -0,0 +1,106 @@
+use {
+    crate::{
+        account::lib::session_token,
+        cli::CommandResult,
+        client,
+        ui::{fail_message, prompt, succeed_message, succeed_symbol},
+    },
+    anyhow::{anyhow, Result},
+    smbcloud_auth::{
+        resend_email_verification::resend_email_verification,
+        update_account::{update_email, update_password},
+    },
+    smbcloud_model::error_codes::ErrorResponse,
+    smbcloud_network::environment::Environment,
+    smbcloud_utils::email_validation,
+    spinners::Spinner,
+};
+
+/// Changes the password while logged in. Under `--ci`, both passwords come
+/// from flags or `SMB_CURRENT_PASSWORD`/`SMB_NEW_PASSWORD`.
+pub async fn process_change_password(
+    env: Environment,
+    current_password: Option<String>,
+    new_password: Option<String>,
+) -> Result<CommandResult> {
+    let access_token = session_token(env, "Changing your password")?;
+    let current_password = match current_password {
+        Some(password) => password,
+        None => prompt::password("Current password")?,
+    };
+    let new_password = match new_password {
+        Some(password) => password,
+        None => prompt::new_password("New password")?,
+    };
+    if new_password.is_empty() {
+        return Err(anyhow!(fail_message("The new password cannot be empty.")));
+    }
+    let mut spinner = loading_spinner("Changing your password");
+
+    update_password(env, client(), access_token, current_password, new_password)
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Changed."));
+    Ok(done_result(
+        "Done. Use your new password the next time you log in.",
+    ))
+}
+
+/// Moves the account to a new email address and sends the verification link
+/// there. The login email stays the same until the link is opened.
+pub async fn process_change_email(
+    env: Environment,
+    email: Option<String>,
+    current_password: Option<String>,
+) -> Result<CommandResult> {
+    let access_token = session_token(env, "Changing your email address")?;
+    let email = match email {
+        Some(email) => email,
+        None => prompt::input_required("New email address")?,
+    };
+    let email = email.trim().to_string();
+    email_validation(&email).map_err(|e| anyhow!(fail_message(e)))?;
+    let current_password = match current_password {
+        Some(password) => password,
+        None => prompt::password("Current password")?,
+    };
+    let mut spinner = loading_spinner("Changing your email address");
+
+    update_email(env, client(), access_token, current_password, email.clone())
+        .await
+        .map_err(api_error)?;
+    if let Err(ErrorResponse::Error { message, .. }) =
+        resend_email_verification(env, client(), email.clone()).await
+    {
+        spinner.stop_and_persist(&succeed_symbol(), succeed_message("Requested."));
+        return Err(anyhow!(fail_message(&format!(
+            "The change is waiting for {email} to be verified, but sending the verification email failed: {message}"
+        ))));
+    }
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Requested."));
+    Ok(done_result(&format!(
+        "Done. Open the link sent to {email} to finish. Until then, log in with your current email."
+    )))
+}
+
+fn loading_spinner(message: &str) -> Spinner {
+    Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message(message),
+    )
+}
+
+fn done_result(message: &str) -> CommandResult {
+    CommandResult {
+        spinner: loading_spinner("Done"),
+        symbol: succeed_symbol(),
+        msg: succeed_message(message),
+    }
+}
+
+fn api_error(error: ErrorResponse) -> anyhow::Error {
+    let ErrorResponse::Error { message, .. } = error;
+    anyhow!(fail_message(&message))
+}
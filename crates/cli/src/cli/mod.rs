@@ -1,8 +1,13 @@
 use {
-    crate::{account, cloud_auth, mail, project, tenant},
-    clap::{Parser, Subcommand},
-    smbcloud_network::environment::Environment,
+    crate::{access_token, account, cloud_auth, deploy, mail, profile, project, tenant},
+    clap::{Args, Parser, Subcommand},
+    smbcloud_model::{
+        error_codes::ErrorResponse,
+        pagination::{Page, PageRequest},
+    },
+    smbcloud_network::{environment::Environment, pagination::collect_pages},
     spinners::Spinner,
+    std::future::Future,
 };
 
 pub struct CommandResult {
@@ -17,13 +22,52 @@ impl CommandResult {
     }
 }
 
+/// Shown after a list command that left pages behind.
+pub const MORE_PAGES_MESSAGE: &str = "Showing the first page. Pass --all for the rest.";
+
+/// Paging flags shared by list commands.
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct PageArgs {
+    /// Fetch every page instead of only the first.
+    #[arg(long)]
+    pub all: bool,
+    /// Records per request. Without it the API picks the page size.
+    #[arg(long, value_name = "N", alias = "limit")]
+    pub page_size: Option<u32>,
+}
+
+impl PageArgs {
+    /// Fetches the first page, or every page with `--all`. The returned
+    /// page's `next_cursor` is only set when more records were left behind.
+    pub async fn fetch<T, F, Fut>(self, mut fetch: F) -> Result<Page<T>, ErrorResponse>
+    where
+        F: FnMut(PageRequest) -> Fut,
+        Fut: Future<Output = Result<Page<T>, ErrorResponse>>,
+    {
+        if self.all {
+            let items = collect_pages(self.page_size, fetch).await?;
+            Ok(Page {
+                items,
+                next_cursor: None,
+            })
+        } else {
+            fetch(PageRequest::first(self.page_size)).await
+        }
+    }
+}
+
 #[derive(Parser)]
 #[clap(author, version, about)]
 pub struct Cli {
-    /// Environment: dev, production
+    /// Environment: dev, production, custom (reads SMB_API_URL and SMB_DEPLOY_HOST)
     #[arg(short, long, env = "ENVIRONMENT", default_value = "production")]
     pub environment: Environment,
 
+    /// Profile to run as, e.g. one per client account. Defaults to the one
+    /// picked with `smb profile use`.
+    #[arg(long, global = true, env = "SMB_PROFILE", value_name = "NAME")]
+    pub profile: Option<String>,
+
     /// Log level: trace, debug, info, warn, error, off
     #[clap(short, long, global = true)]
     pub log_level: Option<String>,
@@ -61,6 +105,10 @@ pub enum Commands {
         /// Matches the `name` field in .smb/config.toml. Omit to deploy the root project.
         #[arg(short, long)]
         project: Option<String>,
+        /// Deploy environment to target, e.g. staging. Selects the
+        /// `[project.environments.<name>]` overlay in .smb/config.toml.
+        #[arg(long = "env", value_name = "NAME")]
+        deploy_environment: Option<String>,
     },
     #[clap(
         about = "Initialize project. Requires an smbCloud account.",
@@ -68,7 +116,20 @@ pub enum Commands {
     )]
     Init {},
     #[clap(about = "Login to your account.", display_order = 2)]
-    Login {},
+    Login {
+        /// Log in with a code entered on another device, for SSH sessions and
+        /// containers without a browser.
+        #[arg(long)]
+        device: bool,
+        /// Log in through smbCloud Auth in the browser (OIDC with PKCE),
+        /// picking the tenant on the consent screen.
+        #[arg(long, conflicts_with = "device")]
+        sso: bool,
+        /// Code from your authenticator app (or a recovery code), for an
+        /// account with two-factor authentication on.
+        #[arg(long, value_name = "CODE", conflicts_with_all = ["device", "sso"])]
+        otp: Option<String>,
+    },
     #[clap(about = "Logout from your account.", display_order = 3)]
     Logout {},
     #[clap(about = "Manage your account.")]
@@ -76,6 +137,16 @@ pub enum Commands {
         #[clap(subcommand)]
         command: account::cli::Commands,
     },
+    #[clap(about = "Manage personal access tokens for CI and scripts.")]
+    Token {
+        #[clap(subcommand)]
+        command: access_token::cli::Commands,
+    },
+    #[clap(about = "Manage your login profiles.")]
+    Profile {
+        #[clap(subcommand)]
+        command: profile::cli::Commands,
+    },
     #[clap(about = "Manage your projects.")]
     Project {
         #[clap(subcommand)]
@@ -101,4 +172,9 @@ pub enum Commands {
         display_order = 4
     )]
     Migrate {},
+    #[clap(about = "Compare and sync .smb/config.toml with the server deploy config.")]
+    Config {
+        #[clap(subcommand)]
+        command: deploy::cli::ConfigCommands,
+    },
 }
@@ -1,15 +1,13 @@
-use crate::cloud_auth::{
-    request::request_empty,
-    url_builder::{build_auth_app_url, build_auth_apps_url},
+use crate::cloud_auth::url_builder::{
+    build_auth_app_secret_url, build_auth_app_url, build_auth_apps_url,
 };
-use reqwest::Client;
 use serde::Serialize;
 use smbcloud_model::{
-    app_auth::{AuthApp, AuthAppCreate, AuthAppUpdate},
+    app_auth::{AuthApp, AuthAppCreate, AuthAppSecret, AuthAppSecretRotate, AuthAppUpdate},
     error_codes::ErrorResponse,
 };
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 #[derive(Serialize)]
 struct AuthAppEnvelope<T> {
@@ -22,11 +20,9 @@ pub async fn get_auth_apps(
     access_token: String,
     project_id: Option<String>,
 ) -> Result<Vec<AuthApp>, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_auth_apps_url(env, client, project_id.as_deref()))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.get(build_auth_apps_url(env, client, project_id.as_deref()));
+    api.send(builder).await
 }
 
 pub async fn get_auth_app(
@@ -35,11 +31,9 @@ pub async fn get_auth_app(
     access_token: String,
     auth_app_id: String,
 ) -> Result<AuthApp, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_auth_app_url(env, client, &auth_app_id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.get(build_auth_app_url(env, client, &auth_app_id));
+    api.send(builder).await
 }
 
 pub async fn create_auth_app(
@@ -48,12 +42,11 @@ pub async fn create_auth_app(
     access_token: String,
     auth_app: AuthAppCreate,
 ) -> Result<AuthApp, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .post(build_auth_apps_url(env, client, None))
-        .json(&AuthAppEnvelope { auth_app })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&AuthAppEnvelope { auth_app });
+    api.send(builder).await
 }
 
 pub async fn update_auth_app(
@@ -63,12 +56,11 @@ pub async fn update_auth_app(
     auth_app_id: String,
     auth_app: AuthAppUpdate,
 ) -> Result<AuthApp, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .put(build_auth_app_url(env, client, &auth_app_id))
-        .json(&AuthAppEnvelope { auth_app })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&AuthAppEnvelope { auth_app });
+    api.send(builder).await
 }
 
 pub async fn delete_auth_app(
@@ -77,9 +69,33 @@ pub async fn delete_auth_app(
     access_token: String,
     auth_app_id: String,
 ) -> Result<(), ErrorResponse> {
-    let builder = Client::new()
-        .delete(build_auth_app_url(env, client, &auth_app_id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request_empty(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.delete(build_auth_app_url(env, client, &auth_app_id));
+    api.send_empty(builder).await
+}
+
+pub async fn get_auth_app_secret(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+) -> Result<AuthAppSecret, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.get(build_auth_app_secret_url(env, client, &auth_app_id, false));
+    api.send(builder).await
+}
+
+/// Issues a new secret. The old one keeps working for the grace period.
+pub async fn rotate_auth_app_secret(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    rotation: AuthAppSecretRotate,
+) -> Result<AuthAppSecret, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .post(build_auth_app_secret_url(env, client, &auth_app_id, true))
+        .json(&rotation);
+    api.send(builder).await
 }
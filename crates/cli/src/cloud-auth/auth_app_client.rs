@@ -0,0 +1,92 @@
+use crate::cloud_auth::url_builder::{build_auth_app_client_url, build_auth_app_clients_url};
+use serde::Serialize;
+use smbcloud_model::{
+    app_auth::{AuthAppClient, AuthAppClientCreate, AuthAppClientUpdate},
+    error_codes::ErrorResponse,
+};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
+
+#[derive(Serialize)]
+struct AuthAppClientEnvelope<T> {
+    auth_app_client: T,
+}
+
+pub async fn get_auth_app_clients(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+) -> Result<Vec<AuthAppClient>, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.get(build_auth_app_clients_url(env, client, &auth_app_id));
+    api.send(builder).await
+}
+
+pub async fn get_auth_app_client(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_client_id: String,
+) -> Result<AuthAppClient, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.get(build_auth_app_client_url(
+        env,
+        client,
+        &auth_app_id,
+        &auth_app_client_id,
+    ));
+    api.send(builder).await
+}
+
+pub async fn create_auth_app_client(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_client: AuthAppClientCreate,
+) -> Result<AuthAppClient, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .post(build_auth_app_clients_url(env, client, &auth_app_id))
+        .json(&AuthAppClientEnvelope { auth_app_client });
+    api.send(builder).await
+}
+
+pub async fn update_auth_app_client(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_client_id: String,
+    auth_app_client: AuthAppClientUpdate,
+) -> Result<AuthAppClient, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .put(build_auth_app_client_url(
+            env,
+            client,
+            &auth_app_id,
+            &auth_app_client_id,
+        ))
+        .json(&AuthAppClientEnvelope { auth_app_client });
+    api.send(builder).await
+}
+
+pub async fn delete_auth_app_client(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_client_id: String,
+) -> Result<(), ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.delete(build_auth_app_client_url(
+        env,
+        client,
+        &auth_app_id,
+        &auth_app_client_id,
+    ));
+    api.send_empty(builder).await
+}
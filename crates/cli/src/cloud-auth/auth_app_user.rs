@@ -0,0 +1,122 @@
+use crate::cloud_auth::url_builder::{build_auth_app_user_url, build_auth_app_users_url};
+use smbcloud_model::{
+    app_auth::AuthAppUser,
+    error_codes::ErrorResponse,
+    pagination::{Page, PageRequest},
+};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
+
+/// One page of an Auth app's end users, optionally only those whose email
+/// contains `email`.
+pub async fn get_auth_app_users_page(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    email: Option<String>,
+    page: &PageRequest,
+) -> Result<Page<AuthAppUser>, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .get(build_auth_app_users_url(
+            env,
+            client,
+            &auth_app_id,
+            email.as_deref(),
+        ))
+        .query(page);
+    api.send_page(builder).await
+}
+
+pub async fn get_auth_app_user(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_user_id: String,
+) -> Result<AuthAppUser, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.get(build_auth_app_user_url(
+        env,
+        client,
+        &auth_app_id,
+        &auth_app_user_id,
+        None,
+    ));
+    api.send(builder).await
+}
+
+/// Stops the user from signing in and revokes their sessions.
+pub async fn disable_auth_app_user(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_user_id: String,
+) -> Result<AuthAppUser, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.post(build_auth_app_user_url(
+        env,
+        client,
+        &auth_app_id,
+        &auth_app_user_id,
+        Some("disable"),
+    ));
+    api.send(builder).await
+}
+
+pub async fn enable_auth_app_user(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_user_id: String,
+) -> Result<AuthAppUser, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.post(build_auth_app_user_url(
+        env,
+        client,
+        &auth_app_id,
+        &auth_app_user_id,
+        Some("enable"),
+    ));
+    api.send(builder).await
+}
+
+/// Emails the user a password reset link from the Auth app.
+pub async fn reset_auth_app_user_password(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_user_id: String,
+) -> Result<(), ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.post(build_auth_app_user_url(
+        env,
+        client,
+        &auth_app_id,
+        &auth_app_user_id,
+        Some("reset_password"),
+    ));
+    api.send_empty(builder).await
+}
+
+pub async fn delete_auth_app_user(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    auth_app_id: String,
+    auth_app_user_id: String,
+) -> Result<(), ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.delete(build_auth_app_user_url(
+        env,
+        client,
+        &auth_app_id,
+        &auth_app_user_id,
+        None,
+    ));
+    api.send_empty(builder).await
+}
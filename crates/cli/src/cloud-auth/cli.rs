@@ -1,4 +1,4 @@
-use clap::Subcommand;
+use {crate::cli::PageArgs, clap::Subcommand};
 
 #[derive(Subcommand)]
 pub enum Commands {
@@ -35,4 +35,141 @@ pub enum Commands {
         #[clap(long, required = true)]
         id: String,
     },
+    #[clap(about = "Manage the OAuth clients of an Auth app.")]
+    Client {
+        #[clap(subcommand)]
+        command: ClientCommands,
+    },
+    #[clap(about = "Manage the end users of an Auth app.")]
+    Users {
+        #[clap(subcommand)]
+        command: UserCommands,
+    },
+    #[clap(about = "Show or rotate the secret of an Auth app.")]
+    Secret {
+        #[clap(subcommand)]
+        command: SecretCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SecretCommands {
+    #[clap(about = "Show an Auth app's secret, masked unless --reveal is passed.")]
+    Show {
+        #[clap(long, required = true)]
+        app_id: String,
+        /// Print the secret in full.
+        #[clap(long)]
+        reveal: bool,
+    },
+    #[clap(about = "Issue a new secret for an Auth app.")]
+    Rotate {
+        #[clap(long, required = true)]
+        app_id: String,
+        /// How long the old secret keeps working, e.g. 30m, 24h or 7d. `0`
+        /// revokes it at once.
+        #[clap(long, default_value = "24h")]
+        grace_period: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ClientCommands {
+    #[clap(about = "List the OAuth clients of an Auth app.")]
+    List {
+        #[clap(long, required = true)]
+        app_id: String,
+    },
+    #[clap(about = "Show an OAuth client.")]
+    Show {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        id: String,
+    },
+    #[clap(about = "Register a public OAuth client (PKCE).")]
+    New {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        name: String,
+        /// Allowed redirect URI: https, loopback http, or a custom scheme.
+        /// Repeat for more.
+        #[clap(long = "redirect-uri", required = true)]
+        redirect_uris: Vec<String>,
+    },
+    #[clap(about = "Update an OAuth client.")]
+    Update {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        id: String,
+        #[clap(long)]
+        name: Option<String>,
+        /// Replaces the whole allowlist. Repeat for more.
+        #[clap(long = "redirect-uri")]
+        redirect_uris: Vec<String>,
+    },
+    #[clap(about = "Delete an OAuth client.")]
+    Delete {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum UserCommands {
+    #[clap(about = "List the users of an Auth app.")]
+    List {
+        #[clap(long, required = true)]
+        app_id: String,
+        /// Only users whose email contains this.
+        #[clap(long)]
+        email: Option<String>,
+        #[clap(flatten)]
+        page: PageArgs,
+        /// Print the users as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+    #[clap(about = "Show a user of an Auth app.")]
+    Show {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        id: String,
+        /// Print the user as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+    #[clap(about = "Stop a user from signing in and revoke their sessions.")]
+    Disable {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        id: String,
+    },
+    #[clap(about = "Let a disabled user sign in again.")]
+    Enable {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        id: String,
+    },
+    #[clap(about = "Delete a user of an Auth app.")]
+    Delete {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        id: String,
+    },
+    #[clap(about = "Email a user a password reset link.")]
+    ResetPassword {
+        #[clap(long, required = true)]
+        app_id: String,
+        #[clap(long, required = true)]
+        id: String,
+    },
 }
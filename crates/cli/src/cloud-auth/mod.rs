@@ -1,6 +1,8 @@
 pub mod auth_app;
+pub mod auth_app_client;
+pub mod auth_app_user;
 pub mod cli;
 pub mod process;
+pub(crate) mod redirect_uri;
 mod render;
-mod request;
 mod url_builder;
@@ -1,21 +1,42 @@
 use crate::{
-    cli::CommandResult,
+    cli::{CommandResult, PageArgs, MORE_PAGES_MESSAGE},
     client,
     cloud_auth::{
         auth_app::{
-            create_auth_app, delete_auth_app, get_auth_app, get_auth_apps, update_auth_app,
+            create_auth_app, delete_auth_app, get_auth_app, get_auth_app_secret, get_auth_apps,
+            rotate_auth_app_secret, update_auth_app,
+        },
+        auth_app_client::{
+            create_auth_app_client, delete_auth_app_client, get_auth_app_client,
+            get_auth_app_clients, update_auth_app_client,
+        },
+        auth_app_user::{
+            delete_auth_app_user, disable_auth_app_user, enable_auth_app_user, get_auth_app_user,
+            get_auth_app_users_page, reset_auth_app_user_password,
+        },
+        cli::{ClientCommands, Commands, SecretCommands, UserCommands},
+        redirect_uri::redirect_uris_allowlist,
+        render::{
+            print_auth_app_client_detail, print_auth_app_clients, print_auth_app_detail,
+            print_auth_app_secret, print_auth_app_user_detail, print_auth_app_users,
+            print_auth_apps,
         },
-        cli::Commands,
-        render::{print_auth_app_detail, print_auth_apps},
     },
     mail::current_project::{resolve_optional_project_id, resolve_required_project_id},
     token::get_smb_token::get_smb_token,
-    ui::{fail_message, fail_symbol, prompt::confirm_delete, succeed_message, succeed_symbol},
+    ui::{
+        description, fail_message, fail_symbol,
+        prompt::{confirm, confirm_delete},
+        succeed_message, succeed_symbol,
+    },
 };
 use anyhow::{anyhow, Result};
-use smbcloud_model::app_auth::{AuthAppCreate, AuthAppUpdate};
+use serde::Serialize;
+use smbcloud_model::app_auth::{
+    AuthAppClientCreate, AuthAppClientUpdate, AuthAppCreate, AuthAppSecretRotate, AuthAppUpdate,
+};
 use smbcloud_network::environment::Environment;
-use spinners::Spinner;
+use spinners::{Spinner, Stream};
 
 pub async fn process_cloud_auth(env: Environment, commands: Commands) -> Result<CommandResult> {
     match commands {
@@ -32,6 +53,64 @@ pub async fn process_cloud_auth(env: Environment, commands: Commands) -> Result<
             support_email,
         } => process_auth_app_update(env, id, name, support_email).await,
         Commands::Delete { id } => process_auth_app_delete(env, id).await,
+        Commands::Client { command } => process_auth_client(env, command).await,
+        Commands::Users { command } => process_auth_users(env, command).await,
+        Commands::Secret { command } => process_auth_secret(env, command).await,
+    }
+}
+
+async fn process_auth_secret(env: Environment, commands: SecretCommands) -> Result<CommandResult> {
+    match commands {
+        SecretCommands::Show { app_id, reveal } => {
+            process_auth_secret_show(env, app_id, reveal).await
+        }
+        SecretCommands::Rotate {
+            app_id,
+            grace_period,
+        } => process_auth_secret_rotate(env, app_id, grace_period).await,
+    }
+}
+
+async fn process_auth_users(env: Environment, commands: UserCommands) -> Result<CommandResult> {
+    match commands {
+        UserCommands::List {
+            app_id,
+            email,
+            page,
+            json,
+        } => process_auth_user_list(env, app_id, email, page, json).await,
+        UserCommands::Show { app_id, id, json } => {
+            process_auth_user_show(env, app_id, id, json).await
+        }
+        UserCommands::Disable { app_id, id } => {
+            process_auth_user_set_disabled(env, app_id, id, true).await
+        }
+        UserCommands::Enable { app_id, id } => {
+            process_auth_user_set_disabled(env, app_id, id, false).await
+        }
+        UserCommands::Delete { app_id, id } => process_auth_user_delete(env, app_id, id).await,
+        UserCommands::ResetPassword { app_id, id } => {
+            process_auth_user_reset_password(env, app_id, id).await
+        }
+    }
+}
+
+async fn process_auth_client(env: Environment, commands: ClientCommands) -> Result<CommandResult> {
+    match commands {
+        ClientCommands::List { app_id } => process_auth_client_list(env, app_id).await,
+        ClientCommands::Show { app_id, id } => process_auth_client_show(env, app_id, id).await,
+        ClientCommands::New {
+            app_id,
+            name,
+            redirect_uris,
+        } => process_auth_client_new(env, app_id, name, redirect_uris).await,
+        ClientCommands::Update {
+            app_id,
+            id,
+            name,
+            redirect_uris,
+        } => process_auth_client_update(env, app_id, id, name, redirect_uris).await,
+        ClientCommands::Delete { app_id, id } => process_auth_client_delete(env, app_id, id).await,
     }
 }
 
@@ -92,6 +171,7 @@ async fn process_auth_app_new(
 
     spinner.stop_and_persist(&succeed_symbol(), succeed_message("Created."));
     print_auth_app_detail(&auth_app);
+    print_reveal_hint(&auth_app.id);
 
     Ok(done_result("Auth app created."))
 }
@@ -154,6 +234,432 @@ async fn process_auth_app_delete(env: Environment, id: String) -> Result<Command
     }
 }
 
+async fn process_auth_secret_show(
+    env: Environment,
+    app_id: String,
+    reveal: bool,
+) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let mut spinner = loading_spinner("Loading Auth app secret");
+
+    let auth_app_secret = get_auth_app_secret(env, client(), access_token, auth_app_id.clone())
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
+    print_auth_app_secret(&auth_app_secret, reveal);
+    if !reveal {
+        print_reveal_hint(&auth_app_id);
+    }
+
+    Ok(done_result("Done."))
+}
+
+async fn process_auth_secret_rotate(
+    env: Environment,
+    app_id: String,
+    grace_period: String,
+) -> Result<CommandResult> {
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let grace_period_seconds = parse_grace_period(&grace_period)?;
+    let prompt = if grace_period_seconds == 0 {
+        format!(
+            "Rotate the secret of Auth app #{auth_app_id}? The current secret stops working now."
+        )
+    } else {
+        format!(
+            "Rotate the secret of Auth app #{auth_app_id}? The current secret keeps working for {}.",
+            grace_period.trim()
+        )
+    };
+    // Without a grace period, rotating breaks every deployment still using the
+    // old secret, so CI only goes ahead when there is one.
+    if !confirm(&prompt, grace_period_seconds > 0)? {
+        return Ok(done_result("Cancelled."));
+    }
+
+    let access_token = get_smb_token(env)?;
+    let mut spinner = loading_spinner("Rotating Auth app secret");
+
+    let auth_app_secret = rotate_auth_app_secret(
+        env,
+        client(),
+        access_token,
+        auth_app_id,
+        AuthAppSecretRotate {
+            grace_period_seconds,
+        },
+    )
+    .await
+    .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Rotated."));
+    // The one time the new secret is shown without asking: it has to be
+    // copied somewhere now.
+    print_auth_app_secret(&auth_app_secret, true);
+
+    Ok(done_result("Auth app secret rotated."))
+}
+
+async fn process_auth_client_list(env: Environment, app_id: String) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let mut spinner = loading_spinner("Loading OAuth clients");
+
+    let auth_app_clients = get_auth_app_clients(env, client(), access_token, auth_app_id)
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
+    print_auth_app_clients(&auth_app_clients);
+
+    Ok(done_result(if auth_app_clients.is_empty() {
+        "No OAuth clients found."
+    } else {
+        "Done."
+    }))
+}
+
+async fn process_auth_client_show(
+    env: Environment,
+    app_id: String,
+    id: String,
+) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let auth_app_client_id = normalize_required("OAuth client id", id)?;
+    let mut spinner = loading_spinner("Loading OAuth client");
+
+    let auth_app_client =
+        get_auth_app_client(env, client(), access_token, auth_app_id, auth_app_client_id)
+            .await
+            .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
+    print_auth_app_client_detail(&auth_app_client);
+
+    Ok(done_result("Done."))
+}
+
+async fn process_auth_client_new(
+    env: Environment,
+    app_id: String,
+    name: String,
+    redirect_uris: Vec<String>,
+) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let auth_app_client = AuthAppClientCreate {
+        name: normalize_required("name", name)?,
+        redirect_uris: redirect_uris_allowlist(&redirect_uris)?,
+    };
+    let mut spinner = loading_spinner("Creating OAuth client");
+
+    let auth_app_client =
+        create_auth_app_client(env, client(), access_token, auth_app_id, auth_app_client)
+            .await
+            .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Created."));
+    print_auth_app_client_detail(&auth_app_client);
+
+    Ok(done_result("OAuth client created."))
+}
+
+async fn process_auth_client_update(
+    env: Environment,
+    app_id: String,
+    id: String,
+    name: Option<String>,
+    redirect_uris: Vec<String>,
+) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let auth_app_client_id = normalize_required("OAuth client id", id)?;
+    let update = AuthAppClientUpdate {
+        name: normalize_optional(name),
+        redirect_uris: if redirect_uris.is_empty() {
+            None
+        } else {
+            Some(redirect_uris_allowlist(&redirect_uris)?)
+        },
+    };
+
+    if update.is_empty() {
+        return Err(anyhow!(
+            "Specify at least one of `--name` or `--redirect-uri`."
+        ));
+    }
+
+    let mut spinner = loading_spinner("Updating OAuth client");
+    let auth_app_client = update_auth_app_client(
+        env,
+        client(),
+        access_token,
+        auth_app_id,
+        auth_app_client_id,
+        update,
+    )
+    .await
+    .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Updated."));
+    print_auth_app_client_detail(&auth_app_client);
+
+    Ok(done_result("OAuth client updated."))
+}
+
+async fn process_auth_client_delete(
+    env: Environment,
+    app_id: String,
+    id: String,
+) -> Result<CommandResult> {
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let auth_app_client_id = normalize_required("OAuth client id", id)?;
+    let confirmed = confirm_delete(
+        "OAuth client deletion confirmation",
+        &format!("Delete OAuth client #{auth_app_client_id} from Auth app #{auth_app_id}"),
+    )?;
+
+    if !confirmed {
+        return Ok(done_result("Cancelled."));
+    }
+
+    let access_token = get_smb_token(env)?;
+    let spinner = loading_spinner("Deleting OAuth client");
+
+    match delete_auth_app_client(env, client(), access_token, auth_app_id, auth_app_client_id).await
+    {
+        Ok(()) => Ok(CommandResult {
+            spinner,
+            symbol: succeed_symbol(),
+            msg: succeed_message("Done. OAuth client has been deleted."),
+        }),
+        Err(error) => Ok(CommandResult {
+            spinner,
+            symbol: fail_symbol(),
+            msg: fail_message(&error.to_string()),
+        }),
+    }
+}
+
+async fn process_auth_user_list(
+    env: Environment,
+    app_id: String,
+    email: Option<String>,
+    page: PageArgs,
+    json: bool,
+) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let email = normalize_optional(email);
+    let mut spinner = output_spinner("Loading users", json);
+
+    let users = page
+        .fetch(|page_request| {
+            let access_token = access_token.clone();
+            let auth_app_id = auth_app_id.clone();
+            let email = email.clone();
+            async move {
+                get_auth_app_users_page(
+                    env,
+                    client(),
+                    access_token,
+                    auth_app_id,
+                    email,
+                    &page_request,
+                )
+                .await
+            }
+        })
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
+    if json {
+        print_json(&users)?;
+    } else {
+        print_auth_app_users(&users.items);
+    }
+
+    let message = if users.items.is_empty() {
+        "No users found."
+    } else if !users.is_last() {
+        MORE_PAGES_MESSAGE
+    } else {
+        "Done."
+    };
+    Ok(CommandResult {
+        spinner: output_spinner("Done", json),
+        symbol: succeed_symbol(),
+        msg: succeed_message(message),
+    })
+}
+
+async fn process_auth_user_show(
+    env: Environment,
+    app_id: String,
+    id: String,
+    json: bool,
+) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let auth_app_user_id = normalize_required("user id", id)?;
+    let mut spinner = output_spinner("Loading user", json);
+
+    let user = get_auth_app_user(env, client(), access_token, auth_app_id, auth_app_user_id)
+        .await
+        .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
+    if json {
+        print_json(&user)?;
+    } else {
+        print_auth_app_user_detail(&user);
+    }
+
+    Ok(CommandResult {
+        spinner: output_spinner("Done", json),
+        symbol: succeed_symbol(),
+        msg: succeed_message("Done."),
+    })
+}
+
+async fn process_auth_user_set_disabled(
+    env: Environment,
+    app_id: String,
+    id: String,
+    disabled: bool,
+) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let auth_app_user_id = normalize_required("user id", id)?;
+    let (action, done) = if disabled {
+        ("Disabling user", "User disabled.")
+    } else {
+        ("Enabling user", "User enabled.")
+    };
+    let mut spinner = loading_spinner(action);
+
+    let user = if disabled {
+        disable_auth_app_user(env, client(), access_token, auth_app_id, auth_app_user_id).await
+    } else {
+        enable_auth_app_user(env, client(), access_token, auth_app_id, auth_app_user_id).await
+    }
+    .map_err(api_error)?;
+
+    spinner.stop_and_persist(&succeed_symbol(), succeed_message("Updated."));
+    print_auth_app_user_detail(&user);
+
+    Ok(done_result(done))
+}
+
+async fn process_auth_user_reset_password(
+    env: Environment,
+    app_id: String,
+    id: String,
+) -> Result<CommandResult> {
+    let access_token = get_smb_token(env)?;
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let auth_app_user_id = normalize_required("user id", id)?;
+    let spinner = loading_spinner("Sending password reset email");
+
+    match reset_auth_app_user_password(env, client(), access_token, auth_app_id, auth_app_user_id)
+        .await
+    {
+        Ok(()) => Ok(CommandResult {
+            spinner,
+            symbol: succeed_symbol(),
+            msg: succeed_message("Done. The user has been emailed a password reset link."),
+        }),
+        Err(error) => Ok(CommandResult {
+            spinner,
+            symbol: fail_symbol(),
+            msg: fail_message(&error.to_string()),
+        }),
+    }
+}
+
+async fn process_auth_user_delete(
+    env: Environment,
+    app_id: String,
+    id: String,
+) -> Result<CommandResult> {
+    let auth_app_id = normalize_required("Auth app id", app_id)?;
+    let auth_app_user_id = normalize_required("user id", id)?;
+    let confirmed = confirm_delete(
+        "User deletion confirmation",
+        &format!("Delete user #{auth_app_user_id} from Auth app #{auth_app_id}"),
+    )?;
+
+    if !confirmed {
+        return Ok(done_result("Cancelled."));
+    }
+
+    let access_token = get_smb_token(env)?;
+    let spinner = loading_spinner("Deleting user");
+
+    match delete_auth_app_user(env, client(), access_token, auth_app_id, auth_app_user_id).await {
+        Ok(()) => Ok(CommandResult {
+            spinner,
+            symbol: succeed_symbol(),
+            msg: succeed_message("Done. User has been deleted."),
+        }),
+        Err(error) => Ok(CommandResult {
+            spinner,
+            symbol: fail_symbol(),
+            msg: fail_message(&error.to_string()),
+        }),
+    }
+}
+
+fn print_reveal_hint(auth_app_id: &str) {
+    println!(
+        "{}",
+        description(&format!(
+            "Run `smb auth secret show --app-id {auth_app_id} --reveal` to see the secret in full."
+        ))
+    );
+}
+
+/// Parses `--grace-period`: `0`, or a number of minutes, hours or days such as
+/// `30m`, `24h` or `7d`. Returns seconds.
+fn parse_grace_period(value: &str) -> Result<u64> {
+    let value = value.trim().to_ascii_lowercase();
+    if value == "0" {
+        return Ok(0);
+    }
+    let invalid = || anyhow!("Invalid --grace-period `{value}`. Use e.g. 30m, 24h, 7d or 0.");
+    let (split, unit) = value.char_indices().last().ok_or_else(invalid)?;
+    let amount: u64 = value[..split].parse().map_err(|_| invalid())?;
+    let unit_seconds = match unit {
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    amount.checked_mul(unit_seconds).ok_or_else(invalid)
+}
+
+/// With `--json`, progress goes to stderr so stdout holds only the JSON.
+fn output_spinner(message: &str, json: bool) -> Spinner {
+    if json {
+        Spinner::with_stream(
+            spinners::Spinners::SimpleDotsScrolling,
+            succeed_message(message),
+            Stream::Stderr,
+        )
+    } else {
+        loading_spinner(message)
+    }
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
 fn loading_spinner(message: &str) -> Spinner {
     Spinner::new(
         spinners::Spinners::SimpleDotsScrolling,
@@ -191,3 +697,19 @@ fn normalize_optional(value: Option<String>) -> Option<String> {
 fn api_error(error: impl std::fmt::Display) -> anyhow::Error {
     anyhow!(error.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grace_period_accepts_minutes_hours_days_and_zero() {
+        assert_eq!(parse_grace_period("30m").unwrap(), 30 * 60);
+        assert_eq!(parse_grace_period("24h").unwrap(), 24 * 60 * 60);
+        assert_eq!(parse_grace_period(" 7D ").unwrap(), 7 * 24 * 60 * 60);
+        assert_eq!(parse_grace_period("0").unwrap(), 0);
+        for invalid in ["", "h", "24", "-1h", "2w", "5é", "é"] {
+            assert!(parse_grace_period(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Client-side checks for an OAuth client's `redirect_uris` allowlist, so a
+//! typo fails before the request instead of at the first login.
+
+use {
+    anyhow::{anyhow, Result},
+    reqwest::Url,
+    std::net::IpAddr,
+};
+
+/// Schemes that would run or read something in the browser rather than hand
+/// the code to an app.
+const FORBIDDEN_SCHEMES: [&str; 4] = ["javascript", "data", "file", "vbscript"];
+
+/// Checks every URI and joins them into the newline-separated form the API
+/// stores. Duplicates are dropped.
+pub(crate) fn redirect_uris_allowlist(redirect_uris: &[String]) -> Result<String> {
+    let mut allowlist: Vec<&str> = Vec::new();
+    for redirect_uri in redirect_uris.iter().map(|uri| uri.trim()) {
+        validate_redirect_uri(redirect_uri)?;
+        if !allowlist.contains(&redirect_uri) {
+            allowlist.push(redirect_uri);
+        }
+    }
+    if allowlist.is_empty() {
+        return Err(anyhow!("Specify at least one `--redirect-uri`."));
+    }
+    Ok(allowlist.join("\n"))
+}
+
+/// A redirect URI must be https, http on a loopback address (native apps
+/// listening locally), or a custom scheme (native apps). Fragments are never
+/// allowed.
+pub(crate) fn validate_redirect_uri(redirect_uri: &str) -> Result<()> {
+    let invalid = |reason: &str| anyhow!("Invalid redirect URI `{redirect_uri}`: {reason}");
+    let url = Url::parse(redirect_uri).map_err(|e| invalid(&e.to_string()))?;
+    if url.fragment().is_some() {
+        return Err(invalid("it must not contain a fragment."));
+    }
+    match url.scheme() {
+        "https" if url.host().is_some() => Ok(()),
+        "https" => Err(invalid("it has no host.")),
+        "http" if url.host_str().is_some_and(is_loopback) => Ok(()),
+        "http" => Err(invalid(
+            "plain http is only allowed on localhost, 127.0.0.1, or [::1].",
+        )),
+        scheme if FORBIDDEN_SCHEMES.contains(&scheme) => {
+            Err(invalid(&format!("the `{scheme}:` scheme is not allowed.")))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_https_loopback_http_and_custom_schemes() {
+        for uri in [
+            "https://app.example.com/callback",
+            "http://localhost:3000/callback",
+            "http://127.0.0.1:8080/callback",
+            "http://[::1]/callback",
+            "com.example.app:/oauth/callback",
+        ] {
+            assert!(validate_redirect_uri(uri).is_ok(), "{uri}");
+        }
+    }
+
+    #[test]
+    fn rejects_public_http_fragments_and_script_schemes() {
+        for uri in [
+            "http://app.example.com/callback",
+            "https://app.example.com/callback#token",
+            "javascript:alert(1)",
+            "not a url",
+        ] {
+            assert!(validate_redirect_uri(uri).is_err(), "{uri}");
+        }
+    }
+
+    #[test]
+    fn joins_the_allowlist_without_duplicates() {
+        let uris = [
+            "https://a.example.com/cb".to_string(),
+            " http://localhost/cb ".to_string(),
+            "https://a.example.com/cb".to_string(),
+        ];
+        assert_eq!(
+            redirect_uris_allowlist(&uris).unwrap(),
+            "https://a.example.com/cb\nhttp://localhost/cb"
+        );
+        assert!(redirect_uris_allowlist(&[]).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use console::style;
-use smbcloud_model::app_auth::AuthApp;
+use smbcloud_model::app_auth::{mask_secret, AuthApp, AuthAppClient, AuthAppSecret, AuthAppUser};
 
 fn print_heading(title: &str) {
     println!("\n{}", style(title).bold().underlined());
@@ -39,7 +39,116 @@ pub(crate) fn print_auth_app_detail(auth_app: &AuthApp) {
     print_field("Name", &auth_app.name);
     print_optional_field("Project ID", auth_app.project_id.as_deref());
     print_optional_field("Support email", auth_app.support_email.as_deref());
-    print_optional_field("Secret", auth_app.secret.as_deref());
+    print_optional_field(
+        "Secret",
+        auth_app.secret.as_deref().map(mask_secret).as_deref(),
+    );
     print_field("Created at", auth_app.created_at.to_rfc3339());
     print_field("Updated at", auth_app.updated_at.to_rfc3339());
 }
+
+pub(crate) fn print_auth_app_clients(auth_app_clients: &[AuthAppClient]) {
+    print_heading("OAuth clients");
+
+    if auth_app_clients.is_empty() {
+        println!("No OAuth clients found.");
+        return;
+    }
+
+    for auth_app_client in auth_app_clients {
+        println!(
+            "#{} {} client_id={}",
+            auth_app_client.id,
+            style(&auth_app_client.name).bold(),
+            auth_app_client.client_id
+        );
+    }
+}
+
+pub(crate) fn print_auth_app_client_detail(auth_app_client: &AuthAppClient) {
+    print_heading("OAuth client");
+    print_field("ID", auth_app_client.id.to_string());
+    print_field("Client ID", &auth_app_client.client_id);
+    print_field("Name", &auth_app_client.name);
+    print_field("Auth app ID", &auth_app_client.auth_app_id);
+    print_field(
+        "Type",
+        if auth_app_client.confidential {
+            "confidential"
+        } else {
+            "public (PKCE)"
+        },
+    );
+    println!("{}", style("Redirect URIs:").cyan());
+    for redirect_uri in auth_app_client.redirect_uris.lines() {
+        println!("  {redirect_uri}");
+    }
+    print_field("Created at", auth_app_client.created_at.to_rfc3339());
+    print_field("Updated at", auth_app_client.updated_at.to_rfc3339());
+}
+
+pub(crate) fn print_auth_app_users(auth_app_users: &[AuthAppUser]) {
+    print_heading("Users");
+
+    if auth_app_users.is_empty() {
+        println!("No users found.");
+        return;
+    }
+
+    for auth_app_user in auth_app_users {
+        println!(
+            "#{} {}{}",
+            auth_app_user.id,
+            style(&auth_app_user.email).bold(),
+            if auth_app_user.disabled {
+                style(" (disabled)").red().to_string()
+            } else {
+                String::new()
+            }
+        );
+    }
+}
+
+pub(crate) fn print_auth_app_user_detail(auth_app_user: &AuthAppUser) {
+    print_heading("User");
+    print_field("ID", auth_app_user.id.to_string());
+    print_field("Email", &auth_app_user.email);
+    print_optional_field("Name", auth_app_user.name.as_deref());
+    print_field(
+        "Email verified",
+        if auth_app_user.email_verified {
+            "yes"
+        } else {
+            "no"
+        },
+    );
+    print_field(
+        "Status",
+        if auth_app_user.disabled {
+            "disabled"
+        } else {
+            "active"
+        },
+    );
+    print_optional_field(
+        "Last sign-in",
+        auth_app_user
+            .last_sign_in_at
+            .map(|last_sign_in_at| last_sign_in_at.to_rfc3339())
+            .as_deref(),
+    );
+    print_field("Created at", auth_app_user.created_at.to_rfc3339());
+    print_field("Updated at", auth_app_user.updated_at.to_rfc3339());
+}
+
+pub(crate) fn print_auth_app_secret(auth_app_secret: &AuthAppSecret, reveal: bool) {
+    print_heading("Auth app secret");
+    if reveal {
+        print_field("Secret", &auth_app_secret.secret);
+    } else {
+        print_field("Secret", mask_secret(&auth_app_secret.secret));
+    }
+    if let Some(expires_at) = auth_app_secret.previous_secret_expires_at {
+        print_field("Previous secret valid until", expires_at.to_rfc3339());
+    }
+}
@@ -26,3 +26,81 @@ pub(crate) fn build_auth_app_url(
     url_builder.add_route(auth_app_id);
     url_builder.build()
 }
+
+pub(crate) fn build_auth_app_clients_url(
+    env: Environment,
+    client: (&SmbClient, &str),
+    auth_app_id: &str,
+) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route("v1/auth_apps");
+    url_builder.add_route(auth_app_id);
+    url_builder.add_route("clients");
+    url_builder.build()
+}
+
+pub(crate) fn build_auth_app_client_url(
+    env: Environment,
+    client: (&SmbClient, &str),
+    auth_app_id: &str,
+    auth_app_client_id: &str,
+) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route("v1/auth_apps");
+    url_builder.add_route(auth_app_id);
+    url_builder.add_route("clients");
+    url_builder.add_route(auth_app_client_id);
+    url_builder.build()
+}
+
+pub(crate) fn build_auth_app_users_url(
+    env: Environment,
+    client: (&SmbClient, &str),
+    auth_app_id: &str,
+    email: Option<&str>,
+) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route("v1/auth_apps");
+    url_builder.add_route(auth_app_id);
+    url_builder.add_route("users");
+    if let Some(email) = email {
+        url_builder.add_param("email", email);
+    }
+    url_builder.build()
+}
+
+/// `v1/auth_apps/{id}/users/{user_id}`, or one of its actions (`disable`,
+/// `enable`, `reset_password`).
+pub(crate) fn build_auth_app_user_url(
+    env: Environment,
+    client: (&SmbClient, &str),
+    auth_app_id: &str,
+    auth_app_user_id: &str,
+    action: Option<&str>,
+) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route("v1/auth_apps");
+    url_builder.add_route(auth_app_id);
+    url_builder.add_route("users");
+    url_builder.add_route(auth_app_user_id);
+    if let Some(action) = action {
+        url_builder.add_route(action);
+    }
+    url_builder.build()
+}
+
+pub(crate) fn build_auth_app_secret_url(
+    env: Environment,
+    client: (&SmbClient, &str),
+    auth_app_id: &str,
+    rotate: bool,
+) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route("v1/auth_apps");
+    url_builder.add_route(auth_app_id);
+    url_builder.add_route("secret");
+    if rotate {
+        url_builder.add_route("rotate");
+    }
+    url_builder.build()
+}
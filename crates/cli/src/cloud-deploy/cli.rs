@@ -0,0 +1,11 @@
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    #[clap(about = "Show a field-by-field table of local vs server deploy config.")]
+    Diff {},
+    #[clap(about = "Write the server deploy config into .smb/config.toml.")]
+    Pull {},
+    #[clap(about = "Send the deploy fields in .smb/config.toml to the server.")]
+    Push {},
+}
@@ -5,6 +5,7 @@ use {
         ui::{fail_message, fail_symbol, succeed_message, succeed_symbol},
     },
     git2::{Cred, CredentialType, Error},
+    log::debug,
     smbcloud_model::{
         account::User,
         error_codes::{ErrorCode, ErrorResponse},
@@ -141,7 +142,12 @@ pub(crate) async fn overlay_server_config(
             config.project.deploy_repo_id = deploy_config.deploy_repo_id;
             true
         }
-        Err(_) => false,
+        Err(e) => {
+            // Deploys still go ahead on the local values; `smb config diff`
+            // is the place to investigate a mismatch.
+            debug!("Failed to load server deploy config for {frontend_app_id}: {e}");
+            false
+        }
     }
 }
 
@@ -4,10 +4,12 @@ use {
     console::style,
     git2::{Remote, Repository},
     smbcloud_model::runner::Runner,
+    smbcloud_network::environment::Environment,
     spinners::Spinner,
 };
 
 pub async fn remote_deployment_setup<'a>(
+    env: Environment,
     runner: &Runner,
     repo: &'a Repository,
     repo_name: &'a str,
@@ -44,7 +46,7 @@ pub async fn remote_deployment_setup<'a>(
             let remote = repo
                 .remote(
                     "smbcloud",
-                    &format!("{}:{}", runner.git_host(), repo_name_format),
+                    &format!("{}:{}", env.git_host(runner), repo_name_format),
                 )
                 .map_err(|e: git2::Error| {
                     spinner.stop_and_persist(&fail_symbol(), e.to_string());
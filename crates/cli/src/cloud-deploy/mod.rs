@@ -1,5 +1,7 @@
+pub mod cli;
 pub mod config;
 mod git;
+pub mod process_config;
 pub mod process_deploy;
 pub mod process_deploy_nextjs_ssr;
 pub mod process_deploy_rails;
@@ -16,6 +18,7 @@ use {
     anyhow::{anyhow, Result},
     smbcloud_deploy::RsyncTransport,
     smbcloud_model::runner::Runner,
+    smbcloud_network::environment::Environment,
     smbcloud_utils::config::Config,
 };
 
@@ -30,6 +33,7 @@ pub(crate) use smbcloud_deploy::known_hosts;
 /// know about: the server host (from the runner), the remote path (from config,
 /// defaulting to `apps/web/<name>`), and the user's local SSH identity file.
 pub(crate) fn rsync_transport(
+    env: Environment,
     config: &Config,
     runner: &Runner,
     user_id: i32,
@@ -43,7 +47,7 @@ pub(crate) fn rsync_transport(
     };
 
     Ok(RsyncTransport::new(
-        runner.rsync_host(),
+        env.deploy_host(runner),
         remote_path,
         identity_file,
     ))
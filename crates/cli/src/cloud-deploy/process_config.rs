@@ -0,0 +1,592 @@
+//! `smb config diff | pull | push`: keep the deploy fields in the local
+//! `.smb/config.toml` and the server-side `DeployConfig` in step.
+//!
+//! `smb deploy` overlays the server values onto the local config in memory
+//! (see `overlay_server_config`), so the two can drift apart without anyone
+//! noticing. These commands make the drift visible and let the user settle it
+//! in either direction. Each one covers the root `[project]` and every
+//! `[[projects]]` entry that carries a `frontend_app_id`.
+//!
+//! `pull` edits the file with `toml_edit` rather than re-serializing `Config`,
+//! so comments, key order and formatting written by hand survive.
+
+use {
+    crate::{
+        account::login::process_login,
+        ci::is_ci,
+        cli::CommandResult,
+        client,
+        deploy::{cli::ConfigCommands, config::get_config, process_migrate::build_payload},
+        token::{get_smb_token::get_smb_token, is_logged_in::is_logged_in},
+        ui::{fail_message, fail_symbol, highlight, succeed_message, succeed_symbol},
+    },
+    anyhow::{anyhow, Result},
+    smbcloud_model::{deploy_config::DeployConfig, project::Project},
+    smbcloud_network::environment::Environment,
+    smbcloud_networking_project::{
+        crud_frontend_app_deploy_config::get_deploy_config,
+        crud_frontend_app_update_deploy_config::update_deploy_config,
+    },
+    smbcloud_utils::config::Config,
+    spinners::Spinner,
+    std::fs,
+    tabled::{builder::Builder, settings::Style},
+    toml_edit::{DocumentMut, Item, Table, Value},
+};
+
+const CONFIG_PATH: &str = ".smb/config.toml";
+
+/// Where a project's table lives inside `.smb/config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigSlot {
+    /// The root `[project]` table.
+    Root,
+    /// The n-th `[[projects]]` entry.
+    Sub(usize),
+}
+
+/// A project in `.smb/config.toml` that is linked to a server-side app.
+struct ConfigTarget<'a> {
+    slot: ConfigSlot,
+    project: &'a Project,
+    frontend_app_id: &'a str,
+}
+
+/// One deploy field as seen locally and on the server, both rendered as text.
+#[derive(Debug, PartialEq, Eq)]
+struct FieldDiff {
+    /// The key used in `.smb/config.toml`.
+    key: &'static str,
+    local: Option<String>,
+    server: Option<String>,
+}
+
+impl FieldDiff {
+    fn new(key: &'static str, local: Option<String>, server: Option<String>) -> Self {
+        Self { key, local, server }
+    }
+
+    fn differs(&self) -> bool {
+        self.local != self.server
+    }
+}
+
+pub async fn process_config(env: Environment, command: ConfigCommands) -> Result<CommandResult> {
+    let is_logged_in = is_logged_in(env).await?;
+    if !is_logged_in {
+        let _ = process_login(env, Some(is_logged_in)).await?;
+    }
+
+    let access_token = get_smb_token(env)?;
+    let config = get_config(env, Some(&access_token))
+        .await
+        .map_err(|e| anyhow!(fail_message(&format!("Failed to load config: {:?}", e))))?;
+
+    let targets = config_targets(&config);
+    if targets.is_empty() {
+        let spinner = Spinner::new(spinners::Spinners::Hamburger, String::new());
+        return Ok(CommandResult {
+            spinner,
+            symbol: succeed_symbol(),
+            msg: succeed_message("No projects with a frontend_app_id found — nothing to sync."),
+        });
+    }
+
+    match command {
+        ConfigCommands::Diff {} => process_config_diff(env, &access_token, &targets).await,
+        ConfigCommands::Pull {} => process_config_pull(env, &access_token, &targets).await,
+        ConfigCommands::Push {} => process_config_push(env, &access_token, &targets).await,
+    }
+}
+
+async fn process_config_diff(
+    env: Environment,
+    access_token: &str,
+    targets: &[ConfigTarget<'_>],
+) -> Result<CommandResult> {
+    let mut differences = 0usize;
+    let mut failed = 0usize;
+
+    for target in targets {
+        print_target_heading(target);
+        match get_deploy_config(
+            env,
+            client(),
+            access_token.to_string(),
+            target.frontend_app_id,
+        )
+        .await
+        {
+            Ok(deploy_config) => {
+                let fields = diff_fields(target.project, &deploy_config);
+                differences += fields.iter().filter(|field| field.differs()).count();
+                render_field_diffs(&fields);
+            }
+            Err(e) => {
+                println!("    {} Failed to load server config: {}", fail_symbol(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    let spinner = Spinner::new(spinners::Spinners::Hamburger, String::new());
+    let summary = format!(
+        "Compared {} project(s): {} difference(s), {} failed.",
+        targets.len() - failed,
+        differences,
+        failed
+    );
+    summary_result(spinner, failed, &summary)
+}
+
+async fn process_config_pull(
+    env: Environment,
+    access_token: &str,
+    targets: &[ConfigTarget<'_>],
+) -> Result<CommandResult> {
+    let content = fs::read_to_string(CONFIG_PATH)
+        .map_err(|e| anyhow!(fail_message(&format!("Failed to read {CONFIG_PATH}: {e}"))))?;
+    let mut document = content
+        .parse::<DocumentMut>()
+        .map_err(|e| anyhow!(fail_message(&format!("Failed to parse {CONFIG_PATH}: {e}"))))?;
+
+    let spinner = Spinner::new(
+        spinners::Spinners::Hamburger,
+        succeed_message("Pulling deploy config from server..."),
+    );
+
+    let mut pulled = 0usize;
+    let mut failed = 0usize;
+
+    for target in targets {
+        print_target_heading(target);
+        let deploy_config = match get_deploy_config(
+            env,
+            client(),
+            access_token.to_string(),
+            target.frontend_app_id,
+        )
+        .await
+        {
+            Ok(deploy_config) => deploy_config,
+            Err(e) => {
+                println!("    {} Failed to load server config: {}", fail_symbol(), e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        match project_table_mut(&mut document, target.slot) {
+            Some(table) => {
+                apply_deploy_config(table, &deploy_config);
+                println!("    {} Pulled.", succeed_symbol());
+                pulled += 1;
+            }
+            None => {
+                println!(
+                    "    {} Could not find this project's table in {}.",
+                    fail_symbol(),
+                    CONFIG_PATH
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    if pulled > 0 {
+        fs::write(CONFIG_PATH, document.to_string())
+            .map_err(|e| anyhow!(fail_message(&format!("Failed to write {CONFIG_PATH}: {e}"))))?;
+    }
+
+    let summary = format!("Pull complete: {} pulled, {} failed.", pulled, failed);
+    summary_result(spinner, failed, &summary)
+}
+
+async fn process_config_push(
+    env: Environment,
+    access_token: &str,
+    targets: &[ConfigTarget<'_>],
+) -> Result<CommandResult> {
+    let spinner = Spinner::new(
+        spinners::Spinners::Hamburger,
+        succeed_message("Pushing deploy config to server..."),
+    );
+
+    let mut pushed = 0usize;
+    let mut failed = 0usize;
+
+    for target in targets {
+        print_target_heading(target);
+        // `pm2_env` is server-only and never written to `.smb/config.toml`, so
+        // there is no local value to push — `smb migrate` seeds it instead.
+        let payload = match build_payload(target.project, None) {
+            Some(payload) => payload,
+            None => {
+                println!("    {} Nothing to push — skipping.", succeed_symbol());
+                continue;
+            }
+        };
+
+        match update_deploy_config(
+            env,
+            client(),
+            access_token.to_string(),
+            target.frontend_app_id,
+            &payload,
+        )
+        .await
+        {
+            Ok(_) => {
+                println!("    {} Pushed.", succeed_symbol());
+                pushed += 1;
+            }
+            Err(e) => {
+                println!("    {} Failed: {}", fail_symbol(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    let summary = format!("Push complete: {} pushed, {} failed.", pushed, failed);
+    summary_result(spinner, failed, &summary)
+}
+
+/// Summary of a diff, pull or push. Any failed project marks it failed, and under
+/// `--ci` turns it into an error so the job exits non-zero.
+fn summary_result(mut spinner: Spinner, failed: usize, summary: &str) -> Result<CommandResult> {
+    if failed == 0 {
+        return Ok(CommandResult {
+            spinner,
+            symbol: succeed_symbol(),
+            msg: succeed_message(summary),
+        });
+    }
+    if is_ci() {
+        spinner.stop_and_persist(&fail_symbol(), fail_message("Done with failures."));
+        return Err(anyhow!(fail_message(summary)));
+    }
+    Ok(CommandResult {
+        spinner,
+        symbol: fail_symbol(),
+        msg: fail_message(summary),
+    })
+}
+
+/// The root project plus every `[[projects]]` entry, keeping only the ones
+/// linked to a server-side app.
+fn config_targets(config: &Config) -> Vec<ConfigTarget<'_>> {
+    let sub_projects = config
+        .projects
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, project)| (ConfigSlot::Sub(index), project));
+
+    std::iter::once((ConfigSlot::Root, &config.project))
+        .chain(sub_projects)
+        .filter_map(|(slot, project)| {
+            project
+                .frontend_app_id
+                .as_deref()
+                .map(|frontend_app_id| ConfigTarget {
+                    slot,
+                    project,
+                    frontend_app_id,
+                })
+        })
+        .collect()
+}
+
+fn print_target_heading(target: &ConfigTarget<'_>) {
+    println!(
+        "\n  {} (frontend_app_id: {})",
+        highlight(&target.project.name),
+        target.frontend_app_id
+    );
+}
+
+/// Compare the local deploy fields of `project` against the server's copy.
+/// The field set mirrors what `smb config push` sends and what
+/// `overlay_server_config` merges in on deploy.
+fn diff_fields(project: &Project, deploy_config: &DeployConfig) -> Vec<FieldDiff> {
+    vec![
+        FieldDiff::new(
+            "runner",
+            Some(project.runner.to_string()),
+            Some(deploy_config.runner.to_string()),
+        ),
+        FieldDiff::new(
+            "deployment_method",
+            Some(project.deployment_method.to_string()),
+            Some(deploy_config.deployment_method.to_string()),
+        ),
+        FieldDiff::new("kind", project.kind.clone(), deploy_config.kind.clone()),
+        FieldDiff::new(
+            "source_path",
+            local_source_path(project),
+            deploy_config.source_path.clone(),
+        ),
+        FieldDiff::new(
+            "path",
+            project.path.clone(),
+            deploy_config.remote_path.clone(),
+        ),
+        FieldDiff::new(
+            "output",
+            project.output.clone(),
+            deploy_config.output_path.clone(),
+        ),
+        FieldDiff::new(
+            "compile_cmd",
+            project.compile_cmd.clone(),
+            deploy_config.build_command.clone(),
+        ),
+        FieldDiff::new(
+            "install_command",
+            project.install_command.clone(),
+            deploy_config.install_command.clone(),
+        ),
+        FieldDiff::new(
+            "binary_name",
+            project.binary_name.clone(),
+            deploy_config.binary_name.clone(),
+        ),
+        FieldDiff::new(
+            "rust_target",
+            project.rust_target.clone(),
+            deploy_config.build_target.clone(),
+        ),
+        FieldDiff::new(
+            "package_manager",
+            project.package_manager.clone(),
+            deploy_config.package_manager.clone(),
+        ),
+        FieldDiff::new(
+            "pm2_app",
+            project.pm2_app.clone(),
+            deploy_config.pm2_app.clone(),
+        ),
+        FieldDiff::new(
+            "port",
+            project.port.map(|port| port.to_string()),
+            deploy_config.port.map(|port| port.to_string()),
+        ),
+        FieldDiff::new(
+            "shared_lib",
+            project.shared_lib.clone(),
+            deploy_config.shared_lib_path.clone(),
+        ),
+    ]
+}
+
+/// `push` sends the local build directory (`source`) as the server's
+/// `source_path`, falling back to `source_path` — compare the same value.
+fn local_source_path(project: &Project) -> Option<String> {
+    project
+        .source
+        .clone()
+        .or_else(|| project.source_path.clone())
+}
+
+fn render_field_diffs(fields: &[FieldDiff]) {
+    let mut builder = Builder::default();
+    builder.push_record(["", "Field", "Local", "Server"]);
+    for field in fields {
+        if field.local.is_none() && field.server.is_none() {
+            continue;
+        }
+        builder.push_record([
+            if field.differs() { "≠" } else { "" }.to_string(),
+            field.key.to_string(),
+            field.local.clone().unwrap_or_else(|| "-".to_string()),
+            field.server.clone().unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+    let mut table = builder.build();
+    table.with(Style::rounded());
+    println!("{table}");
+}
+
+fn project_table_mut(document: &mut DocumentMut, slot: ConfigSlot) -> Option<&mut Table> {
+    match slot {
+        ConfigSlot::Root => document.get_mut("project")?.as_table_mut(),
+        ConfigSlot::Sub(index) => document
+            .get_mut("projects")?
+            .as_array_of_tables_mut()?
+            .get_mut(index),
+    }
+}
+
+/// Write every populated server field into `table`. Fields the server leaves
+/// empty are not removed locally, matching how `overlay_server_config` merges
+/// them on deploy.
+fn apply_deploy_config(table: &mut Table, deploy_config: &DeployConfig) {
+    set_value(table, "runner", i64::from(deploy_config.runner as u8));
+    set_value(
+        table,
+        "deployment_method",
+        i64::from(deploy_config.deployment_method as u8),
+    );
+    if let Some(kind) = &deploy_config.kind {
+        set_value(table, "kind", kind.as_str());
+    }
+    if let Some(source_path) = &deploy_config.source_path {
+        // Keep whichever key the user already relies on for the build directory.
+        let key = if table.contains_key("source") {
+            "source"
+        } else {
+            "source_path"
+        };
+        set_value(table, key, source_path.as_str());
+    }
+    if let Some(remote_path) = &deploy_config.remote_path {
+        set_value(table, "path", remote_path.as_str());
+    }
+    if let Some(output_path) = &deploy_config.output_path {
+        set_value(table, "output", output_path.as_str());
+    }
+    if let Some(build_command) = &deploy_config.build_command {
+        set_value(table, "compile_cmd", build_command.as_str());
+    }
+    if let Some(install_command) = &deploy_config.install_command {
+        set_value(table, "install_command", install_command.as_str());
+    }
+    if let Some(binary_name) = &deploy_config.binary_name {
+        set_value(table, "binary_name", binary_name.as_str());
+    }
+    if let Some(build_target) = &deploy_config.build_target {
+        set_value(table, "rust_target", build_target.as_str());
+    }
+    if let Some(package_manager) = &deploy_config.package_manager {
+        set_value(table, "package_manager", package_manager.as_str());
+    }
+    if let Some(pm2_app) = &deploy_config.pm2_app {
+        set_value(table, "pm2_app", pm2_app.as_str());
+    }
+    if let Some(port) = deploy_config.port {
+        set_value(table, "port", i64::from(port));
+    }
+    if let Some(shared_lib_path) = &deploy_config.shared_lib_path {
+        set_value(table, "shared_lib", shared_lib_path.as_str());
+    }
+}
+
+/// Set `key` to `value`, replacing an existing value in place so the comments
+/// and whitespace around it are kept.
+fn set_value(table: &mut Table, key: &str, value: impl Into<Value>) {
+    let mut value = value.into();
+    match table.get_mut(key) {
+        Some(item) => {
+            if let Some(existing) = item.as_value() {
+                *value.decor_mut() = existing.decor().clone();
+            }
+            *item = Item::Value(value);
+        }
+        None => {
+            table.insert(key, Item::Value(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use smbcloud_model::{project::DeploymentMethod, runner::Runner};
+
+    const CONFIG: &str = r#"name = "acme"
+
+# The web app.
+[project]
+id = 1
+name = "web"
+frontend_app_id = "app-web"
+# Remote directory on the server.
+path = "apps/web/old" # keep me
+port = 3000
+
+[[projects]]
+id = 1
+name = "api"
+frontend_app_id = "app-api"
+source = "services/api"
+"#;
+
+    fn deploy_config() -> DeployConfig {
+        serde_json::from_str(
+            r#"{
+                "id": "app-web",
+                "name": "web",
+                "runner": 4,
+                "deployment_method": 1,
+                "source_path": "services/api",
+                "remote_path": "apps/web/new",
+                "port": 8090,
+                "binary_name": "api"
+            }"#,
+        )
+        .expect("deploy config")
+    }
+
+    #[test]
+    fn targets_cover_root_and_sub_projects() {
+        let config: Config = toml::from_str(CONFIG).expect("config");
+        let slots: Vec<ConfigSlot> = config_targets(&config)
+            .iter()
+            .map(|target| target.slot)
+            .collect();
+        assert_eq!(slots, vec![ConfigSlot::Root, ConfigSlot::Sub(0)]);
+    }
+
+    #[test]
+    fn diff_flags_changed_fields_only() {
+        let config: Config = toml::from_str(CONFIG).expect("config");
+        let fields = diff_fields(&config.project, &deploy_config());
+        let changed: Vec<&str> = fields
+            .iter()
+            .filter(|field| field.differs())
+            .map(|field| field.key)
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                "runner",
+                "deployment_method",
+                "source_path",
+                "path",
+                "binary_name",
+                "port"
+            ]
+        );
+    }
+
+    #[test]
+    fn pull_preserves_comments_and_updates_values() {
+        let mut document = CONFIG.parse::<DocumentMut>().expect("document");
+        let table = project_table_mut(&mut document, ConfigSlot::Root).expect("root table");
+        apply_deploy_config(table, &deploy_config());
+        let written = document.to_string();
+
+        assert!(written.contains("# The web app."));
+        assert!(written
+            .contains("# Remote directory on the server.\npath = \"apps/web/new\" # keep me"));
+        assert!(written.contains("port = 8090"));
+
+        let config: Config = toml::from_str(&written).expect("round trip");
+        assert_eq!(config.project.runner, Runner::Rust);
+        assert_eq!(config.project.deployment_method, DeploymentMethod::Rsync);
+        assert_eq!(config.project.binary_name.as_deref(), Some("api"));
+        assert_eq!(config.project.source_path.as_deref(), Some("services/api"));
+    }
+
+    #[test]
+    fn pull_keeps_the_source_key_when_present() {
+        let mut document = CONFIG.parse::<DocumentMut>().expect("document");
+        let table = project_table_mut(&mut document, ConfigSlot::Sub(0)).expect("sub table");
+        apply_deploy_config(table, &deploy_config());
+
+        let config: Config = toml::from_str(&document.to_string()).expect("round trip");
+        let api = &config.projects.expect("projects")[0];
+        assert_eq!(api.source.as_deref(), Some("services/api"));
+        assert!(api.source_path.is_none());
+    }
+}
@@ -95,9 +95,44 @@ fn resolve_sub_project(mut config: Config, name: &str) -> Result<Config> {
     Ok(config)
 }
 
+/// Select the named `[environments.<name>]` overlay of `config.project`.
+/// Only the environment's `frontend_app_id` matters before the server config
+/// is fetched; the full overlay is re-applied afterwards by
+/// [`apply_environment`] so local environment values win over server ones.
+fn resolve_environment(mut config: Config, name: &str) -> Result<Config> {
+    let environment = config.project.environment(name).cloned().ok_or_else(|| {
+        let available = config.project.environment_names();
+        anyhow!(fail_message(&format!(
+            "Environment '{}' not found for project '{}'. Available: {}",
+            name,
+            config.project.name,
+            if available.is_empty() {
+                "none (add [project.environments.<name>] to .smb/config.toml)".to_string()
+            } else {
+                available.join(", ")
+            }
+        )))
+    })?;
+
+    config.project.apply_environment(&environment);
+    config.environment = Some(name.to_owned());
+    Ok(config)
+}
+
+fn apply_environment(config: &mut Config) {
+    let environment = match config.environment.as_deref() {
+        Some(name) => config.project.environment(name).cloned(),
+        None => None,
+    };
+    if let Some(environment) = environment {
+        config.project.apply_environment(&environment);
+    }
+}
+
 pub async fn process_deploy(
     env: Environment,
     project_name: Option<String>,
+    environment_name: Option<String>,
 ) -> Result<CommandResult> {
     // Check credentials.
     let is_logged_in = is_logged_in(env).await?;
@@ -106,8 +141,8 @@ pub async fn process_deploy(
         // Logging in is interactive; in CI the token must already exist.
         if crate::ci::is_ci() {
             return Err(anyhow!(fail_message(
-                "Not authenticated. In --ci mode, log in beforehand (run `smb login` without --ci, \
-                 or provision the token at ~/.smb/token) — interactive login is disabled."
+                "Not authenticated. In --ci mode, set SMB_TOKEN to a token from `smb token create` \
+                 (or run `smb login` beforehand without --ci) — interactive login is disabled."
             )));
         }
         let _ = process_login(env, Some(is_logged_in)).await?;
@@ -132,7 +167,12 @@ pub async fn process_deploy(
         config = resolve_sub_project(config, name)?;
     }
 
+    if let Some(ref name) = environment_name {
+        config = resolve_environment(config, name)?;
+    }
+
     overlay_server_config(env, &access_token, &mut config).await;
+    apply_environment(&mut config);
 
     // Validate that the logged-in user has access to this project before doing
     // any work — applies to every deployment path including vite-spa.
@@ -170,13 +210,13 @@ pub async fn process_deploy(
             // detection needed, the source tree may have no package.json/Gemfile/etc.
             let runner = config.project.runner;
             let user = me(env, client(), &access_token).await?;
-            let transport = crate::deploy::rsync_transport(&config, &runner, user.id)?;
+            let transport = crate::deploy::rsync_transport(env, &config, &runner, user.id)?;
 
             // The engine ships silently; this command owns the spinner and the
             // final line it hands back to `main` to persist.
             let spinner = Spinner::new(
                 spinners::Spinners::Hamburger,
-                succeed_message(&format!("Syncing to {}…", runner.rsync_host())),
+                succeed_message(&format!("Syncing to {}…", env.deploy_host(&runner))),
             );
             match transport.ship(std::path::Path::new("."), &smbcloud_deploy::NoopReporter) {
                 Ok(()) => Ok(CommandResult {
@@ -247,7 +287,7 @@ async fn git_deploy(
         None => return Err(anyhow!(fail_message("Repository not found."))),
     };
 
-    let mut origin = remote_deployment_setup(&runner, &repo, repository).await?;
+    let mut origin = remote_deployment_setup(env, &runner, &repo, repository).await?;
 
     let commit_hash = match main_branch.resolve() {
         Ok(result) => match result.target() {
@@ -263,6 +303,7 @@ async fn git_deploy(
         commit_hash: commit_hash.to_string(),
         status: DeploymentStatus::Started,
         frontend_app_id: frontend_app_id.clone(),
+        environment: config.environment.clone(),
     };
 
     let created_deployment =
@@ -300,6 +341,7 @@ async fn git_deploy(
         let project_id_for_update_cb = update_project_id;
         let deployment_id_for_update_cb = update_deployment_id;
         let frontend_app_id_for_update_cb = frontend_app_id.clone();
+        let environment_for_update_cb = config.environment.clone();
 
         move |_refname, status_message| {
             if let Some(e) = status_message {
@@ -314,6 +356,7 @@ async fn git_deploy(
                         commit_hash: commit_hash.to_string(),
                         status: DeploymentStatus::Failed,
                         frontend_app_id: frontend_app_id_for_update_cb.clone(),
+                        environment: environment_for_update_cb.clone(),
                     };
 
                     // We are in a sync callback, so we need to block on the async task.
@@ -355,6 +398,7 @@ async fn git_deploy(
                 commit_hash: commit_hash.to_string(),
                 status: DeploymentStatus::Done,
                 frontend_app_id: frontend_app_id.clone(),
+                environment: config.environment.clone(),
             };
             let result = update(
                 env,
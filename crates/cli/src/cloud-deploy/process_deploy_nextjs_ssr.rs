@@ -262,6 +262,7 @@ pub async fn process_deploy_nextjs_ssr(env: Environment, config: Config) -> Resu
             commit_hash: deploy_ref.clone(),
             status: DeploymentStatus::Started,
             frontend_app_id: config.project.frontend_app_id.clone(),
+            environment: config.environment.clone(),
         },
     )
     .await
@@ -279,7 +280,7 @@ pub async fn process_deploy_nextjs_ssr(env: Environment, config: Config) -> Resu
     // `node server.js` can find them at runtime.
 
     let runner = config.project.runner;
-    let rsync_host = runner.rsync_host();
+    let rsync_host = env.deploy_host(&runner);
 
     let home = dirs::home_dir().ok_or_else(|| anyhow!("Could not determine home directory"))?;
     let identity_file = home.join(".ssh").join(format!("id_{}@smbcloud", user.id));
@@ -784,6 +785,7 @@ EOF
                 commit_hash: deploy_ref,
                 status: DeploymentStatus::Done,
                 frontend_app_id: config.project.frontend_app_id.clone(),
+                environment: config.environment.clone(),
             },
         )
         .await
@@ -820,6 +822,7 @@ async fn mark_failed(
                 commit_hash: deploy_ref.to_owned(),
                 status: DeploymentStatus::Failed,
                 frontend_app_id: config.project.frontend_app_id.clone(),
+                environment: config.environment.clone(),
             },
         )
         .await;
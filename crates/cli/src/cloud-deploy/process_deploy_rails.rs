@@ -62,7 +62,7 @@ pub async fn process_deploy_rails(env: Environment, config: Config) -> Result<Co
     let access_token = crate::token::get_smb_token::get_smb_token(env)?;
     let user = me(env, client(), &access_token).await?;
     let runner = config.project.runner;
-    let rsync_host = runner.rsync_host();
+    let rsync_host = env.deploy_host(&runner);
 
     let home = dirs::home_dir().ok_or_else(|| anyhow!("Could not determine home directory"))?;
     let identity_file = home.join(".ssh").join(format!("id_{}@smbcloud", user.id));
@@ -198,6 +198,7 @@ source ~/.bashrc 2>/dev/null || true
             commit_hash: deploy_ref.clone(),
             status: DeploymentStatus::Started,
             frontend_app_id: config.project.frontend_app_id.clone(),
+            environment: config.environment.clone(),
         },
     )
     .await
@@ -215,7 +216,7 @@ source ~/.bashrc 2>/dev/null || true
     //
     // The temporary .git directory is cleaned up after the push.
 
-    let git_host = runner.git_host();
+    let git_host = env.git_host(&runner);
     let remote_url = format!("{}:{}.git", git_host, repository);
 
     let mut push_spinner = Spinner::new(
@@ -314,6 +315,7 @@ source ~/.bashrc 2>/dev/null || true
                 commit_hash: deploy_ref,
                 status: DeploymentStatus::Done,
                 frontend_app_id: config.project.frontend_app_id.clone(),
+                environment: config.environment.clone(),
             },
         )
         .await
@@ -388,6 +390,7 @@ async fn mark_failed(
                 commit_hash: deploy_ref.to_owned(),
                 status: DeploymentStatus::Failed,
                 frontend_app_id: config.project.frontend_app_id.clone(),
+                environment: config.environment.clone(),
             },
         )
         .await;
@@ -73,7 +73,7 @@ pub async fn process_deploy_rust(env: Environment, config: Config) -> Result<Com
     let access_token = crate::token::get_smb_token::get_smb_token(env)?;
     let user = me(env, client(), &access_token).await?;
     let runner = config.project.runner;
-    let rsync_host = runner.rsync_host();
+    let rsync_host = env.deploy_host(&runner);
 
     let deploy_ref = git2::Repository::discover(source)
         .ok()
@@ -92,6 +92,7 @@ pub async fn process_deploy_rust(env: Environment, config: Config) -> Result<Com
             commit_hash: deploy_ref.clone(),
             status: DeploymentStatus::Started,
             frontend_app_id: config.project.frontend_app_id.clone(),
+            environment: config.environment.clone(),
         },
     )
     .await
@@ -285,6 +286,7 @@ pub async fn process_deploy_rust(env: Environment, config: Config) -> Result<Com
                 commit_hash: deploy_ref,
                 status: DeploymentStatus::Done,
                 frontend_app_id: config.project.frontend_app_id.clone(),
+                environment: config.environment.clone(),
             },
         )
         .await;
@@ -634,6 +636,7 @@ async fn mark_failed(
                 commit_hash: deploy_ref.to_owned(),
                 status: DeploymentStatus::Failed,
                 frontend_app_id: config.project.frontend_app_id.clone(),
+                environment: config.environment.clone(),
             },
         )
         .await;
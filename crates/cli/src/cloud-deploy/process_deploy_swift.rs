@@ -84,7 +84,7 @@ pub async fn process_deploy_swift(env: Environment, config: Config) -> Result<Co
     let access_token = crate::token::get_smb_token::get_smb_token(env)?;
     let user = me(env, client(), &access_token).await?;
     let runner = config.project.runner;
-    let rsync_host = runner.rsync_host();
+    let rsync_host = env.deploy_host(&runner);
 
     let deploy_ref = git2::Repository::discover(source)
         .ok()
@@ -104,6 +104,7 @@ pub async fn process_deploy_swift(env: Environment, config: Config) -> Result<Co
             commit_hash: deploy_ref.clone(),
             status: DeploymentStatus::Started,
             frontend_app_id: config.project.frontend_app_id.clone(),
+            environment: config.environment.clone(),
         },
     )
     .await
@@ -375,6 +376,7 @@ pub async fn process_deploy_swift(env: Environment, config: Config) -> Result<Co
                 commit_hash: deploy_ref,
                 status: DeploymentStatus::Done,
                 frontend_app_id: config.project.frontend_app_id.clone(),
+                environment: config.environment.clone(),
             },
         )
         .await;
@@ -771,6 +773,7 @@ async fn mark_failed(
                 commit_hash: deploy_ref.to_owned(),
                 status: DeploymentStatus::Failed,
                 frontend_app_id: config.project.frontend_app_id.clone(),
+                environment: config.environment.clone(),
             },
         )
         .await;
@@ -50,6 +50,7 @@ pub async fn process_deploy_vite_spa(env: Environment, config: Config) -> Result
         commit_hash: deploy_ref.clone(),
         status: DeploymentStatus::Started,
         frontend_app_id: config.project.frontend_app_id.clone(),
+        environment: config.environment.clone(),
     };
 
     let created_deployment = create_deployment(
@@ -71,7 +72,7 @@ pub async fn process_deploy_vite_spa(env: Environment, config: Config) -> Result
     // transport used for static site deployments.
 
     let runner = config.project.runner;
-    let transport = crate::deploy::rsync_transport(&config, &runner, user.id)?;
+    let transport = crate::deploy::rsync_transport(env, &config, &runner, user.id)?;
     match transport.ship(&artifact.source_dir, &reporter) {
         Ok(()) => {}
         Err(error) => {
@@ -80,6 +81,7 @@ pub async fn process_deploy_vite_spa(env: Environment, config: Config) -> Result
                     commit_hash: deploy_ref.clone(),
                     status: DeploymentStatus::Failed,
                     frontend_app_id: config.project.frontend_app_id.clone(),
+                    environment: config.environment.clone(),
                 };
                 let _ = update(
                     env,
@@ -102,6 +104,7 @@ pub async fn process_deploy_vite_spa(env: Environment, config: Config) -> Result
             commit_hash: deploy_ref,
             status: DeploymentStatus::Done,
             frontend_app_id: config.project.frontend_app_id.clone(),
+            environment: config.environment.clone(),
         };
         match update(
             env,
@@ -133,7 +133,7 @@ fn read_pm2_env_from_ecosystem_file(
 
 /// Build a `DeployConfigUpdate` from the local fields on a `Project`.
 /// Returns `None` when there is nothing to migrate.
-fn build_payload(
+pub(crate) fn build_payload(
     project: &Project,
     pm2_env: Option<HashMap<String, serde_json::Value>>,
 ) -> Option<DeployConfigUpdate> {
@@ -205,6 +205,8 @@ fn strip_project(project: &Project) -> Project {
         rust_target: None,
         swift_sdk: None,
         swift_toolchain: None,
+        // Environments are per-target overrides, not migrated deploy fields.
+        environments: project.environments.clone(),
     }
 }
 
@@ -330,6 +332,7 @@ pub async fn process_migrate(env: Environment) -> Result<CommandResult> {
         description: config.description.clone(),
         project: stripped_root,
         projects: stripped_projects,
+        environment: None,
     };
 
     if let Err(e) = write_config(".", stripped_config) {
@@ -111,6 +111,7 @@ pub(crate) async fn setup_project(
         name,
         description,
         projects: None,
+        environment: None,
     };
 
     // Ensure .smb directory exists
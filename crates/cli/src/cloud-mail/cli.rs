@@ -1,4 +1,4 @@
-use clap::Subcommand;
+use {crate::cli::PageArgs, clap::Subcommand};
 
 #[derive(Subcommand)]
 pub enum Commands {
@@ -107,8 +107,9 @@ pub enum MessageCommands {
         app_id: String,
         #[clap(long, required = true)]
         inbox_id: String,
-        #[clap(long, default_value_t = 10)]
-        limit: u32,
+        // Pages hold 10 messages unless --page-size says otherwise (max 100).
+        #[clap(flatten)]
+        page: PageArgs,
     },
     #[clap(about = "Show one inbound mail message.")]
     Show {
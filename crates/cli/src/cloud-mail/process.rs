@@ -1,5 +1,5 @@
 use crate::{
-    cli::CommandResult,
+    cli::{CommandResult, PageArgs, MORE_PAGES_MESSAGE},
     client,
     mail::{
         cli::{Commands, InboxCommands, MessageCommands},
@@ -16,7 +16,7 @@ use anyhow::{anyhow, Result};
 use smbcloud_mail::{
     mail_app::{create_mail_app, delete_mail_app, get_mail_app, get_mail_apps, update_mail_app},
     mail_inbox::{create_mail_inbox, delete_mail_inbox, send_test_email, update_mail_inbox},
-    mail_message::{get_mail_message, get_mail_messages},
+    mail_message::{get_mail_message, get_mail_messages_page},
 };
 use smbcloud_model::mail::{
     MailAppCreate, MailAppUpdate, MailInboxCreate, MailInboxUpdate, MailTestEmailRequest,
@@ -80,8 +80,8 @@ async fn process_mail_message(
         MessageCommands::List {
             app_id,
             inbox_id,
-            limit,
-        } => process_mail_message_list(env, app_id, inbox_id, limit).await,
+            page,
+        } => process_mail_message_list(env, app_id, inbox_id, page).await,
         MessageCommands::Show {
             app_id,
             inbox_id,
@@ -350,27 +350,37 @@ async fn process_mail_message_list(
     env: Environment,
     app_id: String,
     inbox_id: String,
-    limit: u32,
+    page: PageArgs,
 ) -> Result<CommandResult> {
     let access_token = get_smb_token(env)?;
+    let app_id = normalize_required("mail app id", app_id)?;
+    let inbox_id = normalize_required("mail inbox id", inbox_id)?;
+    let page = PageArgs {
+        page_size: Some(page.page_size.unwrap_or(10).clamp(1, 100)),
+        ..page
+    };
     let mut spinner = loading_spinner("Loading mail messages");
 
-    let messages = get_mail_messages(
-        env,
-        client(),
-        access_token,
-        normalize_required("mail app id", app_id)?,
-        normalize_required("mail inbox id", inbox_id)?,
-        Some(limit.clamp(1, 100)),
-    )
-    .await
-    .map_err(api_error)?;
+    let messages = page
+        .fetch(|page_request| {
+            let access_token = access_token.clone();
+            let app_id = app_id.clone();
+            let inbox_id = inbox_id.clone();
+            async move {
+                get_mail_messages_page(env, client(), access_token, app_id, inbox_id, &page_request)
+                    .await
+            }
+        })
+        .await
+        .map_err(api_error)?;
 
     spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
-    print_mail_messages(&messages);
+    print_mail_messages(&messages.items);
 
-    Ok(done_result(if messages.is_empty() {
+    Ok(done_result(if messages.items.is_empty() {
         "No mail messages found."
+    } else if !messages.is_last() {
+        MORE_PAGES_MESSAGE
     } else {
         "Done."
     }))
@@ -1,5 +1,6 @@
 use smbcloud_networking::smb_client::SmbClient;
 
+pub mod access_token;
 pub mod account;
 pub mod ci;
 pub mod cli;
@@ -11,13 +12,18 @@ pub mod interface;
 #[path = "cloud-mail/mod.rs"]
 pub mod mail;
 pub mod mcp;
+pub mod profile;
 pub mod project;
 mod session_config;
 pub mod tenant;
 mod token;
 pub mod ui;
 
-pub use token::clear_smb_token::clear_smb_token;
+pub use token::{
+    clear_smb_token::clear_smb_token,
+    get_smb_token::env_smb_token,
+    renewal::{install_session_renewer, renew_if_expiring},
+};
 
 pub(crate) fn client() -> (&'static SmbClient, &'static str) {
     let secret = env!("CLI_CLIENT_SECRET");
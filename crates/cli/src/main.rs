@@ -3,16 +3,28 @@ use {
     clap::Parser,
     console::style,
     smbcloud_cli::{
-        account::{login::process_login, logout::process_logout, me::process_me, process_account},
+        access_token::process::process_access_token,
+        account::{
+            login::{process_login_device, process_login_sso, process_login_with_otp},
+            logout::process_logout,
+            me::process_me,
+            process_account,
+        },
         clear_smb_token,
         cli::{Cli, CommandResult, Commands},
         cloud_auth::process::process_cloud_auth,
-        deploy::{process_deploy::process_deploy, process_migrate::process_migrate},
+        deploy::{
+            process_config::process_config, process_deploy::process_deploy,
+            process_migrate::process_migrate,
+        },
+        env_smb_token, install_session_renewer,
         mail::process::process_mail,
+        profile::process::process_profile,
         project::{crud_create::process_project_init, process::process_project},
+        renew_if_expiring,
         tenant::process::process_tenant,
     },
-    smbcloud_network::{environment::Environment, network::check_internet_connection},
+    smbcloud_network::environment::Environment,
     std::{
         fs::{create_dir_all, OpenOptions},
         path::PathBuf,
@@ -95,6 +107,21 @@ async fn main() {
     };
     smbcloud_cli::interface::set_interface(interface);
 
+    // Resolve the profile once, before anything reads the token or session.
+    match smbcloud_cli::profile::resolve(environment, cli.profile.clone()) {
+        Ok(profile) => smbcloud_cli::profile::set_profile(profile),
+        Err(e) => {
+            eprintln!(
+                "{} {}",
+                style("✘".to_string()).for_stderr().red(),
+                style(e).red()
+            );
+            std::process::exit(1);
+        }
+    }
+    // From here on a rejected access token is renewed when it can be.
+    install_session_renewer(environment);
+
     // MCP mode runs a stdio server instead of a one-shot command. It implies
     // non-interactive, and all diagnostics must go to stderr so the stdout
     // JSON-RPC stream stays clean.
@@ -119,7 +146,13 @@ async fn main() {
             std::process::exit(0);
         }
         Err(e) => {
-            if e.to_string().contains("Unauthorized access.") {
+            if e.to_string().contains("Unauthorized access.") && env_smb_token().is_some() {
+                println!(
+                    "\n{} {}",
+                    style("✘".to_string()).for_stderr().red(),
+                    style("SMB_TOKEN was rejected. It may have expired or been revoked.").red()
+                );
+            } else if e.to_string().contains("Unauthorized access.") {
                 let _ = clear_smb_token(environment);
                 println!(
                     "\n{} {}",
@@ -150,6 +183,7 @@ async fn run_mcp(cli: Cli) -> Result<()> {
     } else {
         setup_logging(cli.environment, None)?;
     }
+    smbcloud_cli::profile::ensure_exists(cli.environment)?;
     smbcloud_cli::mcp::serve(cli.environment).await
 }
 
@@ -171,41 +205,34 @@ async fn run(cli: Cli) -> Result<CommandResult> {
         setup_logging(cli.environment, None)?;
     }
 
-    // Check if the command requires internet connection
-    let needs_internet = match &cli.command {
-        Some(Commands::Me {})
-        | Some(Commands::Deploy { .. })
-        | Some(Commands::Login {})
-        | Some(Commands::Logout {})
-        | Some(Commands::Account { .. })
-        | Some(Commands::Mail { .. })
-        | Some(Commands::Auth { .. })
-        | Some(Commands::Project { .. })
-        | Some(Commands::Tenant { .. })
-        | Some(Commands::Migrate {})
-        | None => true,
-        Some(Commands::Init {}) => true,
-    };
-
-    // Check internet connectivity for commands that need it
-    if needs_internet && !check_internet_connection().await {
-        return Err(anyhow!(
-            "No internet connection. Please check your network settings and try again."
-        ));
+    // `smb profile` manages profiles, so it runs even if the active one is gone.
+    if !matches!(cli.command, Some(Commands::Profile { .. })) {
+        smbcloud_cli::profile::ensure_exists(cli.environment)?;
     }
+    renew_if_expiring(cli.environment).await;
 
     match cli.command {
         Some(Commands::Me {}) => process_me(cli.environment).await,
         Some(Commands::Init {}) => process_project_init(cli.environment, true).await,
-        Some(Commands::Deploy { project }) => process_deploy(cli.environment, project).await,
+        Some(Commands::Deploy {
+            project,
+            deploy_environment,
+        }) => process_deploy(cli.environment, project, deploy_environment).await,
         Some(Commands::Account { command }) => process_account(cli.environment, command).await,
-        Some(Commands::Login {}) => process_login(cli.environment, None).await,
+        Some(Commands::Login { sso: true, .. }) => process_login_sso(cli.environment).await,
+        Some(Commands::Login { device: true, .. }) => process_login_device(cli.environment).await,
+        Some(Commands::Login { otp, .. }) => {
+            process_login_with_otp(cli.environment, None, otp).await
+        }
         Some(Commands::Logout {}) => process_logout(cli.environment).await,
         Some(Commands::Mail { command }) => process_mail(cli.environment, command).await,
         Some(Commands::Auth { command }) => process_cloud_auth(cli.environment, command).await,
+        Some(Commands::Token { command }) => process_access_token(cli.environment, command).await,
+        Some(Commands::Profile { command }) => process_profile(cli.environment, command).await,
         Some(Commands::Project { command }) => process_project(cli.environment, command).await,
         Some(Commands::Tenant { command }) => process_tenant(cli.environment, command).await,
         Some(Commands::Migrate {}) => process_migrate(cli.environment).await,
-        None => process_deploy(cli.environment, None).await,
+        Some(Commands::Config { command }) => process_config(cli.environment, command).await,
+        None => process_deploy(cli.environment, None, None).await,
     }
 }
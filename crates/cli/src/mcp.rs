@@ -18,8 +18,16 @@ use {
     crate::{
         account::lib::is_logged_in,
         client,
+        cloud_auth::{
+            auth_app_client::{
+                create_auth_app_client, delete_auth_app_client, get_auth_app_client,
+                get_auth_app_clients, update_auth_app_client,
+            },
+            auth_app_user::{get_auth_app_user, get_auth_app_users_page},
+            redirect_uri::redirect_uris_allowlist,
+        },
         mail::current_project::{resolve_optional_project_id, resolve_required_project_id},
-        token::get_smb_token::get_smb_token,
+        token::{get_smb_token::get_smb_token, renewal::renew_if_expiring},
     },
     anyhow::{anyhow, Result},
     rmcp::{
@@ -40,10 +48,13 @@ use {
         mail_message::{get_mail_message, get_mail_messages},
     },
     smbcloud_model::{
-        app_auth::{AuthAppCreate, AuthAppUpdate},
+        app_auth::{
+            AuthApp, AuthAppClientCreate, AuthAppClientUpdate, AuthAppCreate, AuthAppUpdate,
+        },
         mail::{
             MailAppCreate, MailAppUpdate, MailInboxCreate, MailInboxUpdate, MailTestEmailRequest,
         },
+        pagination::PageRequest,
         project::ProjectCreate,
         tenant::{TenantCreate, TenantUpdate},
     },
@@ -69,14 +80,16 @@ impl SmbMcpServer {
     }
 
     /// Resolve the stored auth token, mapping "not logged in" and read failures
-    /// to MCP errors. Every tool that hits the API goes through this.
-    fn access_token(&self) -> Result<String, ErrorData> {
+    /// to MCP errors. Every tool that hits the API goes through this, so a
+    /// session about to expire is renewed here first.
+    async fn access_token(&self) -> Result<String, ErrorData> {
         if !is_logged_in(self.environment) {
             return Err(ErrorData::invalid_request(
                 "Not logged in. Run `smb login` first.",
                 None,
             ));
         }
+        renew_if_expiring(self.environment).await;
         get_smb_token(self.environment).map_err(to_error_data)
     }
 }
@@ -116,6 +129,9 @@ struct ProjectUseArgs {
 struct DeploymentsArgs {
     /// The project ID whose deployments to list.
     project_id: i32,
+    /// Only list deployments made to this deploy environment, e.g. "staging".
+    #[serde(default)]
+    environment: Option<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -341,12 +357,85 @@ struct AuthAppDeleteArgs {
     id: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AuthClientListArgs {
+    /// The Auth app whose OAuth clients to list.
+    app_id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AuthClientShowArgs {
+    /// The Auth app the OAuth client belongs to.
+    app_id: String,
+    /// The OAuth client ID to show.
+    id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AuthClientNewArgs {
+    /// The Auth app to register the OAuth client on.
+    app_id: String,
+    /// Name for the new OAuth client.
+    name: String,
+    /// Allowed redirect URIs. Each must be https, a loopback http URL, or a
+    /// custom scheme.
+    redirect_uris: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AuthClientUpdateArgs {
+    /// The Auth app the OAuth client belongs to.
+    app_id: String,
+    /// The OAuth client ID to update.
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    /// Replaces the whole redirect URI allowlist when given.
+    #[serde(default)]
+    redirect_uris: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AuthClientDeleteArgs {
+    /// The Auth app the OAuth client belongs to.
+    app_id: String,
+    /// The OAuth client ID to delete.
+    id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AuthUserListArgs {
+    /// The Auth app whose users to list.
+    app_id: String,
+    /// Only users whose email contains this.
+    #[serde(default)]
+    email: Option<String>,
+    /// Max number of users to return.
+    #[serde(default = "default_user_limit")]
+    limit: u32,
+    /// The `next_cursor` of the previous page, to fetch the one after it.
+    #[serde(default)]
+    cursor: Option<String>,
+}
+
+fn default_user_limit() -> u32 {
+    50
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AuthUserShowArgs {
+    /// The Auth app the user belongs to.
+    app_id: String,
+    /// The user ID to show.
+    id: String,
+}
+
 #[tool_router]
 impl SmbMcpServer {
     #[tool(description = "Get the authenticated smbCloud user's account info. \
                           Requires a prior `smb login`; returns the user as JSON.")]
     async fn me(&self) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let user = me(self.environment, client(), &token)
             .await
             .map_err(to_error_data)?;
@@ -357,7 +446,7 @@ impl SmbMcpServer {
 
     #[tool(description = "List the authenticated user's smbCloud projects as a JSON array.")]
     async fn project_list(&self) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let projects = get_projects(self.environment, client(), token)
             .await
             .map_err(to_error_data)?;
@@ -369,7 +458,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<ProjectShowArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let project = get_project(self.environment, client(), token, args.id)
             .await
             .map_err(to_error_data)?;
@@ -385,7 +474,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<ProjectUseArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let project = get_project(self.environment, client(), token, args.id)
             .await
             .map_err(to_error_data)?;
@@ -401,10 +490,19 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<DeploymentsArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
-        let deployments = get_deployments(self.environment, client(), token, args.project_id)
-            .await
-            .map_err(to_error_data)?;
+        let token = self.access_token().await?;
+        let mut deployments = get_deployments(
+            self.environment,
+            client(),
+            token,
+            args.project_id,
+            args.environment.as_deref(),
+        )
+        .await
+        .map_err(to_error_data)?;
+        if let Some(ref name) = args.environment {
+            deployments.retain(|deployment| deployment.environment.as_deref() == Some(name));
+        }
         json_result(&deployments)
     }
 
@@ -418,7 +516,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<ProjectCreateArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let payload = ProjectCreate {
             name: args.name,
             description: args.description,
@@ -438,7 +536,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<ProjectUpdateArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         // `update_project` requires the runner; fetch the current project so the
         // description-only update doesn't clobber it.
         let current = get_project(self.environment, client(), token.clone(), args.id.clone())
@@ -465,7 +563,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<ProjectDeleteArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         delete_project(self.environment, client(), token, args.id)
             .await
             .map_err(to_error_data)?;
@@ -478,7 +576,7 @@ impl SmbMcpServer {
         description = "List the authenticated user's smbCloud tenants (workspaces) as a JSON array."
     )]
     async fn tenant_list(&self) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let tenants = crate::tenant::tenant_client::get_tenants(self.environment, client(), token)
             .await
             .map_err(to_error_data)?;
@@ -490,7 +588,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<TenantShowArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let tenant =
             crate::tenant::tenant_client::get_tenant(self.environment, client(), token, args.id)
                 .await
@@ -507,7 +605,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<TenantNewArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let tenant = crate::tenant::tenant_client::create_tenant(
             self.environment,
             client(),
@@ -524,7 +622,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<TenantUpdateArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let tenant = crate::tenant::tenant_client::update_tenant(
             self.environment,
             client(),
@@ -548,7 +646,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<TenantDeleteArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         crate::tenant::tenant_client::delete_tenant(self.environment, client(), token, args.id)
             .await
             .map_err(to_error_data)?;
@@ -565,7 +663,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<TenantUseArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let tenant =
             crate::tenant::tenant_client::get_tenant(self.environment, client(), token, args.id)
                 .await
@@ -582,7 +680,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailAppListArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let project_id = resolve_optional_project_id(self.environment, args.project_id)
             .map_err(to_error_data)?;
         let mail_apps = get_mail_apps(self.environment, client(), token, project_id)
@@ -596,7 +694,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailAppShowArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let mail_app = get_mail_app(self.environment, client(), token, args.id)
             .await
             .map_err(to_error_data)?;
@@ -612,7 +710,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailAppNewArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let project_id = resolve_required_project_id(self.environment, args.project_id)
             .map_err(to_error_data)?;
         let mail_app = create_mail_app(
@@ -638,7 +736,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailAppUpdateArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let update = MailAppUpdate {
             name: args.name,
             domain: args.domain,
@@ -663,7 +761,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailAppDeleteArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         delete_mail_app(self.environment, client(), token, args.id)
             .await
             .map_err(to_error_data)?;
@@ -679,7 +777,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailInboxNewArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let inbox = create_mail_inbox(
             self.environment,
             client(),
@@ -703,7 +801,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailInboxUpdateArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let update = MailInboxUpdate {
             local_part: args.local_part,
             forward_to_email: args.forward_to_email,
@@ -732,7 +830,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailInboxDeleteArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         delete_mail_inbox(self.environment, client(), token, args.app_id, args.id)
             .await
             .map_err(to_error_data)?;
@@ -746,7 +844,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailInboxTestArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let delivery = send_test_email(
             self.environment,
             client(),
@@ -773,7 +871,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailMessageListArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let messages = get_mail_messages(
             self.environment,
             client(),
@@ -792,7 +890,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<MailMessageShowArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let message = get_mail_message(
             self.environment,
             client(),
@@ -808,12 +906,15 @@ impl SmbMcpServer {
 
     // ── Auth apps ────────────────────────────────────────────────────────
 
-    #[tool(description = "List the authenticated user's smbCloud Auth apps as a JSON array.")]
+    #[tool(
+        description = "List the authenticated user's smbCloud Auth apps as a JSON array. \
+                       Secrets are masked; `smb auth secret show --reveal` prints one."
+    )]
     async fn auth_app_list(
         &self,
         Parameters(args): Parameters<AuthAppListArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let project_id = resolve_optional_project_id(self.environment, args.project_id)
             .map_err(to_error_data)?;
         let auth_apps = crate::cloud_auth::auth_app::get_auth_apps(
@@ -824,20 +925,21 @@ impl SmbMcpServer {
         )
         .await
         .map_err(to_error_data)?;
+        let auth_apps: Vec<_> = auth_apps.into_iter().map(AuthApp::masked).collect();
         json_result(&auth_apps)
     }
 
-    #[tool(description = "Show a single Auth app by ID, returned as JSON.")]
+    #[tool(description = "Show a single Auth app by ID, returned as JSON with its secret masked.")]
     async fn auth_app_show(
         &self,
         Parameters(args): Parameters<AuthAppShowArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let auth_app =
             crate::cloud_auth::auth_app::get_auth_app(self.environment, client(), token, args.id)
                 .await
                 .map_err(to_error_data)?;
-        json_result(&auth_app)
+        json_result(&auth_app.masked())
     }
 
     #[tool(
@@ -849,7 +951,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<AuthAppNewArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let project_id = resolve_required_project_id(self.environment, args.project_id)
             .map_err(to_error_data)?;
         let auth_app = crate::cloud_auth::auth_app::create_auth_app(
@@ -864,7 +966,7 @@ impl SmbMcpServer {
         )
         .await
         .map_err(to_error_data)?;
-        json_result(&auth_app)
+        json_result(&auth_app.masked())
     }
 
     #[tool(
@@ -874,7 +976,7 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<AuthAppUpdateArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         let update = AuthAppUpdate {
             name: args.name,
             support_email: args.support_email,
@@ -893,7 +995,7 @@ impl SmbMcpServer {
         )
         .await
         .map_err(to_error_data)?;
-        json_result(&auth_app)
+        json_result(&auth_app.masked())
     }
 
     #[tool(
@@ -904,12 +1006,158 @@ impl SmbMcpServer {
         &self,
         Parameters(args): Parameters<AuthAppDeleteArgs>,
     ) -> Result<CallToolResult, ErrorData> {
-        let token = self.access_token()?;
+        let token = self.access_token().await?;
         crate::cloud_auth::auth_app::delete_auth_app(self.environment, client(), token, args.id)
             .await
             .map_err(to_error_data)?;
         text_result("Auth app deleted.")
     }
+
+    // ── Auth app OAuth clients ───────────────────────────────────────────
+
+    #[tool(description = "List the OAuth clients of an Auth app as a JSON array.")]
+    async fn auth_client_list(
+        &self,
+        Parameters(args): Parameters<AuthClientListArgs>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let token = self.access_token().await?;
+        let auth_app_clients = get_auth_app_clients(self.environment, client(), token, args.app_id)
+            .await
+            .map_err(to_error_data)?;
+        json_result(&auth_app_clients)
+    }
+
+    #[tool(description = "Show a single OAuth client of an Auth app, returned as JSON.")]
+    async fn auth_client_show(
+        &self,
+        Parameters(args): Parameters<AuthClientShowArgs>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let token = self.access_token().await?;
+        let auth_app_client =
+            get_auth_app_client(self.environment, client(), token, args.app_id, args.id)
+                .await
+                .map_err(to_error_data)?;
+        json_result(&auth_app_client)
+    }
+
+    #[tool(
+        description = "Register a public OAuth client (Authorization Code + PKCE) on an Auth \
+                       app. Each redirect URI must be https, a loopback http URL, or a custom \
+                       scheme. Returns the created client, including its client_id, as JSON."
+    )]
+    async fn auth_client_new(
+        &self,
+        Parameters(args): Parameters<AuthClientNewArgs>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let redirect_uris = redirect_uris_allowlist(&args.redirect_uris)
+            .map_err(|e| invalid_request(e.to_string()))?;
+        let token = self.access_token().await?;
+        let auth_app_client = create_auth_app_client(
+            self.environment,
+            client(),
+            token,
+            args.app_id,
+            AuthAppClientCreate {
+                name: args.name,
+                redirect_uris,
+            },
+        )
+        .await
+        .map_err(to_error_data)?;
+        json_result(&auth_app_client)
+    }
+
+    #[tool(
+        description = "Update an OAuth client's name and/or redirect URIs. redirect_uris \
+                       replaces the whole allowlist. Returns the updated client as JSON."
+    )]
+    async fn auth_client_update(
+        &self,
+        Parameters(args): Parameters<AuthClientUpdateArgs>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let redirect_uris = args
+            .redirect_uris
+            .map(|redirect_uris| redirect_uris_allowlist(&redirect_uris))
+            .transpose()
+            .map_err(|e| invalid_request(e.to_string()))?;
+        let update = AuthAppClientUpdate {
+            name: args.name,
+            redirect_uris,
+        };
+        if update.is_empty() {
+            return Err(invalid_request(
+                "Specify at least one of name or redirect_uris.",
+            ));
+        }
+        let token = self.access_token().await?;
+        let auth_app_client = update_auth_app_client(
+            self.environment,
+            client(),
+            token,
+            args.app_id,
+            args.id,
+            update,
+        )
+        .await
+        .map_err(to_error_data)?;
+        json_result(&auth_app_client)
+    }
+
+    #[tool(
+        description = "Delete an OAuth client from an Auth app. This is destructive and \
+                       irreversible — apps using its client_id can no longer sign users in."
+    )]
+    async fn auth_client_delete(
+        &self,
+        Parameters(args): Parameters<AuthClientDeleteArgs>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let token = self.access_token().await?;
+        delete_auth_app_client(self.environment, client(), token, args.app_id, args.id)
+            .await
+            .map_err(to_error_data)?;
+        text_result("OAuth client deleted.")
+    }
+
+    // ── Auth app users ───────────────────────────────────────────────────
+
+    #[tool(
+        description = "List the end users of an Auth app, optionally filtered by email. Returns \
+                       one page as JSON: `items` plus a `next_cursor` to pass back as `cursor` \
+                       for the next page (null on the last page). Read-only."
+    )]
+    async fn auth_user_list(
+        &self,
+        Parameters(args): Parameters<AuthUserListArgs>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let token = self.access_token().await?;
+        let page = PageRequest {
+            cursor: args.cursor,
+            limit: Some(args.limit),
+        };
+        let users = get_auth_app_users_page(
+            self.environment,
+            client(),
+            token,
+            args.app_id,
+            args.email,
+            &page,
+        )
+        .await
+        .map_err(to_error_data)?;
+        json_result(&users)
+    }
+
+    #[tool(description = "Show a single end user of an Auth app, returned as JSON. Read-only.")]
+    async fn auth_user_show(
+        &self,
+        Parameters(args): Parameters<AuthUserShowArgs>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let token = self.access_token().await?;
+        let user = get_auth_app_user(self.environment, client(), token, args.app_id, args.id)
+            .await
+            .map_err(to_error_data)?;
+        json_result(&user)
+    }
 }
 
 #[tool_handler]
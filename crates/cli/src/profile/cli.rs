@@ -0,0 +1,16 @@
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum Commands {
+    #[clap(about = "List your profiles. The active one is marked with *.")]
+    List {},
+    #[clap(about = "Create a profile. Log in to it with `smb --profile <NAME> login`.")]
+    Add {
+        /// Letters, digits, `-` and `_`.
+        name: String,
+    },
+    #[clap(about = "Use a profile when --profile is not given.")]
+    Use { name: String },
+    #[clap(about = "Remove a profile and its stored token.")]
+    Remove { name: String },
+}
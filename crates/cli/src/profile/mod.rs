@@ -0,0 +1,241 @@
+//! Named profiles: several smbCloud logins side by side, one per client
+//! account.
+//!
+//! Each profile has its own token and its own session config (current
+//! tenant and project). The `default` profile keeps the paths the CLI has
+//! always used (`~/.smb/config.toml`, `~/.smb/token.enc`); a named profile
+//! lives under `~/.smb/profiles/<name>/`.
+//!
+//! The active profile is picked once in `main` — `--profile`/`SMB_PROFILE`,
+//! else the one chosen with `smb profile use`, else `default` — and stored
+//! here the same way [`crate::ci`] stores CI mode.
+
+pub mod cli;
+pub mod process;
+
+use {
+    anyhow::{anyhow, Result},
+    serde::{Deserialize, Serialize},
+    smbcloud_network::environment::Environment,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::RwLock,
+    },
+};
+
+/// The profile used when none was picked.
+pub const DEFAULT_PROFILE: &str = "default";
+const PROFILES_DIR: &str = "profiles";
+const STATE_FILE: &str = "profiles.toml";
+
+static PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+/// What `smb profile use` persists in `~/.smb/profiles.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileState {
+    active: Option<String>,
+}
+
+/// Set the active profile. Called once from `main`.
+pub fn set_profile(name: String) {
+    *PROFILE.write().unwrap_or_else(|e| e.into_inner()) = Some(name);
+}
+
+/// The active profile.
+pub fn current() -> String {
+    PROFILE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Resolve the active profile from the parsed `--profile`/`SMB_PROFILE`
+/// value, falling back to the one saved by `smb profile use`.
+pub fn resolve(env: Environment, flag: Option<String>) -> Result<String> {
+    if let Some(name) = flag.filter(|name| !name.trim().is_empty()) {
+        let name = name.trim().to_string();
+        validate_name(&name)?;
+        return Ok(name);
+    }
+    active(env)
+}
+
+/// Fails with a hint when the active profile was never created.
+pub fn ensure_exists(env: Environment) -> Result<()> {
+    let name = current();
+    if exists_in(&smb_root(env)?, &name) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Profile `{name}` does not exist. Create it with `smb profile add {name}`."
+        ))
+    }
+}
+
+/// Directory holding `profile`'s session config and token file.
+pub(crate) fn profile_dir(env: Environment, profile: &str) -> Result<PathBuf> {
+    Ok(profile_dir_in(&smb_root(env)?, profile))
+}
+
+/// Profile names become directory names and keyring entries, so they stay
+/// short and plain.
+pub(crate) fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Invalid profile name `{name}`. Use up to 64 letters, digits, `-` or `_`."
+        ))
+    }
+}
+
+pub(crate) fn list(env: Environment) -> Result<Vec<String>> {
+    list_in(&smb_root(env)?)
+}
+
+pub(crate) fn exists(env: Environment, name: &str) -> Result<bool> {
+    Ok(exists_in(&smb_root(env)?, name))
+}
+
+/// Creates an empty profile. Logging in fills it.
+pub(crate) fn add(env: Environment, name: &str) -> Result<()> {
+    validate_name(name)?;
+    let root = smb_root(env)?;
+    if exists_in(&root, name) {
+        return Err(anyhow!("Profile `{name}` already exists."));
+    }
+    let dir = profile_dir_in(&root, name);
+    fs::create_dir_all(&dir).map_err(|e| anyhow!("Failed to create `{}`: {e}", dir.display()))
+}
+
+/// Makes `name` the profile used when `--profile` is not given.
+pub(crate) fn set_active(env: Environment, name: &str) -> Result<()> {
+    let root = smb_root(env)?;
+    let active = (name != DEFAULT_PROFILE).then(|| name.to_string());
+    write_state(&root, &ProfileState { active })
+}
+
+/// The profile saved by `smb profile use`.
+pub(crate) fn active(env: Environment) -> Result<String> {
+    Ok(read_state(&smb_root(env)?)?
+        .active
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string()))
+}
+
+/// Deletes a named profile's directory. The caller clears its token first,
+/// since a keyring entry lives outside the directory.
+pub(crate) fn remove(env: Environment, name: &str) -> Result<()> {
+    let root = smb_root(env)?;
+    let dir = profile_dir_in(&root, name);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)
+            .map_err(|e| anyhow!("Failed to remove `{}`: {e}", dir.display()))?;
+    }
+    if read_state(&root)?.active.as_deref() == Some(name) {
+        write_state(&root, &ProfileState::default())?;
+    }
+    Ok(())
+}
+
+fn smb_root(env: Environment) -> Result<PathBuf> {
+    let home = home::home_dir().ok_or_else(|| anyhow!("Failed to resolve your home directory."))?;
+    Ok(home.join(env.smb_dir()))
+}
+
+fn profile_dir_in(root: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        root.to_path_buf()
+    } else {
+        root.join(PROFILES_DIR).join(profile)
+    }
+}
+
+fn exists_in(root: &Path, name: &str) -> bool {
+    name == DEFAULT_PROFILE || profile_dir_in(root, name).is_dir()
+}
+
+fn list_in(root: &Path) -> Result<Vec<String>> {
+    let mut names = vec![DEFAULT_PROFILE.to_string()];
+    let dir = root.join(PROFILES_DIR);
+    if dir.is_dir() {
+        let mut named = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                if let Some(name) = entry.file_name().to_str() {
+                    if validate_name(name).is_ok() && name != DEFAULT_PROFILE {
+                        named.push(name.to_string());
+                    }
+                }
+            }
+        }
+        named.sort();
+        names.extend(named);
+    }
+    Ok(names)
+}
+
+fn read_state(root: &Path) -> Result<ProfileState> {
+    let path = root.join(STATE_FILE);
+    if !path.exists() {
+        return Ok(ProfileState::default());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Failed to read `{}`: {e}", path.display()))?;
+    toml::from_str(&content).map_err(|e| anyhow!("Failed to parse `{}`: {e}", path.display()))
+}
+
+fn write_state(root: &Path, state: &ProfileState) -> Result<()> {
+    fs::create_dir_all(root)?;
+    let path = root.join(STATE_FILE);
+    fs::write(&path, toml::to_string(state)?)
+        .map_err(|e| anyhow!("Failed to write `{}`: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::tempdir};
+
+    #[test]
+    fn validates_profile_names() {
+        assert!(validate_name("client-a_2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../etc").is_err());
+        assert!(validate_name("has space").is_err());
+        assert!(validate_name(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn default_profile_keeps_the_legacy_paths() {
+        let root = Path::new("/home/me/.smb");
+        assert_eq!(profile_dir_in(root, DEFAULT_PROFILE), root);
+        assert_eq!(
+            profile_dir_in(root, "work"),
+            root.join("profiles").join("work")
+        );
+    }
+
+    #[test]
+    fn lists_default_first_then_named_profiles() {
+        let root = tempdir().unwrap();
+        assert_eq!(list_in(root.path()).unwrap(), vec!["default"]);
+
+        for name in ["zeta", "acme"] {
+            fs::create_dir_all(profile_dir_in(root.path(), name)).unwrap();
+        }
+
+        assert_eq!(
+            list_in(root.path()).unwrap(),
+            vec!["default", "acme", "zeta"]
+        );
+        assert!(exists_in(root.path(), "acme"));
+        assert!(!exists_in(root.path(), "missing"));
+    }
+}
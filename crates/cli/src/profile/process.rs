@@ -0,0 +1,106 @@
+use crate::{
+    cli::CommandResult,
+    profile::{self, cli::Commands, DEFAULT_PROFILE},
+    token::credential_store::{credential_store_for, Slot},
+    ui::{fail_message, fail_symbol, prompt::confirm_delete, succeed_message, succeed_symbol},
+};
+use anyhow::{anyhow, Result};
+use console::style;
+use smbcloud_network::environment::Environment;
+use spinners::Spinner;
+
+pub async fn process_profile(env: Environment, commands: Commands) -> Result<CommandResult> {
+    match commands {
+        Commands::List {} => process_profile_list(env),
+        Commands::Add { name } => process_profile_add(env, name),
+        Commands::Use { name } => process_profile_use(env, name),
+        Commands::Remove { name } => process_profile_remove(env, name),
+    }
+}
+
+fn process_profile_list(env: Environment) -> Result<CommandResult> {
+    let current = profile::current();
+    println!("\n{}", style("Profiles").bold().underlined());
+    for name in profile::list(env)? {
+        let marker = if name == current { "*" } else { " " };
+        let logged_in = credential_store_for(env, &name, Slot::AccessToken)
+            .and_then(|store| store.load())
+            .map(|token| token.is_some())
+            .unwrap_or(false);
+        let status = if logged_in { "logged in" } else { "logged out" };
+        println!("{marker}{} ({status})", style(&name).bold());
+    }
+    Ok(done_result("Done."))
+}
+
+fn process_profile_add(env: Environment, name: String) -> Result<CommandResult> {
+    let name = name.trim().to_string();
+    profile::add(env, &name)?;
+    Ok(done_result(&format!(
+        "Profile `{name}` created. Log in with `smb --profile {name} login`."
+    )))
+}
+
+fn process_profile_use(env: Environment, name: String) -> Result<CommandResult> {
+    let name = name.trim().to_string();
+    profile::validate_name(&name)?;
+    if !profile::exists(env, &name)? {
+        return Err(not_found(&name));
+    }
+    profile::set_active(env, &name)?;
+    Ok(done_result(&format!("Using profile `{name}`.")))
+}
+
+fn process_profile_remove(env: Environment, name: String) -> Result<CommandResult> {
+    let name = name.trim().to_string();
+    profile::validate_name(&name)?;
+    if name == DEFAULT_PROFILE {
+        return Err(anyhow!(
+            "The default profile can't be removed. Use `smb logout` to sign out of it."
+        ));
+    }
+    if !profile::exists(env, &name)? {
+        return Err(not_found(&name));
+    }
+
+    let confirmed = confirm_delete(
+        "Profile removal confirmation",
+        &format!("Remove profile `{name}` and log out of it"),
+    )?;
+    if !confirmed {
+        return Ok(done_result("Cancelled."));
+    }
+
+    // The keyring entries live outside the profile directory.
+    let cleared = [Slot::AccessToken, Slot::RefreshToken]
+        .into_iter()
+        .try_for_each(|slot| credential_store_for(env, &name, slot)?.delete());
+    if let Err(e) = cleared {
+        return Ok(CommandResult {
+            spinner: loading_spinner("Done"),
+            symbol: fail_symbol(),
+            msg: fail_message(&e.to_string()),
+        });
+    }
+    profile::remove(env, &name)?;
+    Ok(done_result(&format!("Profile `{name}` removed.")))
+}
+
+fn not_found(name: &str) -> anyhow::Error {
+    anyhow!("Profile `{name}` does not exist. Create it with `smb profile add {name}`.")
+}
+
+fn loading_spinner(message: &str) -> Spinner {
+    Spinner::new(
+        spinners::Spinners::SimpleDotsScrolling,
+        succeed_message(message),
+    )
+}
+
+fn done_result(message: &str) -> CommandResult {
+    CommandResult {
+        spinner: loading_spinner("Done"),
+        symbol: succeed_symbol(),
+        msg: succeed_message(message),
+    }
+}
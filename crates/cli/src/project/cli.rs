@@ -1,4 +1,4 @@
-use clap::Subcommand;
+use {crate::cli::PageArgs, clap::Subcommand};
 
 #[derive(Subcommand)]
 pub enum Commands {
@@ -10,7 +10,10 @@ pub enum Commands {
     },
 
     #[clap(about = "List all your projects.")]
-    List {},
+    List {
+        #[clap(flatten)]
+        page: PageArgs,
+    },
 
     #[clap(about = "Show detail of a project.")]
     Show {
@@ -35,6 +38,11 @@ pub enum Commands {
     Deployment {
         #[clap(short, long)]
         id: Option<String>,
+        /// Only list deployments made to this deploy environment, e.g. staging.
+        #[clap(long = "env", value_name = "NAME")]
+        deploy_environment: Option<String>,
+        #[clap(flatten)]
+        page: PageArgs,
     },
     #[clap(about = "Update project description. Specify `--id` to update specific project.")]
     Update {
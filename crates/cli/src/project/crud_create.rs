@@ -340,6 +340,7 @@ fn write_smb_config(workspace_project: &Project, frontend_app: Option<&FrontendA
         description: workspace_project.description.clone(),
         project: deploy_target,
         projects: None,
+        environment: None,
     };
 
     std::fs::create_dir_all(".smb")?;
@@ -1,7 +1,7 @@
 use crate::client;
 use crate::token::get_smb_token::get_smb_token;
 use crate::{
-    cli::CommandResult,
+    cli::{CommandResult, PageArgs, MORE_PAGES_MESSAGE},
     interface::is_tui,
     project::deploy_target::resolve_frontend_app_for_project,
     ui::{
@@ -12,24 +12,32 @@ use crate::{
 use anyhow::{anyhow, Result};
 use smbcloud_model::project::Project;
 use smbcloud_network::environment::Environment;
-use smbcloud_networking_project::crud_project_read::{get_project, get_projects};
+use smbcloud_networking_project::crud_project_read::{get_project, get_projects_page};
 use spinners::Spinner;
 
-pub async fn process_project_list(env: Environment) -> Result<CommandResult> {
+pub async fn process_project_list(env: Environment, page: PageArgs) -> Result<CommandResult> {
     let mut spinner = Spinner::new(
         spinners::Spinners::SimpleDotsScrolling,
         succeed_message("Loading"),
     );
     let token = get_smb_token(env)?;
-    match get_projects(env, client(), token).await {
-        Ok(projects) => {
+    let result = page
+        .fetch(|page_request| {
+            let token = token.clone();
+            async move { get_projects_page(env, client(), token, &page_request).await }
+        })
+        .await;
+    match result {
+        Ok(page) => {
             spinner.stop_and_persist(&succeed_symbol(), succeed_message("Loaded."));
-            let msg = if projects.is_empty() {
+            let msg = if page.items.is_empty() {
                 succeed_message("No projects found.")
+            } else if !page.is_last() {
+                succeed_message(MORE_PAGES_MESSAGE)
             } else {
                 succeed_message("Done.")
             };
-            show_projects(projects)?;
+            show_projects(page.items)?;
             Ok(CommandResult {
                 spinner: Spinner::new(
                     spinners::Spinners::SimpleDotsScrolling,
@@ -1,7 +1,7 @@
 use crate::client;
 use crate::token::get_smb_token::get_smb_token;
 use crate::{
-    cli::CommandResult,
+    cli::{CommandResult, PageArgs, MORE_PAGES_MESSAGE},
     deploy::config::{check_project, get_config},
     interface::is_tui,
     ui::{
@@ -11,12 +11,16 @@ use crate::{
 };
 use anyhow::{anyhow, Result};
 use smbcloud_network::environment::Environment;
-use smbcloud_networking_project::crud_project_deployment_read::{get_deployment, get_deployments};
+use smbcloud_networking_project::crud_project_deployment_read::{
+    get_deployment, get_deployments_page,
+};
 use spinners::Spinner;
 
 pub(crate) async fn process_deployment(
     env: Environment,
     id: Option<String>,
+    deploy_environment: Option<String>,
+    page: PageArgs,
 ) -> Result<CommandResult> {
     let mut spinner: Spinner =
         Spinner::new(spinners::Spinners::Hamburger, succeed_message("Loading"));
@@ -27,6 +31,7 @@ pub(crate) async fn process_deployment(
 
     check_project(env, &access_token, config.project.id).await?;
 
+    let mut msg = "Loaded";
     if let Some(deployment_id) = id {
         // Show detail for a specific deployment
         let deployment_id: i32 = deployment_id.parse()?;
@@ -47,18 +52,44 @@ pub(crate) async fn process_deployment(
     } else {
         // List all deployments for the project
         let access_token = get_smb_token(env)?;
-        let deployments = get_deployments(env, client(), access_token, config.project.id).await?;
+        let project_id = config.project.id;
+        let deployment_page = page
+            .fetch(|page_request| {
+                let access_token = access_token.clone();
+                let deploy_environment = deploy_environment.clone();
+                async move {
+                    get_deployments_page(
+                        env,
+                        client(),
+                        access_token,
+                        project_id,
+                        deploy_environment.as_deref(),
+                        &page_request,
+                    )
+                    .await
+                }
+            })
+            .await?;
+        let more_pages = !deployment_page.is_last();
+        let mut deployments = deployment_page.items;
+        // Older API versions ignore the `environment` query parameter.
+        if let Some(ref name) = deploy_environment {
+            deployments.retain(|deployment| deployment.environment.as_deref() == Some(name));
+        }
         spinner.stop_and_persist(&succeed_symbol(), succeed_message("Load all deployments"));
         if is_tui() {
             show_deployments_tui(deployments).map_err(|e| anyhow!(e))?;
         } else {
             plain::render_deployments(&deployments);
         }
+        if more_pages {
+            msg = MORE_PAGES_MESSAGE;
+        }
     };
 
     Ok(CommandResult {
         spinner: Spinner::new(spinners::Spinners::Hamburger, succeed_message("Loading.")),
         symbol: succeed_symbol(),
-        msg: succeed_message("Loaded"),
+        msg: succeed_message(msg),
     })
 }
@@ -15,11 +15,15 @@ use smbcloud_network::environment::Environment;
 pub async fn process_project(env: Environment, commands: Commands) -> Result<CommandResult> {
     match commands {
         Commands::New { init } => process_project_init(env, init).await,
-        Commands::List {} => process_project_list(env).await,
+        Commands::List { page } => process_project_list(env, page).await,
         Commands::Show { id } => process_project_show(env, id).await,
         Commands::Delete { id } => process_project_delete(env, id).await,
         Commands::Use { id } => process_project_use(env, id).await,
-        Commands::Deployment { id } => process_deployment(env, id).await,
+        Commands::Deployment {
+            id,
+            deploy_environment,
+            page,
+        } => process_deployment(env, id, deploy_environment, page).await,
         Commands::Update { id } => process_project_update(env, id).await,
     }
 }
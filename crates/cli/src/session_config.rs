@@ -6,7 +6,11 @@
 //! working directory, handled by `smbcloud_utils::write_config`) — this file
 //! lives in the home directory and tracks which tenant/project the CLI
 //! session is currently operating against, independent of cwd.
+//!
+//! Each profile keeps its own copy (see [`crate::profile`]); the `default`
+//! profile uses the path above.
 
+use crate::profile;
 use anyhow::{anyhow, Result};
 use smbcloud_model::{
     frontend_app::FrontendApp, project::Config, project::Project, tenant::Tenant,
@@ -15,9 +19,7 @@ use smbcloud_network::environment::Environment;
 use std::path::PathBuf;
 
 fn config_path(env: Environment) -> Result<PathBuf> {
-    let home_directory =
-        home::home_dir().ok_or_else(|| anyhow!("Failed to resolve your home directory."))?;
-    Ok(home_directory.join(env.smb_dir()).join("config.toml"))
+    Ok(profile::profile_dir(env, &profile::current())?.join("config.toml"))
 }
 
 pub fn read(env: Environment) -> Result<Config> {
@@ -1,6 +1,5 @@
 pub mod cli;
 pub mod process;
 mod render;
-mod request;
 pub(crate) mod tenant_client;
 mod url_builder;
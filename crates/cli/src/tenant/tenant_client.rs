@@ -1,15 +1,11 @@
-use crate::tenant::{
-    request::request_empty,
-    url_builder::{build_tenant_url, build_tenants_url},
-};
-use reqwest::Client;
+use crate::tenant::url_builder::{build_tenant_url, build_tenants_url};
 use serde::Serialize;
 use smbcloud_model::{
     error_codes::ErrorResponse,
     tenant::{Tenant, TenantCreate, TenantUpdate},
 };
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 #[derive(Serialize)]
 struct TenantEnvelope<T> {
@@ -21,11 +17,9 @@ pub async fn get_tenants(
     client: (&SmbClient, &str),
     access_token: String,
 ) -> Result<Vec<Tenant>, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_tenants_url(env, client))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.get(build_tenants_url(env, client));
+    api.send(builder).await
 }
 
 pub async fn get_tenant(
@@ -34,11 +28,9 @@ pub async fn get_tenant(
     access_token: String,
     tenant_id: String,
 ) -> Result<Tenant, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_tenant_url(env, client, &tenant_id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.get(build_tenant_url(env, client, &tenant_id));
+    api.send(builder).await
 }
 
 pub async fn create_tenant(
@@ -47,12 +39,11 @@ pub async fn create_tenant(
     access_token: String,
     tenant: TenantCreate,
 ) -> Result<Tenant, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .post(build_tenants_url(env, client))
-        .json(&TenantEnvelope { tenant })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&TenantEnvelope { tenant });
+    api.send(builder).await
 }
 
 pub async fn update_tenant(
@@ -62,12 +53,11 @@ pub async fn update_tenant(
     tenant_id: String,
     tenant: TenantUpdate,
 ) -> Result<Tenant, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .patch(build_tenant_url(env, client, &tenant_id))
-        .json(&TenantEnvelope { tenant })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&TenantEnvelope { tenant });
+    api.send(builder).await
 }
 
 pub async fn delete_tenant(
@@ -76,9 +66,82 @@ pub async fn delete_tenant(
     access_token: String,
     tenant_id: String,
 ) -> Result<(), ErrorResponse> {
-    let builder = Client::new()
-        .delete(build_tenant_url(env, client, &tenant_id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request_empty(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.delete(build_tenant_url(env, client, &tenant_id));
+    api.send_empty(builder).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::cloud_auth::auth_app::{create_auth_app, get_auth_apps},
+        smbcloud_mock_server::{MockServer, MOCK_ACCESS_TOKEN},
+        smbcloud_model::app_auth::AuthAppCreate,
+        smbcloud_network::environment::API_URL_ENV,
+    };
+
+    const CLIENT: (&SmbClient, &str) = (&SmbClient::Cli, "secret");
+
+    fn token() -> String {
+        MOCK_ACCESS_TOKEN.to_string()
+    }
+
+    /// The only test in this crate that talks to an API, since it points
+    /// `SMB_API_URL` at its own mock. Covers the Auth app client too.
+    #[tokio::test]
+    async fn tenant_and_auth_app_clients_against_the_mock() {
+        let server = MockServer::start().await.unwrap();
+        std::env::set_var(API_URL_ENV, server.url());
+        let env = Environment::Custom;
+
+        let tenant = create_tenant(
+            env,
+            CLIENT,
+            token(),
+            TenantCreate {
+                name: "Acme".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_tenants(env, CLIENT, token()).await.unwrap().len(), 2);
+        let renamed = update_tenant(
+            env,
+            CLIENT,
+            token(),
+            tenant.id.to_string(),
+            TenantUpdate {
+                name: Some("Acme Inc".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(renamed.name, "Acme Inc");
+
+        let project = server.state().add_project("site", "", Some(tenant.id));
+        create_auth_app(
+            env,
+            CLIENT,
+            token(),
+            AuthAppCreate {
+                name: "Login".to_string(),
+                project_id: project.id.to_string(),
+                support_email: None,
+            },
+        )
+        .await
+        .unwrap();
+        let auth_apps = get_auth_apps(env, CLIENT, token(), Some(project.id.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(auth_apps.len(), 1);
+
+        delete_tenant(env, CLIENT, token(), tenant.id.to_string())
+            .await
+            .unwrap();
+        assert!(get_tenant(env, CLIENT, token(), tenant.id.to_string())
+            .await
+            .is_err());
+    }
 }
@@ -1,11 +1,12 @@
 use {
-    crate::token::smb_token_file_path::smb_token_file_path, anyhow::Result,
-    smbcloud_network::environment::Environment, std::fs,
+    crate::token::credential_store::{credential_store, refresh_token_store},
+    anyhow::Result,
+    smbcloud_network::environment::Environment,
 };
 
+/// Forgets the session: the access token and, after `smb login --sso`, its
+/// refresh token.
 pub fn clear_smb_token(env: Environment) -> Result<()> {
-    if let Some(path) = smb_token_file_path(env) {
-        fs::remove_file(path)?;
-    }
-    Ok(())
+    credential_store(env)?.delete()?;
+    refresh_token_store(env)?.delete()
 }
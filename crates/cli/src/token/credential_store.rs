@@ -0,0 +1,242 @@
+use {
+    crate::{
+        profile,
+        token::{
+            encrypted_file_store::EncryptedFileStore, keyring_store::KeyringStore,
+            smb_token_file_path::smb_token_file_path,
+        },
+    },
+    anyhow::{anyhow, Result},
+    log::debug,
+    smbcloud_network::environment::Environment,
+    std::{
+        collections::HashMap,
+        fs,
+        path::Path,
+        sync::{Mutex, OnceLock},
+    },
+};
+
+/// Picks the token backend explicitly: `keyring` or `file`. Unset, the OS
+/// keyring is used when it answers and the encrypted file otherwise.
+pub(crate) const TOKEN_STORE_ENV: &str = "SMB_TOKEN_STORE";
+
+/// Somewhere to keep the access token between runs.
+pub(crate) trait CredentialStore {
+    /// Shown in debug logs, e.g. "keyring".
+    fn name(&self) -> &'static str;
+    fn load(&self) -> Result<Option<String>>;
+    fn save(&self, token: &str) -> Result<()>;
+    /// Succeeds when there was nothing to delete.
+    fn delete(&self) -> Result<()>;
+}
+
+/// What a store holds. Each slot is its own keyring entry and its own file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Slot {
+    /// The `Authorization` header value sent with every request.
+    AccessToken,
+    /// The refresh token from `smb login --sso`, when the issuer sent one.
+    RefreshToken,
+}
+
+impl Slot {
+    pub(crate) fn file_name(self) -> &'static str {
+        match self {
+            Slot::AccessToken => "token.enc",
+            Slot::RefreshToken => "refresh_token.enc",
+        }
+    }
+
+    /// Appended to the keyring user. The access token keeps the bare name it
+    /// always had.
+    pub(crate) fn keyring_suffix(self) -> Option<&'static str> {
+        match self {
+            Slot::AccessToken => None,
+            Slot::RefreshToken => Some("refresh"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenStoreKind {
+    Keyring,
+    File,
+}
+
+impl TokenStoreKind {
+    fn from_env() -> Result<Option<Self>> {
+        match std::env::var(TOKEN_STORE_ENV) {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn parse(value: &str) -> Result<Option<Self>> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" => Ok(None),
+            "keyring" => Ok(Some(TokenStoreKind::Keyring)),
+            "file" => Ok(Some(TokenStoreKind::File)),
+            other => Err(anyhow!(
+                "Invalid {TOKEN_STORE_ENV} value `{other}`. Expected `keyring` or `file`."
+            )),
+        }
+    }
+}
+
+/// The store for `env` and the active profile, with any token left behind by
+/// older CLI versions already moved into it.
+pub(crate) fn credential_store(env: Environment) -> Result<Box<dyn CredentialStore>> {
+    credential_store_for(env, &profile::current(), Slot::AccessToken)
+}
+
+/// The store for the active profile's refresh token.
+pub(crate) fn refresh_token_store(env: Environment) -> Result<Box<dyn CredentialStore>> {
+    credential_store_for(env, &profile::current(), Slot::RefreshToken)
+}
+
+/// The backend each environment, profile and slot resolved to in this
+/// process, so the keyring is probed and old tokens are migrated only once.
+type ResolvedStores = HashMap<(String, String, Slot), TokenStoreKind>;
+
+static RESOLVED: OnceLock<Mutex<ResolvedStores>> = OnceLock::new();
+
+/// Like [`credential_store`], for any profile and slot.
+pub(crate) fn credential_store_for(
+    env: Environment,
+    profile: &str,
+    slot: Slot,
+) -> Result<Box<dyn CredentialStore>> {
+    let mut resolved = RESOLVED
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap();
+    let key = (env.to_str().to_string(), profile.to_string(), slot);
+    if let Some(&kind) = resolved.get(&key) {
+        return open(env, profile, slot, kind);
+    }
+    let (kind, store) = resolve(env, profile, slot)?;
+    resolved.insert(key, kind);
+    debug!("Using the {} token store.", store.name());
+    Ok(store)
+}
+
+fn open(
+    env: Environment,
+    profile: &str,
+    slot: Slot,
+    kind: TokenStoreKind,
+) -> Result<Box<dyn CredentialStore>> {
+    Ok(match kind {
+        TokenStoreKind::Keyring => Box::new(KeyringStore::new(env, profile, slot)?),
+        TokenStoreKind::File => Box::new(EncryptedFileStore::for_profile(env, profile, slot)?),
+    })
+}
+
+/// Picks the backend and moves any token left behind by older CLI versions
+/// into it.
+fn resolve(
+    env: Environment,
+    profile: &str,
+    slot: Slot,
+) -> Result<(TokenStoreKind, Box<dyn CredentialStore>)> {
+    let file_store = EncryptedFileStore::for_profile(env, profile, slot)?;
+    let (kind, store): (_, Box<dyn CredentialStore>) = match TokenStoreKind::from_env()? {
+        Some(TokenStoreKind::Keyring) => (
+            TokenStoreKind::Keyring,
+            Box::new(KeyringStore::new(env, profile, slot)?),
+        ),
+        Some(TokenStoreKind::File) => (TokenStoreKind::File, Box::new(file_store)),
+        None => match KeyringStore::available(env, profile, slot) {
+            Some(keyring) => {
+                // A session without a keyring (e.g. over SSH) may have
+                // written the file; the keyring takes it over from here.
+                migrate(&file_store, &keyring)?;
+                (TokenStoreKind::Keyring, Box::new(keyring))
+            }
+            None => (TokenStoreKind::File, Box::new(file_store)),
+        },
+    };
+    if slot == Slot::AccessToken {
+        if let Some(path) = smb_token_file_path(env, profile) {
+            migrate_plaintext_token(&path, store.as_ref())?;
+        }
+    }
+    Ok((kind, store))
+}
+
+/// Moves a token written in plain text by older CLI versions into `store`
+/// and removes the plain-text file.
+///
+/// The plain-text file wins over a token already in the store: it is either
+/// left over from before the store existed or was just provisioned by hand,
+/// as CI jobs do.
+pub(crate) fn migrate_plaintext_token(path: &Path, store: &dyn CredentialStore) -> Result<()> {
+    let token = fs::read_to_string(path)?;
+    let token = token.trim();
+    if !token.is_empty() {
+        store.save(token)?;
+    }
+    fs::remove_file(path)?;
+    debug!(
+        "Moved the plain-text token into the {} store.",
+        store.name()
+    );
+    Ok(())
+}
+
+fn migrate(from: &dyn CredentialStore, to: &dyn CredentialStore) -> Result<()> {
+    if let Some(token) = from.load()? {
+        if to.load()?.is_none() {
+            to.save(&token)?;
+        }
+        from.delete()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::tempdir};
+
+    #[test]
+    fn parses_token_store_values() {
+        assert_eq!(
+            TokenStoreKind::parse("Keyring").unwrap(),
+            Some(TokenStoreKind::Keyring)
+        );
+        assert_eq!(
+            TokenStoreKind::parse("file").unwrap(),
+            Some(TokenStoreKind::File)
+        );
+        assert_eq!(TokenStoreKind::parse("").unwrap(), None);
+        assert!(TokenStoreKind::parse("vault").is_err());
+    }
+
+    #[test]
+    fn migrates_plaintext_token_into_the_store() {
+        let dir = tempdir().unwrap();
+        let legacy = dir.path().join("token");
+        fs::write(&legacy, "legacy-token\n").unwrap();
+        let store = EncryptedFileStore::at(dir.path().join("token.enc"));
+
+        migrate_plaintext_token(&legacy, &store).unwrap();
+
+        assert!(!legacy.exists());
+        assert_eq!(store.load().unwrap().as_deref(), Some("legacy-token"));
+    }
+
+    #[test]
+    fn plaintext_token_replaces_the_stored_one() {
+        let dir = tempdir().unwrap();
+        let legacy = dir.path().join("token");
+        fs::write(&legacy, "new-token").unwrap();
+        let store = EncryptedFileStore::at(dir.path().join("token.enc"));
+        store.save("old-token").unwrap();
+
+        migrate_plaintext_token(&legacy, &store).unwrap();
+
+        assert!(!legacy.exists());
+        assert_eq!(store.load().unwrap().as_deref(), Some("new-token"));
+    }
+}
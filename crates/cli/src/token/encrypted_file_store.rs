@@ -0,0 +1,223 @@
+use {
+    crate::{
+        profile,
+        token::credential_store::{CredentialStore, Slot},
+    },
+    anyhow::{anyhow, Result},
+    base64::{engine::general_purpose::STANDARD, Engine},
+    dirs::home_dir,
+    ring::{
+        aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN},
+        hkdf::{Salt, HKDF_SHA256},
+        rand::{SecureRandom, SystemRandom},
+    },
+    smbcloud_network::environment::Environment,
+    std::{
+        fs::{self, OpenOptions},
+        io::{ErrorKind, Write},
+        path::PathBuf,
+    },
+};
+
+/// Marks the file format so a later change can tell old files apart.
+const FORMAT_PREFIX: &str = "smb-token-v1:";
+const SALT_LEN: usize = 16;
+const KEY_INFO: &[&[u8]] = &[b"smbcloud-cli token"];
+
+/// The token encrypted with ChaCha20-Poly1305 in `~/.smb/token.enc` (or the
+/// profile's directory), mode `0600`.
+///
+/// The key is derived from this machine's id and the user's home directory,
+/// so a copied file (a backup, a synced dotfiles repo) is useless elsewhere.
+/// Anyone who can run code as the user on this machine can still decrypt it;
+/// prefer the OS keyring where there is one.
+pub(crate) struct EncryptedFileStore {
+    path: PathBuf,
+}
+
+impl EncryptedFileStore {
+    pub(crate) fn for_profile(env: Environment, profile: &str, slot: Slot) -> Result<Self> {
+        Ok(Self::at(
+            profile::profile_dir(env, profile)?.join(slot.file_name()),
+        ))
+    }
+
+    pub(crate) fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn key(salt: &[u8]) -> Result<LessSafeKey> {
+        let mut key_bytes = [0u8; 32];
+        Salt::new(HKDF_SHA256, salt)
+            .extract(&machine_secret())
+            .expand(KEY_INFO, &CHACHA20_POLY1305)
+            .and_then(|okm| okm.fill(&mut key_bytes))
+            .map_err(|_| anyhow!("Failed to derive the token encryption key."))?;
+        let key = UnboundKey::new(&CHACHA20_POLY1305, &key_bytes)
+            .map_err(|_| anyhow!("Failed to derive the token encryption key."))?;
+        Ok(LessSafeKey::new(key))
+    }
+}
+
+impl CredentialStore for EncryptedFileStore {
+    fn name(&self) -> &'static str {
+        "encrypted file"
+    }
+
+    fn load(&self) -> Result<Option<String>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(anyhow!("Failed to read {}: {e}", self.path.display())),
+        };
+        let unreadable = || {
+            anyhow!(
+                "{} can't be decrypted on this machine. Delete it and log in again.",
+                self.path.display()
+            )
+        };
+        let encoded = contents
+            .trim()
+            .strip_prefix(FORMAT_PREFIX)
+            .ok_or_else(unreadable)?;
+        let mut sealed = STANDARD.decode(encoded).map_err(|_| unreadable())?;
+        if sealed.len() < SALT_LEN + NONCE_LEN {
+            return Err(unreadable());
+        }
+        let mut ciphertext = sealed.split_off(SALT_LEN + NONCE_LEN);
+        let (salt, nonce) = sealed.split_at(SALT_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| unreadable())?;
+        let token = Self::key(salt)?
+            .open_in_place(nonce, Aad::empty(), &mut ciphertext)
+            .map_err(|_| unreadable())?;
+        String::from_utf8(token.to_vec())
+            .map(Some)
+            .map_err(|_| unreadable())
+    }
+
+    fn save(&self, token: &str) -> Result<()> {
+        let random = SystemRandom::new();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        random
+            .fill(&mut salt)
+            .and_then(|_| random.fill(&mut nonce))
+            .map_err(|_| anyhow!("Failed to generate randomness for the token file."))?;
+
+        let mut ciphertext = token.as_bytes().to_vec();
+        Self::key(&salt)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut ciphertext,
+            )
+            .map_err(|_| anyhow!("Failed to encrypt the token."))?;
+        let sealed = [salt.as_slice(), nonce.as_slice(), &ciphertext].concat();
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Write next to the target and rename, so a crash never leaves a
+        // half-written token behind.
+        let temp_path = self.path.with_extension("enc.tmp");
+        let mut file = private_file(&temp_path)?;
+        file.write_all(format!("{FORMAT_PREFIX}{}\n", STANDARD.encode(sealed)).as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+
+    fn delete(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                Err(anyhow!("Failed to remove {}: {e}", self.path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn private_file(path: &std::path::Path) -> Result<fs::File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies when the file is created.
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn private_file(path: &std::path::Path) -> Result<fs::File> {
+    Ok(OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(path)?)
+}
+
+/// Input for the file key: the machine id where the OS has one, plus the
+/// home directory so two users on one machine get different keys.
+fn machine_secret() -> Vec<u8> {
+    let machine_id = ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .unwrap_or_default();
+    let home = home_dir()
+        .map(|home| home.display().to_string())
+        .unwrap_or_default();
+    format!("{}:{home}", machine_id.trim()).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::tempdir};
+
+    #[test]
+    fn round_trips_and_hides_the_token() {
+        let dir = tempdir().unwrap();
+        let store = EncryptedFileStore::at(dir.path().join("token.enc"));
+        assert_eq!(store.load().unwrap(), None);
+
+        store.save("secret-token").unwrap();
+
+        let on_disk = fs::read_to_string(dir.path().join("token.enc")).unwrap();
+        assert!(on_disk.starts_with(FORMAT_PREFIX));
+        assert!(!on_disk.contains("secret-token"));
+        assert_eq!(store.load().unwrap().as_deref(), Some("secret-token"));
+
+        store.delete().unwrap();
+        store.delete().unwrap();
+        assert_eq!(store.load().unwrap(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn token_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("token.enc");
+        EncryptedFileStore::at(path.clone()).save("t").unwrap();
+
+        let mode = fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn tampered_file_is_rejected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("token.enc");
+        let store = EncryptedFileStore::at(path.clone());
+        store.save("secret-token").unwrap();
+        let mut contents = fs::read_to_string(&path).unwrap();
+        contents.insert_str(FORMAT_PREFIX.len(), "AAAA");
+        fs::write(&path, contents).unwrap();
+
+        assert!(store.load().is_err());
+    }
+}
@@ -1,17 +1,49 @@
 use {
-    crate::token::smb_token_file_path::smb_token_file_path,
+    crate::token::credential_store::credential_store,
     anyhow::{anyhow, Result},
     log::debug,
     smbcloud_network::environment::Environment,
 };
 
+/// A personal access token passed through the environment, e.g. from a CI
+/// secret. It wins over the login stored for the active profile.
+pub const SMB_TOKEN_ENV: &str = "SMB_TOKEN";
+
 pub fn get_smb_token(env: Environment) -> Result<String> {
-    if let Some(path) = smb_token_file_path(env) {
-        std::fs::read_to_string(path).map_err(|e| {
+    if let Some(token) = env_smb_token() {
+        return Ok(token);
+    }
+    get_session_token(env)
+}
+
+/// The token saved by `smb login`, ignoring `SMB_TOKEN`.
+pub fn get_session_token(env: Environment) -> Result<String> {
+    match credential_store(env)?.load() {
+        Ok(Some(token)) => Ok(token),
+        Ok(None) => Err(anyhow!("No access token found. Are you logged in?")),
+        Err(e) => {
             debug!("Error while reading token: {}", e);
-            anyhow!("Error while reading token. Are you logged in?")
-        })
-    } else {
-        Err(anyhow!("Failed to get home directory. Are you logged in?"))
+            Err(anyhow!("Error while reading token. Are you logged in?"))
+        }
     }
 }
+
+/// `SMB_TOKEN` as an `Authorization` value, if it is set.
+pub fn env_smb_token() -> Option<String> {
+    let token = std::env::var(SMB_TOKEN_ENV).ok()?;
+    let token = token.trim();
+    if token.is_empty() {
+        return None;
+    }
+    Some(if token.starts_with("Bearer ") {
+        token.to_string()
+    } else {
+        format!("Bearer {token}")
+    })
+}
+
+/// Whether a token is stored for `env` or set in `SMB_TOKEN`. Says nothing
+/// about whether the API still accepts it; see `is_logged_in` for that.
+pub fn has_smb_token(env: Environment) -> bool {
+    get_smb_token(env).is_ok()
+}
@@ -0,0 +1,80 @@
+use {
+    crate::{
+        profile::DEFAULT_PROFILE,
+        token::credential_store::{CredentialStore, Slot},
+    },
+    anyhow::{anyhow, Result},
+    keyring::{Entry, Error},
+    log::debug,
+    smbcloud_network::environment::Environment,
+};
+
+/// Service name the token is filed under in the OS keyring.
+const KEYRING_SERVICE: &str = "smbcloud-cli";
+
+/// The OS keyring: Secret Service (GNOME Keyring, KWallet) on Linux, the
+/// login Keychain on macOS and Credential Manager on Windows.
+pub(crate) struct KeyringStore {
+    entry: Entry,
+}
+
+impl KeyringStore {
+    /// One entry per environment, profile and slot, so their tokens stay
+    /// apart. The default profile keeps the bare environment name it always
+    /// had.
+    pub(crate) fn new(env: Environment, profile: &str, slot: Slot) -> Result<Self> {
+        let mut user = if profile == DEFAULT_PROFILE {
+            env.to_str().to_string()
+        } else {
+            format!("{}/{profile}", env.to_str())
+        };
+        if let Some(suffix) = slot.keyring_suffix() {
+            user = format!("{user}#{suffix}");
+        }
+        let entry = Entry::new(KEYRING_SERVICE, &user)
+            .map_err(|e| anyhow!("Failed to open the OS keyring: {e}"))?;
+        Ok(Self { entry })
+    }
+
+    /// The keyring store if the platform keyring answers. Headless Linux
+    /// boxes usually have no Secret Service running.
+    pub(crate) fn available(env: Environment, profile: &str, slot: Slot) -> Option<Self> {
+        let store = Self::new(env, profile, slot).ok()?;
+        match store.entry.get_password() {
+            Ok(_) | Err(Error::NoEntry) => Some(store),
+            Err(e) => {
+                debug!("OS keyring unavailable: {e}");
+                None
+            }
+        }
+    }
+}
+
+impl CredentialStore for KeyringStore {
+    fn name(&self) -> &'static str {
+        "keyring"
+    }
+
+    fn load(&self) -> Result<Option<String>> {
+        match self.entry.get_password() {
+            Ok(token) => Ok(Some(token)),
+            Err(Error::NoEntry) => Ok(None),
+            Err(e) => Err(anyhow!("Failed to read the token from the OS keyring: {e}")),
+        }
+    }
+
+    fn save(&self, token: &str) -> Result<()> {
+        self.entry
+            .set_password(token)
+            .map_err(|e| anyhow!("Failed to save the token to the OS keyring: {e}"))
+    }
+
+    fn delete(&self) -> Result<()> {
+        match self.entry.delete_credential() {
+            Ok(()) | Err(Error::NoEntry) => Ok(()),
+            Err(e) => Err(anyhow!(
+                "Failed to remove the token from the OS keyring: {e}"
+            )),
+        }
+    }
+}
@@ -1,4 +1,8 @@
 pub(crate) mod clear_smb_token;
+pub(crate) mod credential_store;
+pub(crate) mod encrypted_file_store;
 pub(crate) mod get_smb_token;
 pub(crate) mod is_logged_in;
+pub(crate) mod keyring_store;
+pub(crate) mod renewal;
 pub(crate) mod smb_token_file_path;
@@ -0,0 +1,188 @@
+//! Keeping an `smb login --sso` session alive.
+//!
+//! That login stores the refresh token and the access token's expiry next to
+//! the access token. The session is renewed before a command when it is about
+//! to expire, and [`CliSessionRenewer`] renews it when the API answers `401`
+//! mid-command, so the request goes out once more. Only when the refresh
+//! itself fails does the user have to log in again.
+
+use {
+    crate::token::{
+        credential_store::{credential_store, refresh_token_store},
+        get_smb_token::{env_smb_token, get_session_token},
+    },
+    anyhow::{anyhow, Result},
+    chrono::Utc,
+    log::debug,
+    serde::{Deserialize, Serialize},
+    smbcloud_auth_sdk::oidc::{refresh_token, OidcSession},
+    smbcloud_network::{
+        environment::Environment,
+        session::{set_session_renewer, RenewFuture, SessionRenewer},
+    },
+    smbcloud_networking::constants::CLI_OIDC_CLIENT_ID,
+    tokio::sync::Mutex,
+};
+
+/// Renew this many seconds before the access token expires.
+const EXPIRY_LEEWAY: i64 = 60;
+
+/// One renewal at a time, so requests rejected together share it.
+static RENEWING: Mutex<()> = Mutex::const_new(());
+
+/// What the refresh-token slot holds.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Renewal {
+    pub(crate) refresh_token: String,
+    /// Unix timestamp (seconds) the access token expires at, if known.
+    pub(crate) expires_at: Option<i64>,
+}
+
+impl Renewal {
+    /// `None` when the issuer sent no refresh token: the session then lasts
+    /// as long as its access token.
+    pub(crate) fn from_session(session: &OidcSession) -> Option<Self> {
+        session.refresh_token.clone().map(|refresh_token| Self {
+            refresh_token,
+            expires_at: session.expires_at,
+        })
+    }
+
+    fn expires_soon(&self, now: i64) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at - EXPIRY_LEEWAY <= now)
+    }
+}
+
+/// The `Authorization` value for `session`.
+pub(crate) fn authorization(session: &OidcSession) -> String {
+    format!("Bearer {}", session.access_token)
+}
+
+/// Saves the access token and what renewing it takes. A login without a
+/// refresh token clears the one an earlier login left behind.
+pub(crate) fn store(
+    env: Environment,
+    authorization: &str,
+    renewal: Option<&Renewal>,
+) -> Result<()> {
+    credential_store(env)?.save(authorization)?;
+    let refresh_store = refresh_token_store(env)?;
+    match renewal {
+        Some(renewal) => refresh_store.save(&serde_json::to_string(renewal)?),
+        None => refresh_store.delete(),
+    }
+}
+
+fn load(env: Environment) -> Result<Option<Renewal>> {
+    let Some(stored) = refresh_token_store(env)?.load()? else {
+        return Ok(None);
+    };
+    match serde_json::from_str(&stored) {
+        Ok(renewal) => Ok(Some(renewal)),
+        Err(e) => {
+            debug!("Ignoring an unreadable refresh token: {e}");
+            Ok(None)
+        }
+    }
+}
+
+/// Trades the stored refresh token for a new access token and saves both.
+/// Returns the new `Authorization` value.
+async fn renew(env: Environment) -> Result<String> {
+    let renewal = load(env)?.ok_or_else(|| anyhow!("This session can't be renewed."))?;
+    let token = refresh_token(env, CLI_OIDC_CLIENT_ID, &renewal.refresh_token)
+        .await
+        .map_err(|e| anyhow!("Failed to renew the session: {e}"))?;
+    let session = OidcSession {
+        access_token: String::new(),
+        refresh_token: Some(renewal.refresh_token),
+        expires_at: renewal.expires_at,
+    }
+    .renewed(token, Utc::now().timestamp());
+    let authorization = authorization(&session);
+    store(
+        env,
+        &authorization,
+        Renewal::from_session(&session).as_ref(),
+    )?;
+    debug!("Session renewed.");
+    Ok(authorization)
+}
+
+/// Renews the session if its access token expires within a minute. A failed
+/// renewal is only logged: the command's first request then gets a `401`,
+/// which [`CliSessionRenewer`] tries once more before giving up.
+pub async fn renew_if_expiring(env: Environment) {
+    if env_smb_token().is_some() {
+        return;
+    }
+    let _renewing = RENEWING.lock().await;
+    match load(env) {
+        Ok(Some(renewal)) if renewal.expires_soon(Utc::now().timestamp()) => {
+            if let Err(e) = renew(env).await {
+                debug!("{e}");
+            }
+        }
+        Ok(_) => {}
+        Err(e) => debug!("Failed to read the refresh token: {e}"),
+    }
+}
+
+/// Renews the stored session when the API rejects its access token.
+pub struct CliSessionRenewer {
+    env: Environment,
+}
+
+impl SessionRenewer for CliSessionRenewer {
+    fn renew<'a>(&'a self, rejected: &'a str) -> RenewFuture<'a> {
+        Box::pin(async move {
+            // `SMB_TOKEN` is a personal access token; there's nothing to renew.
+            if env_smb_token().is_some() {
+                return None;
+            }
+            let _renewing = RENEWING.lock().await;
+            // Another request renewed it while this one waited.
+            if let Ok(current) = get_session_token(self.env) {
+                if current != rejected {
+                    return Some(current);
+                }
+            }
+            match renew(self.env).await {
+                Ok(authorization) => Some(authorization),
+                Err(e) => {
+                    debug!("{e}");
+                    None
+                }
+            }
+        })
+    }
+}
+
+/// Lets API requests renew the session on `401`. Called once from `main`,
+/// after the profile is picked.
+pub fn install_session_renewer(env: Environment) {
+    set_session_renewer(CliSessionRenewer { env });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renews_a_minute_before_expiry() {
+        let renewal = Renewal {
+            refresh_token: "refresh".to_string(),
+            expires_at: Some(1_000),
+        };
+        assert!(!renewal.expires_soon(900));
+        assert!(renewal.expires_soon(940));
+        assert!(renewal.expires_soon(2_000));
+
+        let open_ended = Renewal {
+            refresh_token: "refresh".to_string(),
+            expires_at: None,
+        };
+        assert!(!open_ended.expires_soon(i64::MAX));
+    }
+}
@@ -1,22 +1,19 @@
-use {
-    dirs::home_dir,
-    log::debug,
-    smbcloud_network::environment::Environment,
-    std::path::{Path, PathBuf},
-};
+use {crate::profile, log::debug, smbcloud_network::environment::Environment, std::path::PathBuf};
 
-pub fn smb_token_file_path(env: Environment) -> Option<PathBuf> {
-    match home_dir() {
-        Some(home_path) => {
-            let token_path = [&env.smb_dir(), "/token"].join("");
-            let token_file = home_path.join(Path::new(&token_path));
+/// The plain-text `~/.smb/token` older CLI versions wrote (or one provisioned
+/// by hand in a profile's directory), if it is still there.
+/// `credential_store` moves it into the current store on first use.
+pub fn smb_token_file_path(env: Environment, profile: &str) -> Option<PathBuf> {
+    match profile::profile_dir(env, profile) {
+        Ok(dir) => {
+            let token_file = dir.join("token");
             if token_file.exists() && token_file.is_file() {
                 return Some(token_file);
             }
             None
         }
-        None => {
-            debug!("Failed to get home directory.");
+        Err(e) => {
+            debug!("{e}");
             None
         }
     }
@@ -29,7 +29,7 @@ use std::io;
 ///
 /// Switches to the alternate screen and blocks until `q` / `Esc` is pressed,
 /// then restores the terminal exactly as it was.
-pub fn show_user_tui(user: &User) -> io::Result<()> {
+pub fn show_user_tui(user: &User, profile: &str) -> io::Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -37,7 +37,7 @@ pub fn show_user_tui(user: &User) -> io::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run_loop(&mut terminal, user);
+    let result = run_loop(&mut terminal, user, profile);
 
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
@@ -51,9 +51,10 @@ pub fn show_user_tui(user: &User) -> io::Result<()> {
 fn run_loop<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     user: &User,
+    profile: &str,
 ) -> io::Result<()> {
     loop {
-        terminal.draw(|frame| render(frame, user))?;
+        terminal.draw(|frame| render(frame, user, profile))?;
 
         if let Event::Key(key) = event::read()? {
             if key.kind != KeyEventKind::Press {
@@ -68,7 +69,7 @@ fn run_loop<B: ratatui::backend::Backend>(
 
 // ── Rendering ─────────────────────────────────────────────────────────────────
 
-fn render(frame: &mut ratatui::Frame, user: &User) {
+fn render(frame: &mut ratatui::Frame, user: &User, profile: &str) {
     let area = frame.area();
 
     frame.render_widget(Block::default().style(Style::default().bg(BG)), area);
@@ -83,7 +84,7 @@ fn render(frame: &mut ratatui::Frame, user: &User) {
         .split(area);
 
     render_title(frame, zones[0]);
-    render_profile(frame, user, zones[1]);
+    render_profile(frame, user, profile, zones[1]);
     render_footer(frame, zones[2]);
 }
 
@@ -120,7 +121,12 @@ fn render_title(frame: &mut ratatui::Frame, area: ratatui::layout::Rect) {
 
 // ── Profile card ──────────────────────────────────────────────────────────────
 
-fn render_profile(frame: &mut ratatui::Frame, user: &User, area: ratatui::layout::Rect) {
+fn render_profile(
+    frame: &mut ratatui::Frame,
+    user: &User,
+    profile: &str,
+    area: ratatui::layout::Rect,
+) {
     // Center the card horizontally — at most 66 columns wide.
     let h_zones = Layout::default()
         .direction(Direction::Horizontal)
@@ -133,12 +139,12 @@ fn render_profile(frame: &mut ratatui::Frame, user: &User, area: ratatui::layout
 
     let card_col = h_zones[1];
 
-    // Center the card vertically — 13 lines tall (11 content + 2 border).
+    // Center the card vertically — 14 lines tall (12 content + 2 border).
     let v_zones = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Fill(1),
-            Constraint::Length(13),
+            Constraint::Length(14),
             Constraint::Fill(1),
         ])
         .split(card_col);
@@ -177,6 +183,7 @@ fn render_profile(frame: &mut ratatui::Frame, user: &User, area: ratatui::layout
         Line::from(""),
         // Field rows.
         field_line("ID", &user.id.to_string()),
+        field_line("Profile", profile),
         Line::from(""),
         field_line("Member since", &member_since),
         field_line("Last updated", &last_updated),
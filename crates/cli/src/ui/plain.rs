@@ -15,10 +15,11 @@ use {
 };
 
 /// Render the authenticated user's account details as a plain key/value block.
-pub fn render_user(user: &User) {
+pub fn render_user(user: &User, profile: &str) {
     println!();
     println!("  {}", highlight(&user.email));
     println!("  {:<14}{}", "ID", user.id);
+    println!("  {:<14}{}", "Profile", profile);
     println!(
         "  {:<14}{}",
         "Member since",
@@ -73,12 +74,13 @@ pub fn render_deployments(deployments: &[Deployment]) {
         return;
     }
     let mut builder = Builder::default();
-    builder.push_record(["ID", "Commit", "Status", "Created"]);
+    builder.push_record(["ID", "Commit", "Status", "Environment", "Created"]);
     for deployment in deployments {
         builder.push_record([
             deployment.id.to_string(),
             deployment.commit_hash.chars().take(8).collect::<String>(),
             deployment.status.to_string(),
+            deployment.environment.clone().unwrap_or_default(),
             deployment.created_at.format("%Y-%m-%d %H:%M").to_string(),
         ]);
     }
@@ -95,6 +97,7 @@ pub fn render_deployment_detail(deployment: &Deployment) {
     println!("  {:<16}{}", "Commit", deployment.commit_hash);
     println!("  {:<16}{}", "Status", deployment.status);
     field_opt("App", deployment.frontend_app_name.as_deref());
+    field_opt("Environment", deployment.environment.as_deref());
     println!(
         "  {:<16}{}",
         "Created",
@@ -5,8 +5,8 @@
 //!
 //! * [`confirm`] resolves to its default instead of asking.
 //! * [`input`] / [`input_optional`] return the supplied default, or fail.
-//! * [`password`] and the required [`input_required`] fail fast — secrets and
-//!   free-form answers can never be guessed.
+//! * [`password`], [`new_password`] and the required [`input_required`] fail
+//!   fast — secrets and free-form answers can never be guessed.
 //! * [`select`] / [`select_opt`] use an explicit CI default when the caller
 //!   provides one, otherwise fail.
 //!
@@ -179,6 +179,18 @@ pub fn password(prompt: &str) -> Result<String> {
         .map_err(io_error)
 }
 
+/// A new password, typed twice. Always fails in CI mode, like [`password`].
+pub fn new_password(prompt: &str) -> Result<String> {
+    if is_ci() {
+        return Err(ci_required(prompt));
+    }
+    Password::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .with_confirmation("Repeat password", "Error: the passwords don't match.")
+        .interact()
+        .map_err(io_error)
+}
+
 /// Single-choice selection. In CI mode returns `ci_default` when the caller
 /// supplies one, otherwise fails.
 pub fn select<T: ToString>(
@@ -0,0 +1,70 @@
+use crate::{api_error, block_on, network_error, parse_env, serialize};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use pythonize::depythonize;
+use smbcloud_email_sdk::{EmailClient, EmailCredentials, EmailError, SendEmail};
+
+fn email_client(env: &str, api_key: &str, base_url: Option<&str>) -> PyResult<EmailClient> {
+    let credentials = EmailCredentials { api_key };
+    match base_url {
+        Some(base_url) => Ok(EmailClient::with_base_url(base_url, credentials)),
+        None => parse_env(env).map(|env| EmailClient::from_credentials(env, credentials)),
+    }
+}
+
+fn email_error(error: EmailError) -> PyErr {
+    match error {
+        EmailError::Http(error) => network_error(error),
+        EmailError::Api { status, message } => api_error(status, message),
+    }
+}
+
+/// `message` is a dict with the fields of `SendEmail` (`from`, `to`,
+/// `subject`, `html`, `text`, ...).
+#[pyfunction]
+#[pyo3(signature = (env, api_key, message, base_url=None))]
+pub(crate) fn email_send(
+    py: Python<'_>,
+    env: &str,
+    api_key: &str,
+    message: &Bound<'_, PyAny>,
+    base_url: Option<&str>,
+) -> PyResult<PyObject> {
+    let message: SendEmail =
+        depythonize(message).map_err(|e| PyValueError::new_err(format!("invalid message: {e}")))?;
+    let client = email_client(env, api_key, base_url)?;
+    block_on(py, client.send(&message))
+        .map_err(email_error)
+        .and_then(|result| serialize(py, &result))
+}
+
+#[pyfunction]
+#[pyo3(signature = (env, api_key, id, base_url=None))]
+pub(crate) fn email_get_message(
+    py: Python<'_>,
+    env: &str,
+    api_key: &str,
+    id: &str,
+    base_url: Option<&str>,
+) -> PyResult<PyObject> {
+    let client = email_client(env, api_key, base_url)?;
+    block_on(py, client.get_message(id))
+        .map_err(email_error)
+        .and_then(|result| serialize(py, &result))
+}
+
+#[pyfunction]
+#[pyo3(signature = (env, api_key, status=None, limit=None, cursor=None, base_url=None))]
+pub(crate) fn email_list_messages_page(
+    py: Python<'_>,
+    env: &str,
+    api_key: &str,
+    status: Option<&str>,
+    limit: Option<u32>,
+    cursor: Option<&str>,
+    base_url: Option<&str>,
+) -> PyResult<PyObject> {
+    let client = email_client(env, api_key, base_url)?;
+    block_on(py, client.list_messages_page(status, limit, cursor))
+        .map_err(email_error)
+        .and_then(|page| serialize(py, &page))
+}
@@ -0,0 +1,102 @@
+use crate::{api_error, block_on, network_error, parse_env, serialize};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use pythonize::depythonize;
+use serde_json::Value;
+use smbcloud_gresiq_sdk::{DocumentQuery, GresiqClient, GresiqCredentials, GresiqError};
+
+fn gresiq_client(
+    env: &str,
+    api_key: &str,
+    api_secret: &str,
+    base_url: Option<&str>,
+) -> PyResult<GresiqClient> {
+    let credentials = GresiqCredentials {
+        api_key,
+        api_secret,
+    };
+    match base_url {
+        Some(base_url) => Ok(GresiqClient::with_base_url(base_url, credentials)),
+        None => parse_env(env).map(|env| GresiqClient::from_credentials(env, credentials)),
+    }
+}
+
+fn gresiq_error(error: GresiqError) -> PyErr {
+    match error {
+        GresiqError::Http(error) => network_error(error),
+        GresiqError::Api { status, message } => api_error(status, message),
+    }
+}
+
+fn json_value(name: &str, value: &Bound<'_, PyAny>) -> PyResult<Value> {
+    depythonize(value).map_err(|e| PyValueError::new_err(format!("invalid {name}: {e}")))
+}
+
+#[pyfunction]
+#[pyo3(signature = (env, api_key, api_secret, table, record, base_url=None))]
+pub(crate) fn gresiq_insert(
+    py: Python<'_>,
+    env: &str,
+    api_key: &str,
+    api_secret: &str,
+    table: &str,
+    record: &Bound<'_, PyAny>,
+    base_url: Option<&str>,
+) -> PyResult<()> {
+    let record = json_value("record", record)?;
+    let client = gresiq_client(env, api_key, api_secret, base_url)?;
+    block_on(py, client.insert(table, &record)).map_err(gresiq_error)
+}
+
+#[pyfunction]
+#[pyo3(signature = (env, api_key, api_secret, collection, doc, key=None, base_url=None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gresiq_upsert_document(
+    py: Python<'_>,
+    env: &str,
+    api_key: &str,
+    api_secret: &str,
+    collection: &str,
+    doc: &Bound<'_, PyAny>,
+    key: Option<&str>,
+    base_url: Option<&str>,
+) -> PyResult<()> {
+    let doc = json_value("doc", doc)?;
+    let client = gresiq_client(env, api_key, api_secret, base_url)?;
+    block_on(py, client.upsert_document(collection, key, &doc)).map_err(gresiq_error)
+}
+
+/// `filter` is a dict matched by JSON containment; `order` and `dir` take
+/// `created_at`/`updated_at` and `asc`/`desc`.
+#[pyfunction]
+#[pyo3(signature = (
+    env, api_key, api_secret, collection,
+    filter=None, order=None, dir=None, limit=None, cursor=None, base_url=None
+))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gresiq_get_collection_page(
+    py: Python<'_>,
+    env: &str,
+    api_key: &str,
+    api_secret: &str,
+    collection: &str,
+    filter: Option<&Bound<'_, PyAny>>,
+    order: Option<String>,
+    dir: Option<String>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+    base_url: Option<&str>,
+) -> PyResult<PyObject> {
+    let query = DocumentQuery {
+        filter: filter
+            .map(|filter| json_value("filter", filter))
+            .transpose()?,
+        order,
+        dir,
+        limit,
+        cursor,
+    };
+    let client = gresiq_client(env, api_key, api_secret, base_url)?;
+    block_on(py, client.get_collection_page::<Value>(collection, &query))
+        .map_err(gresiq_error)
+        .and_then(|page| serialize(py, &page))
+}
@@ -5,6 +5,10 @@
 // so suppress the lint at crate level.
 #![allow(clippy::useless_conversion)]
 
+mod email;
+mod gresiq;
+mod oidc;
+
 use pyo3::{
     create_exception,
     exceptions::{PyException, PyValueError},
@@ -13,17 +17,21 @@ use pyo3::{
 use pythonize::pythonize;
 use serde::Serialize;
 use smbcloud_auth_sdk::{
-    client_credentials::ClientCredentials, login::login_with_client as rust_login_with_client,
+    client_credentials::ClientCredentials,
+    login::login_with_client_otp as rust_login_with_client_otp,
     logout::logout_with_client as rust_logout_with_client,
     me::me_with_client as rust_me_with_client,
     remove::remove_with_client as rust_remove_with_client,
+    reset_password::reset_password_with_client as rust_reset_password_with_client,
     signup::signup_with_client as rust_signup_with_client,
 };
 use smbcloud_model::{
-    account::ErrorCode as AccountErrorCode, error_codes::ErrorResponse, login::AccountStatus,
+    account::ErrorCode as AccountErrorCode,
+    error_codes::{ErrorCode, ErrorResponse},
+    login::AccountStatus,
 };
 use smbcloud_network::environment::Environment;
-use std::sync::OnceLock;
+use std::{future::Future, sync::OnceLock};
 use tokio::runtime::{Builder, Runtime};
 
 create_exception!(_native, NativeSdkError, PyException);
@@ -33,6 +41,9 @@ struct ErrorPayload {
     error_code: i32,
     error_name: String,
     message: String,
+    /// HTTP status of an Email or GresIQ API error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
 }
 
 #[derive(Serialize)]
@@ -47,6 +58,7 @@ enum LoginPayload {
         status_code: u32,
         message: String,
     },
+    OtpRequired,
 }
 
 fn runtime() -> &'static Runtime {
@@ -59,16 +71,26 @@ fn runtime() -> &'static Runtime {
     })
 }
 
-fn parse_env(env: &str) -> PyResult<Environment> {
+/// Runs `future` on the shared runtime with the GIL released, so the asyncio
+/// wrappers can have several calls in flight from executor threads.
+pub(crate) fn block_on<F>(py: Python<'_>, future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    py.allow_threads(|| runtime().block_on(future))
+}
+
+pub(crate) fn parse_env(env: &str) -> PyResult<Environment> {
     env.parse()
-        .map_err(|_| PyValueError::new_err("env must be 'dev' or 'production'"))
+        .map_err(|_| PyValueError::new_err("env must be 'dev', 'production' or 'custom'"))
 }
 
 fn client_credentials<'a>(app_id: &'a str, app_secret: &'a str) -> ClientCredentials<'a> {
     ClientCredentials { app_id, app_secret }
 }
 
-fn serialize<'py, T>(py: Python<'py>, value: &T) -> PyResult<PyObject>
+pub(crate) fn serialize<'py, T>(py: Python<'py>, value: &T) -> PyResult<PyObject>
 where
     T: Serialize,
 {
@@ -84,6 +106,7 @@ fn login_payload(status: AccountStatus) -> LoginPayload {
             status_code: status as u32,
             message: status.to_string(),
         },
+        AccountStatus::OtpRequired => LoginPayload::OtpRequired,
     }
 }
 
@@ -97,20 +120,45 @@ fn account_error_name(code: AccountErrorCode) -> &'static str {
         AccountErrorCode::EmailAlreadyExist => "email_already_exist",
         AccountErrorCode::InvalidPassword => "invalid_password",
         AccountErrorCode::HostedMailAccountUnverified => "hosted_mail_account_unverified",
+        AccountErrorCode::OtpRequired => "otp_required",
+        AccountErrorCode::InvalidOtp => "invalid_otp",
     }
 }
 
-fn native_error(error: ErrorResponse) -> PyErr {
+pub(crate) fn native_error(error: ErrorResponse) -> PyErr {
     let ErrorResponse::Error {
         error_code,
         message,
     } = error;
 
+    sdk_error(error_code, message, None)
+}
+
+/// A request to the Email or GresIQ API that never got a reply.
+pub(crate) fn network_error(error: impl ToString) -> PyErr {
+    sdk_error(ErrorCode::NetworkError, error.to_string(), None)
+}
+
+/// A non-2xx reply from the Email or GresIQ API, under the `ErrorCode` that
+/// matches its status so Python raises the same exception family as for the
+/// auth calls.
+pub(crate) fn api_error(status: u16, message: String) -> PyErr {
+    let error_code = match status {
+        400 | 422 => ErrorCode::InvalidParams,
+        401 => ErrorCode::Unauthorized,
+        403 => ErrorCode::Forbidden,
+        _ => ErrorCode::Unknown,
+    };
+    sdk_error(error_code, message, Some(status))
+}
+
+fn sdk_error(error_code: ErrorCode, message: String, status: Option<u16>) -> PyErr {
     let error_name = error_code.rb_constant_name();
     let payload = ErrorPayload {
         error_code: error_code as i32,
         error_name,
         message,
+        status,
     };
 
     let message = serde_json::to_string(&payload).unwrap_or_else(|_| {
@@ -131,44 +179,43 @@ fn signup_with_client(
 ) -> PyResult<PyObject> {
     parse_env(env).and_then(|env| {
         let client = client_credentials(app_id, app_secret);
-        runtime()
-            .block_on(rust_signup_with_client(
-                env,
-                client,
-                email.to_string(),
-                password.to_string(),
-            ))
-            .map_err(native_error)
-            .and_then(|result| serialize(py, &result))
+        block_on(
+            py,
+            rust_signup_with_client(env, client, email.to_string(), password.to_string()),
+        )
+        .map_err(native_error)
+        .and_then(|result| serialize(py, &result))
     })
 }
 
+/// `otp` is a TOTP or recovery code, for a user that has two-factor
+/// authentication on.
 #[pyfunction]
-fn login_with_client(
+#[pyo3(signature = (env, app_id, app_secret, email, password, otp=None))]
+fn login_with_client_otp(
     py: Python<'_>,
     env: &str,
     app_id: &str,
     app_secret: &str,
     email: &str,
     password: &str,
+    otp: Option<String>,
 ) -> PyResult<PyObject> {
     parse_env(env).and_then(|env| {
         let client = client_credentials(app_id, app_secret);
-        runtime()
-            .block_on(rust_login_with_client(
-                env,
-                client,
-                email.to_string(),
-                password.to_string(),
-            ))
-            .map(login_payload)
-            .map_err(native_error)
-            .and_then(|result| serialize(py, &result))
+        block_on(
+            py,
+            rust_login_with_client_otp(env, client, email.to_string(), password.to_string(), otp),
+        )
+        .map(login_payload)
+        .map_err(native_error)
+        .and_then(|result| serialize(py, &result))
     })
 }
 
 #[pyfunction]
 fn logout_with_client(
+    py: Python<'_>,
     env: &str,
     app_id: &str,
     app_secret: &str,
@@ -176,13 +223,11 @@ fn logout_with_client(
 ) -> PyResult<()> {
     parse_env(env).and_then(|env| {
         let client = client_credentials(app_id, app_secret);
-        runtime()
-            .block_on(rust_logout_with_client(
-                env,
-                client,
-                access_token.to_string(),
-            ))
-            .map_err(native_error)
+        block_on(
+            py,
+            rust_logout_with_client(env, client, access_token.to_string()),
+        )
+        .map_err(native_error)
     })
 }
 
@@ -196,8 +241,7 @@ fn me_with_client(
 ) -> PyResult<PyObject> {
     parse_env(env).and_then(|env| {
         let client = client_credentials(app_id, app_secret);
-        runtime()
-            .block_on(rust_me_with_client(env, client, access_token))
+        block_on(py, rust_me_with_client(env, client, access_token))
             .map_err(native_error)
             .and_then(|result| serialize(py, &result))
     })
@@ -205,6 +249,7 @@ fn me_with_client(
 
 #[pyfunction]
 fn remove_with_client(
+    py: Python<'_>,
     env: &str,
     app_id: &str,
     app_secret: &str,
@@ -212,9 +257,26 @@ fn remove_with_client(
 ) -> PyResult<()> {
     parse_env(env).and_then(|env| {
         let client = client_credentials(app_id, app_secret);
-        runtime()
-            .block_on(rust_remove_with_client(env, client, access_token))
-            .map_err(native_error)
+        block_on(py, rust_remove_with_client(env, client, access_token)).map_err(native_error)
+    })
+}
+
+#[pyfunction]
+fn reset_password_with_client(
+    py: Python<'_>,
+    env: &str,
+    app_id: &str,
+    app_secret: &str,
+    email: &str,
+) -> PyResult<PyObject> {
+    parse_env(env).and_then(|env| {
+        let client = client_credentials(app_id, app_secret);
+        block_on(
+            py,
+            rust_reset_password_with_client(env, client, email.to_string()),
+        )
+        .map_err(native_error)
+        .and_then(|result| serialize(py, &result))
     })
 }
 
@@ -222,9 +284,23 @@ fn remove_with_client(
 fn python_module(py: Python<'_>, module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add("NativeSdkError", py.get_type_bound::<NativeSdkError>())?;
     module.add_function(wrap_pyfunction!(signup_with_client, module)?)?;
-    module.add_function(wrap_pyfunction!(login_with_client, module)?)?;
+    module.add_function(wrap_pyfunction!(login_with_client_otp, module)?)?;
     module.add_function(wrap_pyfunction!(logout_with_client, module)?)?;
     module.add_function(wrap_pyfunction!(me_with_client, module)?)?;
     module.add_function(wrap_pyfunction!(remove_with_client, module)?)?;
+    module.add_function(wrap_pyfunction!(reset_password_with_client, module)?)?;
+    module.add_function(wrap_pyfunction!(oidc::build_authorization_request, module)?)?;
+    module.add_function(wrap_pyfunction!(oidc::parse_callback_url, module)?)?;
+    module.add_function(wrap_pyfunction!(oidc::exchange_code, module)?)?;
+    module.add_function(wrap_pyfunction!(oidc::get_userinfo, module)?)?;
+    module.add_function(wrap_pyfunction!(email::email_send, module)?)?;
+    module.add_function(wrap_pyfunction!(email::email_get_message, module)?)?;
+    module.add_function(wrap_pyfunction!(email::email_list_messages_page, module)?)?;
+    module.add_function(wrap_pyfunction!(gresiq::gresiq_insert, module)?)?;
+    module.add_function(wrap_pyfunction!(gresiq::gresiq_upsert_document, module)?)?;
+    module.add_function(wrap_pyfunction!(
+        gresiq::gresiq_get_collection_page,
+        module
+    )?)?;
     Ok(())
 }
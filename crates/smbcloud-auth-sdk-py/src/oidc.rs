@@ -0,0 +1,73 @@
+use crate::{block_on, native_error, parse_env, serialize};
+use pyo3::prelude::*;
+
+/// Starts a hosted login: the URL to send the browser to, plus the `state`
+/// and `code_verifier` to keep until it returns.
+#[pyfunction]
+pub(crate) fn build_authorization_request(
+    py: Python<'_>,
+    env: &str,
+    oidc_client_id: &str,
+    redirect_uri: &str,
+) -> PyResult<PyObject> {
+    parse_env(env).and_then(|env| {
+        smbcloud_auth_sdk::oidc::build_authorization_request(
+            env,
+            oidc_client_id,
+            redirect_uri.to_string(),
+        )
+        .map_err(native_error)
+        .and_then(|result| serialize(py, &result))
+    })
+}
+
+/// Reads the `code` and `state` off the URL the browser came back to.
+#[pyfunction]
+pub(crate) fn parse_callback_url(py: Python<'_>, callback_url: &str) -> PyResult<PyObject> {
+    smbcloud_auth_sdk::oidc::parse_callback_url(callback_url)
+        .map_err(native_error)
+        .and_then(|result| serialize(py, &result))
+}
+
+#[pyfunction]
+pub(crate) fn exchange_code(
+    py: Python<'_>,
+    env: &str,
+    oidc_client_id: &str,
+    redirect_uri: &str,
+    code: &str,
+    code_verifier: &str,
+) -> PyResult<PyObject> {
+    parse_env(env).and_then(|env| {
+        block_on(
+            py,
+            smbcloud_auth_sdk::oidc::exchange_code(
+                env,
+                oidc_client_id,
+                redirect_uri,
+                code,
+                code_verifier,
+            ),
+        )
+        .map_err(native_error)
+        .and_then(|result| serialize(py, &result))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (env, access_token, tenant_id=None))]
+pub(crate) fn get_userinfo(
+    py: Python<'_>,
+    env: &str,
+    access_token: &str,
+    tenant_id: Option<&str>,
+) -> PyResult<PyObject> {
+    parse_env(env).and_then(|env| {
+        block_on(
+            py,
+            smbcloud_auth_sdk::oidc::get_userinfo(env, access_token, tenant_id),
+        )
+        .map_err(native_error)
+        .and_then(|result| serialize(py, &result))
+    })
+}
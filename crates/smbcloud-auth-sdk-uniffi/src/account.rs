@@ -0,0 +1,131 @@
+use {
+    crate::{AuthError, ClientCredentials, Environment},
+    smbcloud_model::login::AccountStatus,
+};
+
+#[derive(Debug, Clone, uniffi::Enum)]
+pub enum LoginResult {
+    /// No account with that email.
+    NotFound,
+    Ready {
+        access_token: String,
+    },
+    /// The account exists but can't sign in yet, e.g. its email is
+    /// unverified. `code` is the account `ErrorCode` value.
+    Incomplete {
+        code: u32,
+        message: String,
+    },
+    /// The user has two-factor authentication on. Log in again with
+    /// `login_with_client_otp` and their TOTP or recovery code.
+    OtpRequired,
+}
+
+impl From<AccountStatus> for LoginResult {
+    fn from(status: AccountStatus) -> Self {
+        match status {
+            AccountStatus::NotFound => LoginResult::NotFound,
+            AccountStatus::Ready { access_token } => LoginResult::Ready { access_token },
+            AccountStatus::Incomplete { status } => LoginResult::Incomplete {
+                message: status.to_string(),
+                code: status as u32,
+            },
+            AccountStatus::OtpRequired => LoginResult::OtpRequired,
+        }
+    }
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct SignupResult {
+    pub code: Option<i32>,
+    pub message: String,
+    /// The new user's id, when the server returned it.
+    pub user_id: Option<i32>,
+}
+
+/// Timestamps are RFC 3339.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct User {
+    pub id: i32,
+    pub email: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn signup_with_client(
+    env: Environment,
+    client: ClientCredentials,
+    email: String,
+    password: String,
+) -> Result<SignupResult, AuthError> {
+    let result =
+        smbcloud_auth_sdk::signup::signup_with_client(env.into(), client.borrow(), email, password)
+            .await?;
+    Ok(SignupResult {
+        code: result.code,
+        message: result.message,
+        user_id: result.data.map(|data| data.id),
+    })
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn login_with_client(
+    env: Environment,
+    client: ClientCredentials,
+    email: String,
+    password: String,
+) -> Result<LoginResult, AuthError> {
+    let status =
+        smbcloud_auth_sdk::login::login_with_client(env.into(), client.borrow(), email, password)
+            .await?;
+    Ok(status.into())
+}
+
+/// Like `login_with_client`, with a TOTP or recovery code for a user that
+/// has two-factor authentication on.
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn login_with_client_otp(
+    env: Environment,
+    client: ClientCredentials,
+    email: String,
+    password: String,
+    otp: Option<String>,
+) -> Result<LoginResult, AuthError> {
+    let status = smbcloud_auth_sdk::login::login_with_client_otp(
+        env.into(),
+        client.borrow(),
+        email,
+        password,
+        otp,
+    )
+    .await?;
+    Ok(status.into())
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn me_with_client(
+    env: Environment,
+    client: ClientCredentials,
+    access_token: String,
+) -> Result<User, AuthError> {
+    let user =
+        smbcloud_auth_sdk::me::me_with_client(env.into(), client.borrow(), &access_token).await?;
+    Ok(User {
+        id: user.id,
+        email: user.email,
+        created_at: user.created_at.to_rfc3339(),
+        updated_at: user.updated_at.to_rfc3339(),
+    })
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn logout_with_client(
+    env: Environment,
+    client: ClientCredentials,
+    access_token: String,
+) -> Result<(), AuthError> {
+    smbcloud_auth_sdk::logout::logout_with_client(env.into(), client.borrow(), access_token)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::{AuthError, ClientCredentials, Environment};
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AppleAuthorizationRequest {
+    pub authorize_url: String,
+    pub redirect_uri: String,
+    pub state: String,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AppleAuthSession {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub provider: String,
+    pub provider_account_id: String,
+    pub state: Option<String>,
+}
+
+/// The URL to open in `ASWebAuthenticationSession` or a Custom Tab to sign in
+/// with Apple. A random `state` is generated when none is given.
+#[uniffi::export]
+pub fn apple_build_authorization_request(
+    env: Environment,
+    client: ClientCredentials,
+    redirect_uri: String,
+    state: Option<String>,
+) -> Result<AppleAuthorizationRequest, AuthError> {
+    let request = smbcloud_auth_sdk::apple::build_authorization_request_with_client(
+        env.into(),
+        client.borrow(),
+        redirect_uri,
+        state,
+    )?;
+    Ok(AppleAuthorizationRequest {
+        authorize_url: request.authorize_url,
+        redirect_uri: request.redirect_uri,
+        state: request.state,
+    })
+}
+
+/// Reads the session off the URL the browser came back to. Pass the `state`
+/// from [`apple_build_authorization_request`] to have it checked.
+#[uniffi::export]
+pub fn apple_parse_callback_url(
+    callback_url: String,
+    expected_state: Option<String>,
+) -> Result<AppleAuthSession, AuthError> {
+    let session =
+        smbcloud_auth_sdk::apple::parse_callback_url(&callback_url, expected_state.as_deref())?;
+    Ok(AppleAuthSession {
+        access_token: session.access_token,
+        refresh_token: session.refresh_token,
+        email: session.email,
+        name: session.name,
+        provider: session.provider,
+        provider_account_id: session.provider_account_id,
+        state: session.state,
+    })
+}
@@ -0,0 +1,82 @@
+//! Swift and Kotlin bindings for the smbCloud Auth SDK, generated with
+//! uniffi. Network calls are `async` and run on a tokio runtime owned by the
+//! bindings, so they can be awaited from Swift concurrency or Kotlin
+//! coroutines directly.
+
+mod account;
+mod apple;
+mod oidc;
+
+use smbcloud_model::error_codes::{ErrorCode, ErrorResponse};
+
+uniffi::setup_scaffolding!();
+
+#[derive(Debug, Clone, Copy, uniffi::Enum)]
+pub enum Environment {
+    Dev,
+    Production,
+    /// Resolved from `SMB_API_URL` and `SMB_DEPLOY_HOST`.
+    Custom,
+}
+
+impl From<Environment> for smbcloud_network::environment::Environment {
+    fn from(env: Environment) -> Self {
+        match env {
+            Environment::Dev => Self::Dev,
+            Environment::Production => Self::Production,
+            Environment::Custom => Self::Custom,
+        }
+    }
+}
+
+/// The `client_id` and `client_secret` of an smbCloud Auth app.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ClientCredentials {
+    pub app_id: String,
+    pub app_secret: String,
+}
+
+impl ClientCredentials {
+    fn borrow(&self) -> smbcloud_auth_sdk::client_credentials::ClientCredentials<'_> {
+        smbcloud_auth_sdk::client_credentials::ClientCredentials {
+            app_id: &self.app_id,
+            app_secret: &self.app_secret,
+        }
+    }
+}
+
+/// An `ErrorResponse` grouped by `ErrorCode` family. `code` is the raw
+/// `ErrorCode` value for callers that need finer matching.
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum AuthError {
+    /// The request never got a reply.
+    #[error("{message}")]
+    Network { message: String },
+    /// The reply, or a URL handed in, could not be read.
+    #[error("{message}")]
+    Parse { message: String },
+    /// Codes 100-199: unauthorized, invalid parameters or forbidden.
+    #[error("{message}")]
+    Unauthorized { code: i32, message: String },
+    /// Codes 1000-1999: email, password and login-flow errors.
+    #[error("{message}")]
+    Account { code: i32, message: String },
+    #[error("{message}")]
+    Other { code: i32, message: String },
+}
+
+impl From<ErrorResponse> for AuthError {
+    fn from(error: ErrorResponse) -> Self {
+        let ErrorResponse::Error {
+            error_code,
+            message,
+        } = error;
+        match error_code as i32 {
+            code if code == ErrorCode::NetworkError as i32 => AuthError::Network { message },
+            code if code == ErrorCode::ParseError as i32 => AuthError::Parse { message },
+            code @ 100..200 => AuthError::Unauthorized { code, message },
+            code @ 1000..2000 => AuthError::Account { code, message },
+            code => AuthError::Other { code, message },
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{AuthError, Environment};
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AuthorizationRequest {
+    pub authorize_url: String,
+    pub redirect_uri: String,
+    pub state: String,
+    pub code_verifier: String,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct CallbackPayload {
+    pub code: String,
+    pub state: String,
+    pub tenant_id: Option<String>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: Option<i32>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+    pub id_token: Option<String>,
+}
+
+impl From<smbcloud_auth_sdk::oidc::TokenResponse> for TokenResponse {
+    fn from(token: smbcloud_auth_sdk::oidc::TokenResponse) -> Self {
+        TokenResponse {
+            access_token: token.access_token,
+            token_type: token.token_type,
+            expires_in: token.expires_in,
+            refresh_token: token.refresh_token,
+            scope: token.scope,
+            id_token: token.id_token,
+        }
+    }
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct UserInfo {
+    pub sub: String,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub role: Option<i32>,
+    pub updated_at: Option<i64>,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+}
+
+/// Starts a hosted login: the URL to open, plus the `state` and
+/// `code_verifier` to keep until the browser returns.
+#[uniffi::export]
+pub fn oidc_build_authorization_request(
+    env: Environment,
+    oidc_client_id: String,
+    redirect_uri: String,
+) -> Result<AuthorizationRequest, AuthError> {
+    let request = smbcloud_auth_sdk::oidc::build_authorization_request(
+        env.into(),
+        &oidc_client_id,
+        redirect_uri,
+    )?;
+    Ok(AuthorizationRequest {
+        authorize_url: request.authorize_url,
+        redirect_uri: request.redirect_uri,
+        state: request.state,
+        code_verifier: request.code_verifier,
+    })
+}
+
+/// Reads the `code` and `state` off the URL the browser came back to. Compare
+/// `state` with the one from [`oidc_build_authorization_request`].
+#[uniffi::export]
+pub fn oidc_parse_callback_url(callback_url: String) -> Result<CallbackPayload, AuthError> {
+    let payload = smbcloud_auth_sdk::oidc::parse_callback_url(&callback_url)?;
+    Ok(CallbackPayload {
+        code: payload.code,
+        state: payload.state,
+        tenant_id: payload.tenant_id,
+    })
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn oidc_exchange_code(
+    env: Environment,
+    oidc_client_id: String,
+    redirect_uri: String,
+    code: String,
+    code_verifier: String,
+) -> Result<TokenResponse, AuthError> {
+    let token = smbcloud_auth_sdk::oidc::exchange_code(
+        env.into(),
+        &oidc_client_id,
+        &redirect_uri,
+        &code,
+        &code_verifier,
+    )
+    .await?;
+    Ok(token.into())
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn oidc_refresh_token(
+    env: Environment,
+    oidc_client_id: String,
+    refresh_token: String,
+) -> Result<TokenResponse, AuthError> {
+    let token =
+        smbcloud_auth_sdk::oidc::refresh_token(env.into(), &oidc_client_id, &refresh_token).await?;
+    Ok(token.into())
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn oidc_get_userinfo(
+    env: Environment,
+    access_token: String,
+    tenant_id: Option<String>,
+) -> Result<UserInfo, AuthError> {
+    let user =
+        smbcloud_auth_sdk::oidc::get_userinfo(env.into(), &access_token, tenant_id.as_deref())
+            .await?;
+    Ok(UserInfo {
+        sub: user.sub,
+        name: user.name,
+        preferred_username: user.preferred_username,
+        role: user.role,
+        updated_at: user.updated_at,
+        email: user.email,
+        email_verified: user.email_verified,
+    })
+}
@@ -2,5 +2,56 @@ mod apple;
 mod login;
 mod logout;
 mod me;
+mod oidc;
 mod remove;
+mod reset_password;
 mod signup;
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+// Shapes of the objects the exports resolve and reject with. Errors carry
+// the numeric `ErrorCode` of smbcloud-model, e.g. 100 for Unauthorized.
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+export interface SmbcloudAuthError {
+  error_code: number;
+  message: string;
+}
+
+export interface AuthorizationRequest {
+  authorize_url: string;
+  redirect_uri: string;
+  state: string;
+  code_verifier: string;
+}
+
+export interface CallbackPayload {
+  code: string;
+  state: string;
+  tenant_id?: string;
+}
+
+export interface TokenResponse {
+  access_token: string;
+  token_type: string;
+  expires_in?: number;
+  refresh_token?: string;
+  scope?: string;
+  id_token?: string;
+}
+
+export interface UserInfo {
+  sub: string;
+  name?: string;
+  preferred_username?: string;
+  role?: number;
+  updated_at?: number;
+  email?: string;
+  email_verified?: boolean;
+}
+
+export interface ResetPasswordResponse {
+  code?: number;
+  message: string;
+}
+"#;
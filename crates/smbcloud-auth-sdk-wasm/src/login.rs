@@ -21,3 +21,26 @@ pub async fn login_with_client(
         Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
     }
 }
+
+/// Like `login_with_client`, with a TOTP or recovery code for a user that
+/// has two-factor authentication on. Send it after a login resolved to
+/// `"OtpRequired"`.
+#[wasm_bindgen]
+pub async fn login_with_client_otp(
+    env: Environment,
+    app_id: String,
+    app_secret: String,
+    email: String,
+    password: String,
+    otp: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let client = ClientCredentials {
+        app_id: &app_id,
+        app_secret: &app_secret,
+    };
+
+    match smbcloud_auth_sdk::login::login_with_client_otp(env, client, email, password, otp).await {
+        Ok(response) => Ok(serde_wasm_bindgen::to_value(&response)?),
+        Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+    }
+}
@@ -0,0 +1,61 @@
+use smbcloud_network::environment::Environment;
+use wasm_bindgen::JsValue;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Starts a hosted login: the URL to send the browser to, plus the `state`
+/// and `code_verifier` to keep (e.g. in `sessionStorage`) until it returns.
+#[wasm_bindgen(unchecked_return_type = "AuthorizationRequest")]
+pub fn build_authorization_request(
+    env: Environment,
+    oidc_client_id: String,
+    redirect_uri: String,
+) -> Result<JsValue, JsValue> {
+    match smbcloud_auth_sdk::oidc::build_authorization_request(env, &oidc_client_id, redirect_uri) {
+        Ok(response) => Ok(serde_wasm_bindgen::to_value(&response)?),
+        Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+    }
+}
+
+/// Reads the `code` and `state` off the URL the browser came back to. Compare
+/// `state` with the one kept from [`build_authorization_request`].
+#[wasm_bindgen(unchecked_return_type = "CallbackPayload")]
+pub fn parse_callback_url(callback_url: String) -> Result<JsValue, JsValue> {
+    match smbcloud_auth_sdk::oidc::parse_callback_url(&callback_url) {
+        Ok(response) => Ok(serde_wasm_bindgen::to_value(&response)?),
+        Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+    }
+}
+
+#[wasm_bindgen(unchecked_return_type = "TokenResponse")]
+pub async fn exchange_code(
+    env: Environment,
+    oidc_client_id: String,
+    redirect_uri: String,
+    code: String,
+    code_verifier: String,
+) -> Result<JsValue, JsValue> {
+    match smbcloud_auth_sdk::oidc::exchange_code(
+        env,
+        &oidc_client_id,
+        &redirect_uri,
+        &code,
+        &code_verifier,
+    )
+    .await
+    {
+        Ok(response) => Ok(serde_wasm_bindgen::to_value(&response)?),
+        Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+    }
+}
+
+#[wasm_bindgen(unchecked_return_type = "UserInfo")]
+pub async fn get_userinfo(
+    env: Environment,
+    access_token: String,
+    tenant_id: Option<String>,
+) -> Result<JsValue, JsValue> {
+    match smbcloud_auth_sdk::oidc::get_userinfo(env, &access_token, tenant_id.as_deref()).await {
+        Ok(response) => Ok(serde_wasm_bindgen::to_value(&response)?),
+        Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+    }
+}
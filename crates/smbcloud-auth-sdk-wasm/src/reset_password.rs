@@ -0,0 +1,22 @@
+use smbcloud_auth_sdk::client_credentials::ClientCredentials;
+use smbcloud_network::environment::Environment;
+use wasm_bindgen::JsValue;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen(unchecked_return_type = "ResetPasswordResponse")]
+pub async fn reset_password_with_client(
+    env: Environment,
+    app_id: String,
+    app_secret: String,
+    email: String,
+) -> Result<JsValue, JsValue> {
+    let client = ClientCredentials {
+        app_id: &app_id,
+        app_secret: &app_secret,
+    };
+
+    match smbcloud_auth_sdk::reset_password::reset_password_with_client(env, client, email).await {
+        Ok(response) => Ok(serde_wasm_bindgen::to_value(&response)?),
+        Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+    }
+}
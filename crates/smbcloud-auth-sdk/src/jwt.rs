@@ -0,0 +1,638 @@
+//! Offline verification of smbCloud Auth tokens.
+//!
+//! Instead of calling `get_userinfo` or `me_with_client` on every request, a
+//! service can check an `id_token` or access token locally: the signature
+//! against the issuer's JWKS, then `iss`, `aud`, `exp` and `nbf`. The JWKS is
+//! fetched once and cached; a token signed by a key the cache doesn't know
+//! triggers one refetch, which picks up key rotation. Refetches are limited to
+//! one per [`DEFAULT_JWKS_REFETCH_COOLDOWN`], so tokens with made-up `kid`s
+//! can't make the verifier hammer the issuer.
+//!
+//! RS256, ES256 and EdDSA (Ed25519) are accepted; `none` never is.
+
+use {
+    crate::oidc::UserInfo,
+    base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD},
+    ring::signature::{
+        ECDSA_P256_SHA256_FIXED, ED25519, RSA_PKCS1_2048_8192_SHA256, RsaPublicKeyComponents,
+        UnparsedPublicKey,
+    },
+    serde::{Deserialize, Serialize, de::DeserializeOwned},
+    smbcloud_model::error_codes::{ErrorCode, ErrorResponse},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network},
+    std::{
+        future::Future,
+        pin::Pin,
+        sync::RwLock,
+        time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    },
+};
+
+const JWKS_PATH: &str = "v1/client/oauth/jwks";
+/// How long a fetched JWKS is trusted before it is fetched again.
+pub const DEFAULT_JWKS_TTL: Duration = Duration::from_secs(60 * 60);
+/// Shortest time between two fetches of a JWKS that is still fresh. A token
+/// with an unknown key inside it is rejected without a fetch.
+pub const DEFAULT_JWKS_REFETCH_COOLDOWN: Duration = Duration::from_secs(60);
+/// Clock skew tolerated on `exp` and `nbf`, in seconds.
+pub const DEFAULT_LEEWAY: i64 = 60;
+
+/// One public key of a JSON Web Key Set (RFC 7517). Only the members the
+/// supported algorithms need are read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// RSA modulus, base64url.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// RSA exponent, base64url.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// EC x coordinate, or the Ed25519 public key, base64url.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// EC y coordinate, base64url.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+pub type JwksFuture<'a> = Pin<Box<dyn Future<Output = Result<Jwks, ErrorResponse>> + Send + 'a>>;
+
+/// Where a [`JwtVerifier`] gets its keys. [`HttpJwks`] fetches the issuer's
+/// published set; [`StaticJwks`] serves a fixed one, for pinned keys and
+/// tests.
+pub trait JwksSource: Send + Sync {
+    fn fetch(&self) -> JwksFuture<'_>;
+}
+
+/// The JWKS published at `url`.
+pub struct HttpJwks {
+    url: String,
+}
+
+impl HttpJwks {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    /// The JWKS of smbCloud Auth in `env`.
+    pub fn for_environment(env: Environment) -> Self {
+        Self::new(format!("{}/{JWKS_PATH}", env.api_base_url()))
+    }
+}
+
+impl JwksSource for HttpJwks {
+    fn fetch(&self) -> JwksFuture<'_> {
+        Box::pin(async move {
+            let builder = shared_client()
+                .get(&self.url)
+                .header("Accept", "application/json");
+            network::request(builder).await
+        })
+    }
+}
+
+pub struct StaticJwks(pub Jwks);
+
+impl JwksSource for StaticJwks {
+    fn fetch(&self) -> JwksFuture<'_> {
+        let jwks = self.0.clone();
+        Box::pin(async move { Ok(jwks) })
+    }
+}
+
+/// `aud` is either one string or an array of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    pub fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::One(value) => value == audience,
+            Audience::Many(values) => values.iter().any(|value| value == audience),
+        }
+    }
+}
+
+/// The claims of a verified token. The profile claims mirror [`UserInfo`]
+/// and are present only when the token's scopes grant them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: Audience,
+    /// Unix timestamp (seconds).
+    pub exp: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+    /// The tenant the token was issued for, when the issuer scoped it to one.
+    /// Issuers send it as a string or a number.
+    #[serde(
+        default,
+        deserialize_with = "string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tenant_id: Option<String>,
+}
+
+fn string_or_number<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::String(value)) => Some(value),
+            Some(serde_json::Value::Number(value)) => Some(value.to_string()),
+            _ => None,
+        },
+    )
+}
+
+impl Claims {
+    /// The same user as `get_userinfo` would return for this token.
+    pub fn user_info(&self) -> UserInfo {
+        UserInfo {
+            sub: self.sub.clone(),
+            name: self.name.clone(),
+            preferred_username: self.preferred_username.clone(),
+            role: self.role,
+            updated_at: self.updated_at,
+            email: self.email.clone(),
+            email_verified: self.email_verified,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Header {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+struct CachedJwks {
+    fetched_at: Instant,
+    jwks: Jwks,
+}
+
+/// Verifies tokens issued to one OIDC client (`audience`) by one issuer.
+/// Share one verifier across requests so they share its key cache.
+pub struct JwtVerifier<S = HttpJwks> {
+    source: S,
+    issuer: String,
+    audience: String,
+    leeway: i64,
+    ttl: Duration,
+    refetch_cooldown: Duration,
+    cache: RwLock<Option<CachedJwks>>,
+}
+
+impl JwtVerifier<HttpJwks> {
+    /// A verifier for tokens smbCloud Auth in `env` issued to
+    /// `oidc_client_id`.
+    pub fn new(env: Environment, oidc_client_id: impl Into<String>) -> Self {
+        Self::with_source(
+            HttpJwks::for_environment(env),
+            env.api_base_url(),
+            oidc_client_id,
+        )
+    }
+}
+
+impl<S: JwksSource> JwtVerifier<S> {
+    pub fn with_source(source: S, issuer: impl Into<String>, audience: impl Into<String>) -> Self {
+        Self {
+            source,
+            issuer: issuer.into(),
+            audience: audience.into(),
+            leeway: DEFAULT_LEEWAY,
+            ttl: DEFAULT_JWKS_TTL,
+            refetch_cooldown: DEFAULT_JWKS_REFETCH_COOLDOWN,
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Clock skew tolerated on `exp` and `nbf`, in seconds.
+    pub fn leeway(mut self, leeway: i64) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    /// How long a fetched JWKS is used before it is fetched again.
+    pub fn jwks_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// How long after a fetch an unknown key is rejected instead of fetching
+    /// the JWKS again.
+    pub fn jwks_refetch_cooldown(mut self, cooldown: Duration) -> Self {
+        self.refetch_cooldown = cooldown;
+        self
+    }
+
+    /// Verifies `token` and returns its claims.
+    pub async fn verify(&self, token: &str) -> Result<Claims, ErrorResponse> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default();
+        self.verify_at(token, now).await
+    }
+
+    /// Like [`Self::verify`], with `now` as a Unix timestamp (seconds).
+    pub async fn verify_at(&self, token: &str, now: i64) -> Result<Claims, ErrorResponse> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(malformed());
+        };
+        let signed = &token[..header.len() + 1 + payload.len()];
+        let header: Header = decode_json(header)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| malformed())?;
+
+        let jwk = self.key(header.kid.as_deref(), &header.alg).await?;
+        verify_signature(&jwk, &header.alg, signed.as_bytes(), &signature)?;
+
+        let claims: Claims = decode_json(payload)?;
+        if claims.iss.trim_end_matches('/') != self.issuer.trim_end_matches('/') {
+            return Err(unauthorized("Token was issued by another issuer."));
+        }
+        if !claims.aud.contains(&self.audience) {
+            return Err(unauthorized("Token was issued to another client."));
+        }
+        if claims.exp + self.leeway <= now {
+            return Err(unauthorized("Token has expired."));
+        }
+        if claims.nbf.is_some_and(|nbf| nbf - self.leeway > now) {
+            return Err(unauthorized("Token is not valid yet."));
+        }
+        Ok(claims)
+    }
+
+    /// The key that signed a token, from the cache when it's fresh and knows
+    /// the key, fetched again otherwise unless the last fetch is within the
+    /// refetch cooldown.
+    async fn key(&self, kid: Option<&str>, alg: &str) -> Result<Jwk, ErrorResponse> {
+        if let Some(jwk) = self.cached_key(kid, alg) {
+            return Ok(jwk);
+        }
+        if self.cooling_down() {
+            return Err(unknown_key());
+        }
+        let jwks = self.source.fetch().await?;
+        let jwk = find_key(&jwks, kid, alg);
+        *self.cache.write().unwrap_or_else(|e| e.into_inner()) = Some(CachedJwks {
+            fetched_at: Instant::now(),
+            jwks,
+        });
+        jwk.ok_or_else(unknown_key)
+    }
+
+    /// True while the cached JWKS is fresh and was fetched less than the
+    /// refetch cooldown ago.
+    fn cooling_down(&self) -> bool {
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        cache.as_ref().is_some_and(|cached| {
+            let age = cached.fetched_at.elapsed();
+            age < self.ttl && age < self.refetch_cooldown
+        })
+    }
+
+    fn cached_key(&self, kid: Option<&str>, alg: &str) -> Option<Jwk> {
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        let cached = cache.as_ref()?;
+        if cached.fetched_at.elapsed() >= self.ttl {
+            return None;
+        }
+        find_key(&cached.jwks, kid, alg)
+    }
+}
+
+fn find_key(jwks: &Jwks, kid: Option<&str>, alg: &str) -> Option<Jwk> {
+    jwks.keys
+        .iter()
+        .filter(|jwk| jwk.alg.as_deref().is_none_or(|key_alg| key_alg == alg))
+        .find(|jwk| kid.is_none() || jwk.kid.as_deref() == kid)
+        .cloned()
+}
+
+fn verify_signature(
+    jwk: &Jwk,
+    alg: &str,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), ErrorResponse> {
+    let bad_signature = || unauthorized("Token signature is invalid.");
+    let result = match (alg, jwk.kty.as_str()) {
+        ("RS256", "RSA") => RsaPublicKeyComponents {
+            n: key_bytes(&jwk.n)?,
+            e: key_bytes(&jwk.e)?,
+        }
+        .verify(&RSA_PKCS1_2048_8192_SHA256, message, signature),
+        ("ES256", "EC") if jwk.crv.as_deref() == Some("P-256") => {
+            // Uncompressed SEC1 point: 0x04 || x || y.
+            let point = [vec![0x04], key_bytes(&jwk.x)?, key_bytes(&jwk.y)?].concat();
+            UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point).verify(message, signature)
+        }
+        ("EdDSA", "OKP") if jwk.crv.as_deref() == Some("Ed25519") => {
+            UnparsedPublicKey::new(&ED25519, key_bytes(&jwk.x)?).verify(message, signature)
+        }
+        _ => {
+            return Err(unauthorized(&format!(
+                "Token algorithm `{alg}` is not accepted."
+            )));
+        }
+    };
+    result.map_err(|_| bad_signature())
+}
+
+fn key_bytes(value: &Option<String>) -> Result<Vec<u8>, ErrorResponse> {
+    value
+        .as_deref()
+        .and_then(|value| URL_SAFE_NO_PAD.decode(value).ok())
+        .ok_or_else(|| unauthorized("Signing key is incomplete."))
+}
+
+fn decode_json<T: DeserializeOwned>(segment: &str) -> Result<T, ErrorResponse> {
+    let bytes = URL_SAFE_NO_PAD.decode(segment).map_err(|_| malformed())?;
+    serde_json::from_slice(&bytes).map_err(|_| malformed())
+}
+
+fn malformed() -> ErrorResponse {
+    ErrorResponse::Error {
+        error_code: ErrorCode::ParseError,
+        message: "Token is not a well-formed JWT.".to_string(),
+    }
+}
+
+fn unknown_key() -> ErrorResponse {
+    unauthorized("Token was signed with an unknown key.")
+}
+
+fn unauthorized(message: &str) -> ErrorResponse {
+    ErrorResponse::Error {
+        error_code: ErrorCode::Unauthorized,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        ring::{
+            rand::SystemRandom,
+            signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, Ed25519KeyPair, KeyPair},
+        },
+        serde_json::json,
+        std::sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    const ISSUER: &str = "https://api.smbcloud.xyz";
+    const NOW: i64 = 1_800_000_000;
+
+    struct EcKey {
+        kid: &'static str,
+        pair: EcdsaKeyPair,
+    }
+
+    impl EcKey {
+        fn generate(kid: &'static str) -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+            let pair =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap();
+            Self { kid, pair }
+        }
+
+        fn jwk(&self) -> Jwk {
+            let point = self.pair.public_key().as_ref();
+            Jwk {
+                kty: "EC".to_string(),
+                kid: Some(self.kid.to_string()),
+                alg: Some("ES256".to_string()),
+                n: None,
+                e: None,
+                crv: Some("P-256".to_string()),
+                x: Some(URL_SAFE_NO_PAD.encode(&point[1..33])),
+                y: Some(URL_SAFE_NO_PAD.encode(&point[33..])),
+            }
+        }
+
+        fn sign(&self, claims: serde_json::Value) -> String {
+            let signed = signing_input(json!({"alg": "ES256", "kid": self.kid}), claims);
+            let signature = self
+                .pair
+                .sign(&SystemRandom::new(), signed.as_bytes())
+                .unwrap();
+            format!("{signed}.{}", URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        }
+    }
+
+    fn signing_input(header: serde_json::Value, claims: serde_json::Value) -> String {
+        format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        )
+    }
+
+    fn claims() -> serde_json::Value {
+        json!({
+            "iss": ISSUER,
+            "sub": "42",
+            "aud": "my-app",
+            "exp": NOW + 300,
+            "iat": NOW,
+            "email": "user@example.com",
+            "email_verified": true,
+        })
+    }
+
+    /// Serves whatever JWKS it holds, counting fetches.
+    struct CountingJwks {
+        jwks: RwLock<Jwks>,
+        fetches: AtomicUsize,
+    }
+
+    impl JwksSource for CountingJwks {
+        fn fetch(&self) -> JwksFuture<'_> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let jwks = self.jwks.read().unwrap().clone();
+            Box::pin(async move { Ok(jwks) })
+        }
+    }
+
+    #[tokio::test]
+    async fn verifies_a_token_and_returns_its_claims() {
+        let key = EcKey::generate("k1");
+        let verifier = JwtVerifier::with_source(
+            StaticJwks(Jwks {
+                keys: vec![key.jwk()],
+            }),
+            ISSUER,
+            "my-app",
+        );
+
+        let claims = verifier.verify_at(&key.sign(claims()), NOW).await.unwrap();
+
+        assert_eq!(claims.sub, "42");
+        assert_eq!(
+            claims.user_info().email.as_deref(),
+            Some("user@example.com")
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_issuer_audience_expiry_and_signature() {
+        let key = EcKey::generate("k1");
+        let verifier = JwtVerifier::with_source(
+            StaticJwks(Jwks {
+                keys: vec![key.jwk()],
+            }),
+            ISSUER,
+            "my-app",
+        );
+        let with = |field: &str, value: serde_json::Value| {
+            let mut claims = claims();
+            claims[field] = value;
+            key.sign(claims)
+        };
+
+        for token in [
+            with("iss", json!("https://evil.example")),
+            with("aud", json!(["other-app"])),
+            with("exp", json!(NOW - 120)),
+            with("nbf", json!(NOW + 120)),
+        ] {
+            assert!(verifier.verify_at(&token, NOW).await.is_err());
+        }
+
+        // Signed by a key the issuer never published.
+        let stranger = EcKey::generate("k1");
+        assert!(
+            verifier
+                .verify_at(&stranger.sign(claims()), NOW)
+                .await
+                .is_err()
+        );
+
+        // `alg: none` with no signature.
+        let unsigned = format!("{}.", signing_input(json!({"alg": "none"}), claims()));
+        assert!(verifier.verify_at(&unsigned, NOW).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn accepts_ed25519_keys() {
+        let rng = SystemRandom::new();
+        let pair =
+            Ed25519KeyPair::from_pkcs8(Ed25519KeyPair::generate_pkcs8(&rng).unwrap().as_ref())
+                .unwrap();
+        let jwk = Jwk {
+            kty: "OKP".to_string(),
+            kid: None,
+            alg: None,
+            n: None,
+            e: None,
+            crv: Some("Ed25519".to_string()),
+            x: Some(URL_SAFE_NO_PAD.encode(pair.public_key().as_ref())),
+            y: None,
+        };
+        let signed = signing_input(json!({"alg": "EdDSA"}), claims());
+        let token = format!(
+            "{signed}.{}",
+            URL_SAFE_NO_PAD.encode(pair.sign(signed.as_bytes()).as_ref())
+        );
+        let verifier =
+            JwtVerifier::with_source(StaticJwks(Jwks { keys: vec![jwk] }), ISSUER, "my-app");
+
+        assert!(verifier.verify_at(&token, NOW).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn caches_keys_and_refetches_for_an_unknown_kid() {
+        let old = EcKey::generate("old");
+        let new = EcKey::generate("new");
+        let source = CountingJwks {
+            jwks: RwLock::new(Jwks {
+                keys: vec![old.jwk()],
+            }),
+            fetches: AtomicUsize::new(0),
+        };
+        let verifier = JwtVerifier::with_source(source, ISSUER, "my-app")
+            .jwks_refetch_cooldown(Duration::ZERO);
+
+        verifier.verify_at(&old.sign(claims()), NOW).await.unwrap();
+        verifier.verify_at(&old.sign(claims()), NOW).await.unwrap();
+        assert_eq!(verifier.source.fetches.load(Ordering::SeqCst), 1);
+
+        // The issuer rotates to a new key.
+        verifier.source.jwks.write().unwrap().keys.push(new.jwk());
+        verifier.verify_at(&new.sign(claims()), NOW).await.unwrap();
+        assert_eq!(verifier.source.fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn unknown_kids_refetch_once_per_cooldown() {
+        let known = EcKey::generate("known");
+        let source = CountingJwks {
+            jwks: RwLock::new(Jwks {
+                keys: vec![known.jwk()],
+            }),
+            fetches: AtomicUsize::new(0),
+        };
+        let verifier = JwtVerifier::with_source(source, ISSUER, "my-app");
+
+        for kid in ["forged-1", "forged-2"] {
+            let forged = EcKey::generate(kid);
+            assert!(
+                verifier
+                    .verify_at(&forged.sign(claims()), NOW)
+                    .await
+                    .is_err()
+            );
+        }
+        assert_eq!(verifier.source.fetches.load(Ordering::SeqCst), 1);
+
+        // Known keys still verify from the cache meanwhile.
+        verifier
+            .verify_at(&known.sign(claims()), NOW)
+            .await
+            .unwrap();
+        assert_eq!(verifier.source.fetches.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,9 +1,14 @@
 pub mod apple;
 pub mod client_credentials;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod jwt;
 pub mod login;
 pub mod logout;
 pub mod me;
+#[cfg(all(feature = "tower", not(target_arch = "wasm32")))]
+pub mod middleware;
 pub mod oidc;
 pub mod remove;
 pub mod reset_password;
 pub mod signup;
+pub mod two_factor;
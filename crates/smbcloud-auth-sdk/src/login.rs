@@ -1,11 +1,12 @@
 use {
     crate::client_credentials::{ClientCredentials, base_url_builder as tenant_base_url_builder},
-    reqwest::Client,
     smbcloud_model::{
         error_codes::ErrorResponse,
         login::{AccountStatus, LoginParams, UserParam},
     },
-    smbcloud_network::{environment::Environment, network::request_login},
+    smbcloud_network::{
+        api_client::shared_client, environment::Environment, network::request_login,
+    },
 };
 
 pub async fn login_with_client(
@@ -13,14 +14,28 @@ pub async fn login_with_client(
     client: ClientCredentials<'_>,
     username: String,
     password: String,
+) -> Result<AccountStatus, ErrorResponse> {
+    login_with_client_otp(env, client, username, password, None).await
+}
+
+/// Like [`login_with_client`], with a TOTP or recovery code for users that
+/// have two-factor authentication on. Without one, such a user resolves to
+/// [`AccountStatus::OtpRequired`].
+pub async fn login_with_client_otp(
+    env: Environment,
+    client: ClientCredentials<'_>,
+    username: String,
+    password: String,
+    otp: Option<String>,
 ) -> Result<AccountStatus, ErrorResponse> {
     let login_params = LoginParams {
         user: UserParam {
             email: username,
             password,
+            otp_attempt: otp,
         },
     };
-    let builder = Client::new()
+    let builder = shared_client()
         .post(build_login_url(env, client))
         .json(&login_params)
         .header("User-agent", client.app_id);
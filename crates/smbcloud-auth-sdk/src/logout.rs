@@ -1,8 +1,8 @@
 use {
     crate::client_credentials::{ClientCredentials, base_url_builder as tenant_base_url_builder},
-    reqwest::{Client, StatusCode},
+    reqwest::StatusCode,
     smbcloud_model::error_codes::{ErrorCode, ErrorResponse},
-    smbcloud_network::environment::Environment,
+    smbcloud_network::{api_client::shared_client, environment::Environment},
 };
 
 pub async fn logout_with_client(
@@ -10,7 +10,7 @@ pub async fn logout_with_client(
     client: ClientCredentials<'_>,
     access_token: String,
 ) -> Result<(), ErrorResponse> {
-    let response = match Client::new()
+    let response = match shared_client()
         .delete(build_logout_url(env, client))
         .header("Authorization", access_token)
         .header("Accept", "application/json")
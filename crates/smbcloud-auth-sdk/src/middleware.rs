@@ -0,0 +1,383 @@
+//! Tower middleware that protects a service with smbCloud Auth (`tower`
+//! feature).
+//!
+//! [`AuthLayer`] takes the bearer token off each request and verifies it,
+//! offline against the issuer's JWKS or by calling `get_userinfo`. A request
+//! that passes reaches the inner service with an [`AuthenticatedUser`] in its
+//! extensions; one that doesn't is answered `401`, or `403` when the user
+//! lacks a required role.
+//!
+//! ```ignore
+//! let verifier = JwtVerifier::new(Environment::Production, "your-oidc-client-id");
+//! let app = Router::new()
+//!     .route("/me", get(|Extension(user): Extension<AuthenticatedUser>| async move {
+//!         user.user.sub
+//!     }))
+//!     .layer(AuthLayer::offline(verifier));
+//! ```
+
+use {
+    crate::{
+        jwt::{Claims, HttpJwks, JwksSource, JwtVerifier},
+        oidc::{UserInfo, get_userinfo},
+    },
+    http::{HeaderValue, Request, Response, StatusCode, header},
+    smbcloud_model::error_codes::{ErrorCode, ErrorResponse},
+    smbcloud_network::{api_client::TENANT_HEADER, environment::Environment},
+    std::{
+        future::Future,
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Poll},
+    },
+    tower_layer::Layer,
+    tower_service::Service,
+};
+
+/// What the middleware puts in the extensions of a request it let through.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub user: UserInfo,
+    /// The tenant the request acts on. With `get_userinfo` it is the
+    /// `X-Smbcloud-Tenant-Id` the issuer checked the user belongs to; offline
+    /// it is the token's `tenant_id` claim and the header is ignored. `None`
+    /// when neither applies.
+    pub tenant_id: Option<String>,
+    /// The token's claims, when it was verified offline.
+    pub claims: Option<Claims>,
+}
+
+enum Verification<S> {
+    Offline(JwtVerifier<S>),
+    UserInfo(Environment),
+}
+
+/// Verifies the bearer token of every request before the inner service sees
+/// it.
+pub struct AuthLayer<S = HttpJwks> {
+    verification: Arc<Verification<S>>,
+    /// Roles a user needs one of; empty means any user.
+    roles: Arc<[i32]>,
+}
+
+// Derived `Clone` would needlessly require `S: Clone`.
+impl<S> Clone for AuthLayer<S> {
+    fn clone(&self) -> Self {
+        Self {
+            verification: self.verification.clone(),
+            roles: self.roles.clone(),
+        }
+    }
+}
+
+impl AuthLayer<HttpJwks> {
+    /// Verifies tokens by asking smbCloud Auth in `env` (`get_userinfo`).
+    /// Every request costs a round trip, but a revoked token is refused at
+    /// once.
+    pub fn userinfo(env: Environment) -> Self {
+        Self::from_verification(Verification::UserInfo(env))
+    }
+}
+
+impl<S: JwksSource> AuthLayer<S> {
+    /// Verifies tokens locally with `verifier`.
+    pub fn offline(verifier: JwtVerifier<S>) -> Self {
+        Self::from_verification(Verification::Offline(verifier))
+    }
+
+    fn from_verification(verification: Verification<S>) -> Self {
+        Self {
+            verification: Arc::new(verification),
+            roles: Arc::new([]),
+        }
+    }
+
+    /// Lets through only users whose `UserInfo.role` is `role`. Call again to
+    /// accept more roles. A token without the `profile` scope carries no role
+    /// and is refused.
+    pub fn require_role(mut self, role: i32) -> Self {
+        self.roles = self.roles.iter().copied().chain([role]).collect();
+        self
+    }
+}
+
+impl<S, Inner> Layer<Inner> for AuthLayer<S> {
+    type Service = AuthService<S, Inner>;
+
+    fn layer(&self, inner: Inner) -> Self::Service {
+        AuthService {
+            layer: self.clone(),
+            inner,
+        }
+    }
+}
+
+pub struct AuthService<S, Inner> {
+    layer: AuthLayer<S>,
+    inner: Inner,
+}
+
+impl<S, Inner: Clone> Clone for AuthService<S, Inner> {
+    fn clone(&self) -> Self {
+        Self {
+            layer: self.layer.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S, Inner, ReqBody, ResBody> Service<Request<ReqBody>> for AuthService<S, Inner>
+where
+    S: JwksSource + 'static,
+    Inner: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    Inner::Future: Send,
+    ReqBody: Send + 'static,
+    ResBody: Default,
+{
+    type Response = Response<ResBody>;
+    type Error = Inner::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        // The clone isn't ready yet; keep the one that is for this request.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+        // Owned, so the future doesn't borrow a body that may not be `Sync`.
+        let token = bearer_token(request.headers()).map(str::to_string);
+        let tenant_id = request
+            .headers()
+            .get(TENANT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        Box::pin(async move {
+            match layer.authenticate(token, tenant_id).await {
+                Ok(user) => {
+                    request.extensions_mut().insert(user);
+                    inner.call(request).await
+                }
+                Err(status) => Ok(refuse(status)),
+            }
+        })
+    }
+}
+
+impl<S: JwksSource> AuthLayer<S> {
+    async fn authenticate(
+        &self,
+        token: Option<String>,
+        tenant_id: Option<String>,
+    ) -> Result<AuthenticatedUser, StatusCode> {
+        let token = token.ok_or(StatusCode::UNAUTHORIZED)?;
+
+        let (user, tenant_id, claims) = match self.verification.as_ref() {
+            Verification::Offline(verifier) => {
+                let claims = verifier.verify(&token).await.map_err(|e| status_for(&e))?;
+                (claims.user_info(), claims.tenant_id.clone(), Some(claims))
+            }
+            Verification::UserInfo(env) => {
+                let user = get_userinfo(*env, &token, tenant_id.as_deref())
+                    .await
+                    .map_err(|e| status_for(&e))?;
+                (user, tenant_id, None)
+            }
+        };
+
+        if !self.roles.is_empty() && !user.role.is_some_and(|role| self.roles.contains(&role)) {
+            return Err(StatusCode::FORBIDDEN);
+        }
+        Ok(AuthenticatedUser {
+            user,
+            tenant_id,
+            claims,
+        })
+    }
+}
+
+fn bearer_token(headers: &http::HeaderMap) -> Option<&str> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+/// A token the issuer rejects is the client's problem; failing to reach the
+/// issuer is not.
+fn status_for(error: &ErrorResponse) -> StatusCode {
+    match error {
+        ErrorResponse::Error {
+            error_code: ErrorCode::Unauthorized | ErrorCode::ParseError | ErrorCode::InvalidParams,
+            ..
+        } => StatusCode::UNAUTHORIZED,
+        ErrorResponse::Error {
+            error_code: ErrorCode::Forbidden,
+            ..
+        } => StatusCode::FORBIDDEN,
+        _ => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+fn refuse<B: Default>(status: StatusCode) -> Response<B> {
+    let mut response = Response::new(B::default());
+    *response.status_mut() = status;
+    if status == StatusCode::UNAUTHORIZED {
+        response
+            .headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::jwt::{Jwk, Jwks, StaticJwks},
+        base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD},
+        ring::{
+            rand::SystemRandom,
+            signature::{Ed25519KeyPair, KeyPair},
+        },
+        serde_json::json,
+        std::convert::Infallible,
+        tower::{ServiceExt, service_fn},
+    };
+
+    const ISSUER: &str = "https://api.smbcloud.xyz";
+
+    struct Issuer {
+        pair: Ed25519KeyPair,
+    }
+
+    impl Issuer {
+        fn new() -> Self {
+            let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+            Self {
+                pair: Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap(),
+            }
+        }
+
+        fn verifier(&self) -> JwtVerifier<StaticJwks> {
+            let jwk = Jwk {
+                kty: "OKP".to_string(),
+                kid: None,
+                alg: Some("EdDSA".to_string()),
+                n: None,
+                e: None,
+                crv: Some("Ed25519".to_string()),
+                x: Some(URL_SAFE_NO_PAD.encode(self.pair.public_key().as_ref())),
+                y: None,
+            };
+            JwtVerifier::with_source(StaticJwks(Jwks { keys: vec![jwk] }), ISSUER, "my-app")
+        }
+
+        fn token(&self, role: i32) -> String {
+            self.token_with(json!({ "role": role }))
+        }
+
+        /// A token with `extra` merged into the standard claims.
+        fn token_with(&self, extra: serde_json::Value) -> String {
+            let mut claims = json!({
+                "iss": ISSUER,
+                "sub": "42",
+                "aud": "my-app",
+                "exp": i64::MAX / 2,
+            });
+            for (name, value) in extra.as_object().unwrap() {
+                claims[name] = value.clone();
+            }
+            let signed = format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(json!({"alg": "EdDSA"}).to_string()),
+                URL_SAFE_NO_PAD.encode(claims.to_string())
+            );
+            let signature = self.pair.sign(signed.as_bytes());
+            format!("{signed}.{}", URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        }
+    }
+
+    /// Echoes who the middleware said the caller is.
+    async fn whoami(request: Request<String>) -> Result<Response<String>, Infallible> {
+        let user = request.extensions().get::<AuthenticatedUser>().unwrap();
+        Ok(Response::new(format!(
+            "{} {}",
+            user.user.sub,
+            user.tenant_id.as_deref().unwrap_or("-")
+        )))
+    }
+
+    fn request(authorization: Option<String>) -> Request<String> {
+        let mut builder = Request::builder().header(TENANT_HEADER, "7");
+        if let Some(authorization) = authorization {
+            builder = builder.header(header::AUTHORIZATION, authorization);
+        }
+        builder.body(String::new()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn lets_verified_users_through_with_their_tenant() {
+        let issuer = Issuer::new();
+        let layer = AuthLayer::offline(issuer.verifier());
+        let body = |token: String| {
+            let service = layer.layer(service_fn(whoami));
+            async move {
+                let response = service
+                    .oneshot(request(Some(format!("Bearer {token}"))))
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                response.into_body()
+            }
+        };
+
+        // The tenant comes from the signed claim, not the header (`7`).
+        assert_eq!(
+            body(issuer.token_with(json!({ "tenant_id": 9 }))).await,
+            "42 9"
+        );
+        assert_eq!(body(issuer.token(1)).await, "42 -");
+    }
+
+    #[tokio::test]
+    async fn refuses_missing_or_forged_tokens() {
+        let issuer = Issuer::new();
+        let layer = AuthLayer::offline(issuer.verifier());
+        let forged = format!("Bearer {}", Issuer::new().token(1));
+
+        for authorization in [None, Some("Basic abc".to_string()), Some(forged)] {
+            let response = layer
+                .layer(service_fn(whoami))
+                .oneshot(request(authorization))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+        }
+    }
+
+    #[tokio::test]
+    async fn enforces_required_roles() {
+        let issuer = Issuer::new();
+        let layer = AuthLayer::offline(issuer.verifier())
+            .require_role(2)
+            .require_role(3);
+
+        let status = |role| {
+            let service = layer.layer(service_fn(whoami));
+            let token = issuer.token(role);
+            async move {
+                service
+                    .oneshot(request(Some(format!("Bearer {token}"))))
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+        assert_eq!(status(1).await, StatusCode::FORBIDDEN);
+        assert_eq!(status(3).await, StatusCode::OK);
+    }
+}
@@ -1,10 +1,14 @@
 use {
     base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD},
-    reqwest::{Client, Url},
+    reqwest::Url,
     serde::{Deserialize, Serialize},
     sha2::{Digest, Sha256},
     smbcloud_model::error_codes::{ErrorCode, ErrorResponse},
-    smbcloud_network::{environment::Environment, network},
+    smbcloud_network::{
+        api_client::{TENANT_HEADER, shared_client},
+        environment::Environment,
+        network,
+    },
     uuid::Uuid,
 };
 
@@ -24,6 +28,10 @@ pub struct AuthorizationRequest {
 pub struct CallbackPayload {
     pub code: String,
     pub state: String,
+    /// The tenant picked on the consent screen, when the user belongs to
+    /// more than one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +44,45 @@ pub struct TokenResponse {
     pub id_token: Option<String>,
 }
 
+/// A [`TokenResponse`] pinned to the clock it arrived by: what a client keeps
+/// to renew the session before `access_token` runs out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcSession {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Unix timestamp (seconds). `None` when the issuer sent no `expires_in`.
+    pub expires_at: Option<i64>,
+}
+
+impl OidcSession {
+    /// `now` is a Unix timestamp (seconds).
+    pub fn from_token_response(token: TokenResponse, now: i64) -> Self {
+        Self {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|expires_in| now + i64::from(expires_in)),
+        }
+    }
+
+    /// Whether the access token expires within `leeway` seconds of `now`.
+    pub fn expires_within(&self, now: i64, leeway: i64) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at - leeway <= now)
+    }
+
+    /// The session after a refresh. The issuer may or may not rotate the
+    /// refresh token; when it sends none, the current one stays valid.
+    pub fn renewed(self, token: TokenResponse, now: i64) -> Self {
+        let refresh_token = token.refresh_token.clone().or(self.refresh_token);
+        Self {
+            refresh_token,
+            ..Self::from_token_response(token, now)
+        }
+    }
+}
+
 /// OIDC userinfo claims returned by `GET /v1/client/oauth/userinfo`.
 ///
 /// Only `sub` is always present; every other claim is gated by the scopes the
@@ -101,17 +148,35 @@ pub fn parse_callback_url(callback_url: &str) -> Result<CallbackPayload, ErrorRe
 
     let mut code = None;
     let mut state = None;
+    let mut tenant_id = None;
+    let mut error = None;
+    let mut error_description = None;
 
     for (key, value) in url.query_pairs() {
         match key.as_ref() {
             "code" => code = Some(value.into_owned()),
             "state" => state = Some(value.into_owned()),
+            "tenant_id" => tenant_id = Some(value.into_owned()),
+            "error" => error = Some(value.into_owned()),
+            "error_description" => error_description = Some(value.into_owned()),
             _ => {}
         }
     }
 
+    // The user declined on the consent screen, or the request was invalid.
+    if let Some(error) = error {
+        return Err(ErrorResponse::Error {
+            error_code: ErrorCode::InvalidParams,
+            message: error_description.unwrap_or(error),
+        });
+    }
+
     match (code, state) {
-        (Some(code), Some(state)) => Ok(CallbackPayload { code, state }),
+        (Some(code), Some(state)) => Ok(CallbackPayload {
+            code,
+            state,
+            tenant_id,
+        }),
         _ => Err(ErrorResponse::Error {
             error_code: ErrorCode::InvalidParams,
             message: "Missing authorization code or state.".to_string(),
@@ -133,7 +198,7 @@ pub async fn exchange_code(
             message: err.to_string(),
         })?;
 
-    let builder = Client::new()
+    let builder = shared_client()
         .post(url)
         .form(&[
             ("grant_type", "authorization_code"),
@@ -147,6 +212,33 @@ pub async fn exchange_code(
     network::request(builder).await
 }
 
+/// Trades a refresh token for a new access token (the `refresh_token`
+/// grant). Fails with `Unauthorized` once the refresh token was revoked or
+/// expired; the user has to sign in again then.
+pub async fn refresh_token(
+    env: Environment,
+    oidc_client_id: &str,
+    refresh_token: &str,
+) -> Result<TokenResponse, ErrorResponse> {
+    let url = issuer_base_url(env)
+        .join(TOKEN_PATH)
+        .map_err(|err| ErrorResponse::Error {
+            error_code: ErrorCode::ParseError,
+            message: err.to_string(),
+        })?;
+
+    let builder = shared_client()
+        .post(url)
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("client_id", oidc_client_id),
+            ("refresh_token", refresh_token),
+        ])
+        .header("Accept", "application/json");
+
+    network::request(builder).await
+}
+
 pub async fn get_userinfo(
     env: Environment,
     access_token: &str,
@@ -159,19 +251,18 @@ pub async fn get_userinfo(
             message: err.to_string(),
         })?;
 
-    let mut builder = Client::new()
+    let mut builder = shared_client()
         .get(url)
         .bearer_auth(access_token)
         .header("Accept", "application/json");
 
     if let Some(tenant_id) = tenant_id {
-        builder = builder.header("X-Smbcloud-Tenant-Id", tenant_id);
+        builder = builder.header(TENANT_HEADER, tenant_id);
     }
 
     network::request(builder).await
 }
 
 fn issuer_base_url(env: Environment) -> Url {
-    Url::parse(&format!("{}://{}/", env.api_protocol(), env.api_host()))
-        .expect("valid smbcloud api base url")
+    Url::parse(&format!("{}/", env.api_base_url())).expect("valid smbcloud api base url")
 }
@@ -1,8 +1,7 @@
 use {
     crate::client_credentials::{ClientCredentials, base_url_builder as tenant_base_url_builder},
-    reqwest::Client,
     smbcloud_model::error_codes::ErrorResponse,
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
 };
 
 pub async fn remove_with_client(
@@ -10,7 +9,7 @@ pub async fn remove_with_client(
     client: ClientCredentials<'_>,
     access_token: &str,
 ) -> Result<(), ErrorResponse> {
-    let builder = Client::new()
+    let builder = shared_client()
         .delete(build_remove_url(env, client))
         .header("Authorization", access_token)
         .header("User-agent", client.app_id);
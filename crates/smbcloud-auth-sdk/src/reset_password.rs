@@ -1,12 +1,11 @@
 use {
     crate::client_credentials::{ClientCredentials, base_url_builder as tenant_base_url_builder},
-    reqwest::Client,
     smbcloud_model::{
         error_codes::ErrorResponse,
         forgot::{Args, Email},
         reset_password_response::ResetPasswordResponse,
     },
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
 };
 
 /// Requests password-reset instructions for a tenant `AuthUser`.
@@ -24,7 +23,7 @@ pub async fn reset_password_with_client(
     let params = Args {
         user: Email { email },
     };
-    let builder = Client::new()
+    let builder = shared_client()
         .post(build_reset_password_url(env, client))
         .json(&params)
         .header("User-agent", client.app_id);
@@ -1,11 +1,10 @@
 use {
     crate::client_credentials::{ClientCredentials, base_url_builder as tenant_base_url_builder},
-    reqwest::Client,
     smbcloud_model::{
         error_codes::ErrorResponse,
         signup::{SignupEmailParams, SignupResult, SignupUserEmail},
     },
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
 };
 
 pub async fn signup_with_client(
@@ -17,7 +16,7 @@ pub async fn signup_with_client(
     let params = SignupEmailParams {
         user: SignupUserEmail { email, password },
     };
-    let builder = Client::new()
+    let builder = shared_client()
         .post(build_signup_url(env, client))
         .json(&params)
         .header("User-agent", client.app_id);
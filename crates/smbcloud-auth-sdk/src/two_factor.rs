@@ -0,0 +1,144 @@
+use {
+    crate::client_credentials::{ClientCredentials, base_url_builder as tenant_base_url_builder},
+    smbcloud_model::{
+        error_codes::ErrorResponse,
+        two_factor::{OtpParams, RecoveryCodes, TwoFactorSetup},
+    },
+    smbcloud_network::{
+        api_client::shared_client,
+        environment::Environment,
+        network::{request, request_empty},
+    },
+};
+
+/// Starts TOTP enrolment for the signed-in user. Show
+/// [`TwoFactorSetup::provisioning_uri`] as a QR code, then confirm with
+/// [`enable_two_factor_with_client`].
+pub async fn setup_two_factor_with_client(
+    env: Environment,
+    client: ClientCredentials<'_>,
+    access_token: &str,
+) -> Result<TwoFactorSetup, ErrorResponse> {
+    let builder = shared_client()
+        .post(build_two_factor_url(env, client, None))
+        .header("Authorization", access_token)
+        .header("User-agent", client.app_id);
+    request(builder).await
+}
+
+/// Turns two-factor authentication on with a code from the pending secret.
+/// The recovery codes are only in this response.
+pub async fn enable_two_factor_with_client(
+    env: Environment,
+    client: ClientCredentials<'_>,
+    access_token: &str,
+    otp: String,
+) -> Result<RecoveryCodes, ErrorResponse> {
+    let builder = shared_client()
+        .put(build_two_factor_url(env, client, None))
+        .json(&OtpParams { otp_attempt: otp })
+        .header("Authorization", access_token)
+        .header("User-agent", client.app_id);
+    request(builder).await
+}
+
+/// Turns two-factor authentication off. Needs a current TOTP or recovery code.
+pub async fn disable_two_factor_with_client(
+    env: Environment,
+    client: ClientCredentials<'_>,
+    access_token: &str,
+    otp: String,
+) -> Result<(), ErrorResponse> {
+    let builder = shared_client()
+        .delete(build_two_factor_url(env, client, None))
+        .json(&OtpParams { otp_attempt: otp })
+        .header("Authorization", access_token)
+        .header("User-agent", client.app_id);
+    request_empty(builder).await
+}
+
+/// Replaces all of the user's recovery codes with new ones.
+pub async fn regenerate_recovery_codes_with_client(
+    env: Environment,
+    client: ClientCredentials<'_>,
+    access_token: &str,
+    otp: String,
+) -> Result<RecoveryCodes, ErrorResponse> {
+    let builder = shared_client()
+        .post(build_two_factor_url(env, client, Some("recovery_codes")))
+        .json(&OtpParams { otp_attempt: otp })
+        .header("Authorization", access_token)
+        .header("User-agent", client.app_id);
+    request(builder).await
+}
+
+fn build_two_factor_url(
+    env: Environment,
+    client: ClientCredentials<'_>,
+    path: Option<&str>,
+) -> String {
+    let mut url_builder = tenant_base_url_builder(env, client);
+    url_builder.add_route("v1/client/me/two_factor");
+    if let Some(path) = path {
+        url_builder.add_route(path);
+    }
+    url_builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        smbcloud_mock_server::{MOCK_OTP, MockServer},
+        smbcloud_model::error_codes::ErrorCode,
+    };
+
+    const CLIENT: ClientCredentials<'static> = ClientCredentials {
+        app_id: "app",
+        app_secret: "secret",
+    };
+
+    #[tokio::test]
+    async fn two_factor_turns_on_with_a_code_and_off_with_a_recovery_code() {
+        let server = MockServer::shared();
+        let env = Environment::Custom;
+        let token = "sdk-two-factor-token";
+        server
+            .state()
+            .add_account("sdk-two-factor@smbcloud.xyz", "password", token);
+
+        let setup = setup_two_factor_with_client(env, CLIENT, token)
+            .await
+            .unwrap();
+        assert!(setup.provisioning_uri.contains(&setup.secret));
+        assert!(matches!(
+            regenerate_recovery_codes_with_client(env, CLIENT, token, MOCK_OTP.to_string()).await,
+            Err(ErrorResponse::Error {
+                error_code: ErrorCode::InvalidParams,
+                ..
+            })
+        ));
+        let codes = enable_two_factor_with_client(env, CLIENT, token, MOCK_OTP.to_string())
+            .await
+            .unwrap();
+        let codes = regenerate_recovery_codes_with_client(
+            env,
+            CLIENT,
+            token,
+            codes.recovery_codes[0].clone(),
+        )
+        .await
+        .unwrap();
+
+        disable_two_factor_with_client(env, CLIENT, token, codes.recovery_codes[0].clone())
+            .await
+            .unwrap();
+        assert!(
+            !server
+                .state()
+                .accounts
+                .iter()
+                .any(|account| { account.access_token == token && account.two_factor.enabled })
+        );
+    }
+}
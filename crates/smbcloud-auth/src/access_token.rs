@@ -0,0 +1,69 @@
+use {
+    serde::Serialize,
+    smbcloud_model::{
+        access_token::{AccessToken, AccessTokenCreate, CreatedAccessToken},
+        error_codes::ErrorResponse,
+    },
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::{
+        api_client, constants::PATH_ACCESS_TOKENS, smb_base_url_builder, smb_client::SmbClient,
+    },
+};
+
+#[derive(Serialize)]
+struct AccessTokenEnvelope {
+    access_token: AccessTokenCreate,
+}
+
+/// Creates a personal access token. The secret is only in this response.
+/// Needs a login session; access tokens can't create other tokens.
+pub async fn create_access_token(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    params: AccessTokenCreate,
+) -> Result<CreatedAccessToken, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .post(build_access_tokens_url(env, client, None))
+        .json(&AccessTokenEnvelope {
+            access_token: params,
+        });
+    api.send(builder).await
+}
+
+pub async fn get_access_tokens(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+) -> Result<Vec<AccessToken>, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.get(build_access_tokens_url(env, client, None));
+    api.send(builder).await
+}
+
+/// Revokes a token right away. Requests already using it start failing with
+/// `401 Unauthorized`.
+pub async fn revoke_access_token(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    id: i64,
+) -> Result<(), ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.delete(build_access_tokens_url(env, client, Some(id)));
+    api.send_empty(builder).await
+}
+
+fn build_access_tokens_url(
+    env: Environment,
+    client: (&SmbClient, &str),
+    id: Option<i64>,
+) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route(PATH_ACCESS_TOKENS);
+    if let Some(id) = id {
+        url_builder.add_route(&id.to_string());
+    }
+    url_builder.build()
+}
@@ -1,7 +1,6 @@
 use {
-    reqwest::Client,
     smbcloud_model::{account::SmbAuthorization, error_codes::ErrorResponse},
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
     smbcloud_networking::{
         constants::PATH_USERS_CHECK_EMAIL, smb_base_url_builder, smb_client::SmbClient,
     },
@@ -12,7 +11,7 @@ pub async fn check_email(
     client: (&SmbClient, &str),
     email: &str,
 ) -> Result<SmbAuthorization, ErrorResponse> {
-    let builder = Client::new()
+    let builder = shared_client()
         .get(build_url(env, client, email))
         .header("Accept", "application/json")
         .header("Content-Type", "application/x-www-form-urlencoded");
@@ -0,0 +1,257 @@
+//! Device-code login (RFC 8628) for machines without a browser: start it with
+//! [`request_device_code`], show the user code and URL, then wait in
+//! [`poll_device_token`] while the user approves on another device.
+
+use {
+    log::debug,
+    reqwest::StatusCode,
+    smbcloud_model::{
+        device_authorization::{
+            DEFAULT_POLL_INTERVAL, DeviceAuthorization, DeviceToken, DeviceTokenError,
+            DeviceTokenErrorCode, DeviceTokenRequest,
+        },
+        error_codes::{ErrorCode, ErrorResponse},
+    },
+    smbcloud_network::{
+        api_client::shared_client, environment::Environment, network::parse_error_response,
+    },
+    smbcloud_networking::{
+        constants::{PATH_DEVICE_CODE, PATH_DEVICE_TOKEN},
+        smb_base_url_builder,
+        smb_client::SmbClient,
+    },
+    std::time::Duration,
+    tokio::time::{Instant, sleep},
+};
+
+/// How much longer to wait between polls after each `slow_down`.
+const SLOW_DOWN_STEP: Duration = Duration::from_secs(5);
+
+/// One answer from the token endpoint.
+#[derive(Debug)]
+enum Poll {
+    Granted(DeviceToken),
+    Pending,
+    SlowDown,
+    /// The request never got an answer. Worth another try while the code
+    /// is still valid.
+    Unreachable,
+}
+
+/// Starts a device login.
+pub async fn request_device_code(
+    env: Environment,
+    client: (&SmbClient, &str),
+) -> Result<DeviceAuthorization, ErrorResponse> {
+    request_device_code_at(&build_url(env, client, PATH_DEVICE_CODE)).await
+}
+
+/// Polls until the user approves, denies or lets the code expire, honouring
+/// the server's interval (never under the RFC's 5 seconds) and `slow_down`.
+/// Network errors are retried until the code expires. The returned token is
+/// stored like the one from a password login.
+pub async fn poll_device_token(
+    env: Environment,
+    client: (&SmbClient, &str),
+    authorization: &DeviceAuthorization,
+) -> Result<DeviceToken, ErrorResponse> {
+    poll_device_token_at(
+        &build_url(env, client, PATH_DEVICE_TOKEN),
+        client.0.id(),
+        authorization,
+    )
+    .await
+}
+
+async fn request_device_code_at(url: &str) -> Result<DeviceAuthorization, ErrorResponse> {
+    let builder = shared_client()
+        .post(url)
+        .header("Accept", "application/json");
+    smbcloud_network::network::request(builder).await
+}
+
+async fn poll_device_token_at(
+    url: &str,
+    client_id: &str,
+    authorization: &DeviceAuthorization,
+) -> Result<DeviceToken, ErrorResponse> {
+    let deadline = Instant::now() + Duration::from_secs(authorization.expires_in);
+    let mut interval = Duration::from_secs(authorization.interval.max(DEFAULT_POLL_INTERVAL));
+    loop {
+        sleep(interval).await;
+        if Instant::now() >= deadline {
+            return Err(error(ErrorCode::DeviceCodeExpired));
+        }
+        match poll_once(url, client_id, &authorization.device_code).await? {
+            Poll::Granted(token) => return Ok(token),
+            Poll::Pending => {}
+            Poll::SlowDown => {
+                interval += SLOW_DOWN_STEP;
+                debug!("Device login: slowing down to {interval:?} between polls.");
+            }
+            Poll::Unreachable => debug!("Device login: poll failed, trying again."),
+        }
+    }
+}
+
+async fn poll_once(url: &str, client_id: &str, device_code: &str) -> Result<Poll, ErrorResponse> {
+    let response = match shared_client()
+        .post(url)
+        .header("Accept", "application/json")
+        .form(&DeviceTokenRequest::new(device_code, client_id))
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            debug!("Device login poll failed: {e}");
+            return Ok(Poll::Unreachable);
+        }
+    };
+
+    match response.status() {
+        StatusCode::OK | StatusCode::CREATED => response
+            .json()
+            .await
+            .map(Poll::Granted)
+            .map_err(|_| error(ErrorCode::ParseError)),
+        StatusCode::BAD_REQUEST => {
+            let body = response.text().await.unwrap_or_default();
+            let device_error: DeviceTokenError =
+                serde_json::from_str(&body).map_err(|_| error(ErrorCode::ParseError))?;
+            match device_error.error {
+                DeviceTokenErrorCode::AuthorizationPending => Ok(Poll::Pending),
+                DeviceTokenErrorCode::SlowDown => Ok(Poll::SlowDown),
+                DeviceTokenErrorCode::ExpiredToken => Err(error(ErrorCode::DeviceCodeExpired)),
+                DeviceTokenErrorCode::AccessDenied => Err(error(ErrorCode::DeviceAccessDenied)),
+                DeviceTokenErrorCode::Unknown => Err(ErrorResponse::Error {
+                    error_code: ErrorCode::Unknown,
+                    message: device_error
+                        .error_description
+                        .unwrap_or_else(|| ErrorCode::Unknown.message(None).to_string()),
+                }),
+            }
+        }
+        _ => Err(parse_error_response::<ErrorResponse>(response)
+            .await
+            .unwrap_or_else(|e| e)),
+    }
+}
+
+fn error(error_code: ErrorCode) -> ErrorResponse {
+    ErrorResponse::Error {
+        message: error_code.message(None).to_string(),
+        error_code,
+    }
+}
+
+fn build_url(env: Environment, client: (&SmbClient, &str), path: &str) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route(path);
+    url_builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        smbcloud_mock_server::{MOCK_ACCESS_TOKEN, MockServer},
+    };
+
+    const CLIENT_ID: &str = "cli";
+
+    async fn start(server: &MockServer) -> DeviceAuthorization {
+        server.state().device_poll_interval = 0;
+        request_device_code_at(&format!("{}/{PATH_DEVICE_CODE}", server.url()))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polls_until_the_user_approves() {
+        let server = MockServer::start().await.unwrap();
+        let authorization = start(&server).await;
+        let token_url = format!("{}/{PATH_DEVICE_TOKEN}", server.url());
+
+        assert!(matches!(
+            poll_once(&token_url, CLIENT_ID, &authorization.device_code).await,
+            Ok(Poll::Pending)
+        ));
+        assert!(server.state().approve_device(&authorization.user_code));
+
+        let started = Instant::now();
+        let token = poll_device_token_at(&token_url, CLIENT_ID, &authorization)
+            .await
+            .unwrap();
+        assert_eq!(token.authorization(), format!("Bearer {MOCK_ACCESS_TOKEN}"));
+        // The mock asked for no interval at all.
+        assert!(started.elapsed() >= Duration::from_secs(DEFAULT_POLL_INTERVAL));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keeps_polling_through_network_errors_until_the_code_expires() {
+        let unreachable = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let authorization = DeviceAuthorization {
+            device_code: "device-code".to_string(),
+            user_code: "USER-CODE".to_string(),
+            verification_uri: "https://smbcloud.xyz/device".to_string(),
+            verification_uri_complete: None,
+            expires_in: 30,
+            interval: 5,
+        };
+
+        let result = poll_device_token_at(
+            &format!("http://{unreachable}/{PATH_DEVICE_TOKEN}"),
+            CLIENT_ID,
+            &authorization,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ErrorResponse::Error {
+                error_code: ErrorCode::DeviceCodeExpired,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_when_the_user_denies() {
+        let server = MockServer::start().await.unwrap();
+        let authorization = start(&server).await;
+        server.state().deny_device(&authorization.user_code);
+
+        let result = poll_device_token_at(
+            &format!("{}/{PATH_DEVICE_TOKEN}", server.url()),
+            CLIENT_ID,
+            &authorization,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ErrorResponse::Error {
+                error_code: ErrorCode::DeviceAccessDenied,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn polling_too_fast_gets_slow_down() {
+        let server = MockServer::start().await.unwrap();
+        let authorization = start(&server).await;
+        server.state().device_poll_interval = 60;
+        let token_url = format!("{}/{PATH_DEVICE_TOKEN}", server.url());
+
+        poll_once(&token_url, CLIENT_ID, &authorization.device_code)
+            .await
+            .unwrap();
+        assert!(matches!(
+            poll_once(&token_url, CLIENT_ID, &authorization.device_code).await,
+            Ok(Poll::SlowDown)
+        ));
+    }
+}
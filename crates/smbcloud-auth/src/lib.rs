@@ -1,4 +1,6 @@
+pub mod access_token;
 pub mod check_email;
+pub mod device_authorization;
 pub mod login;
 pub mod logout;
 pub mod me;
@@ -8,3 +10,5 @@ pub mod resend_email_verification;
 pub mod resend_reset_password_instruction;
 pub mod reset_password;
 pub mod signup;
+pub mod two_factor;
+pub mod update_account;
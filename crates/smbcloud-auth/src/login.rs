@@ -1,10 +1,11 @@
 use {
-    reqwest::Client,
     smbcloud_model::{
         error_codes::ErrorResponse,
         login::{AccountStatus, LoginParams, UserParam},
     },
-    smbcloud_network::{environment::Environment, network::request_login},
+    smbcloud_network::{
+        api_client::shared_client, environment::Environment, network::request_login,
+    },
     smbcloud_networking::{
         constants::{PATH_USERS_SIGN_IN, SMB_USER_AGENT},
         smb_client::SmbClient,
@@ -17,14 +18,28 @@ pub async fn login(
     client: (&SmbClient, &str),
     username: String,
     password: String,
+) -> Result<AccountStatus, ErrorResponse> {
+    login_with_otp(env, client, username, password, None).await
+}
+
+/// Like [`login`], with a TOTP or recovery code for accounts that have
+/// two-factor authentication on. Without one, such an account resolves to
+/// [`AccountStatus::OtpRequired`].
+pub async fn login_with_otp(
+    env: Environment,
+    client: (&SmbClient, &str),
+    username: String,
+    password: String,
+    otp: Option<String>,
 ) -> Result<AccountStatus, ErrorResponse> {
     let login_params = LoginParams {
         user: UserParam {
             email: username,
             password,
+            otp_attempt: otp,
         },
     };
-    let builder = Client::new()
+    let builder = shared_client()
         .post(build_smb_login_url(env, client))
         .json(&login_params)
         .header("User-agent", SMB_USER_AGENT);
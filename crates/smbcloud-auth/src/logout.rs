@@ -1,7 +1,7 @@
 use {
-    reqwest::{Client, StatusCode},
+    reqwest::StatusCode,
     smbcloud_model::error_codes::{ErrorCode, ErrorResponse},
-    smbcloud_network::environment::Environment,
+    smbcloud_network::{api_client::shared_client, environment::Environment},
     smbcloud_networking::{
         constants::PATH_USERS_SIGN_OUT, smb_base_url_builder, smb_client::SmbClient,
     },
@@ -12,7 +12,7 @@ pub async fn logout(
     client: (&SmbClient, &str),
     access_token: String,
 ) -> Result<(), ErrorResponse> {
-    let response = match Client::new()
+    let response = match shared_client()
         .delete(build_smb_logout_url(env, client))
         .header("Authorization", access_token)
         .header("Accept", "application/json")
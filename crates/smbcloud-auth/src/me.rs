@@ -1,7 +1,6 @@
 use {
-    reqwest::Client,
     smbcloud_model::{account::User, error_codes::ErrorResponse},
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
     smbcloud_networking::{constants::PATH_USERS_ME, smb_base_url_builder, smb_client::SmbClient},
 };
 
@@ -10,7 +9,7 @@ pub async fn me(
     client: (&SmbClient, &str),
     access_token: &str,
 ) -> Result<User, ErrorResponse> {
-    let builder = Client::new()
+    let builder = shared_client()
         .get(build_smb_info_url(env, client))
         .header("Authorization", access_token)
         .header("Accept", "application/json")
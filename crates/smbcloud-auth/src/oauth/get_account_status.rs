@@ -1,7 +1,8 @@
 use {
-    reqwest::Client,
     smbcloud_model::{error_codes::ErrorResponse, login::AccountStatus},
-    smbcloud_network::{environment::Environment, network::request_login},
+    smbcloud_network::{
+        api_client::shared_client, environment::Environment, network::request_login,
+    },
     smbcloud_networking::{
         constants::PATH_ACCOUNT_STATUS, smb_base_url_builder, smb_client::SmbClient,
     },
@@ -12,7 +13,7 @@ pub async fn get_account_status(
     client: (&SmbClient, &str),
     email: &str,
 ) -> Result<AccountStatus, ErrorResponse> {
-    let builder = Client::new()
+    let builder = shared_client()
         .get(build_url(env, client, email))
         .header("Accept", "application/json")
         .header("Content-Type", "application/x-www-form-urlencoded");
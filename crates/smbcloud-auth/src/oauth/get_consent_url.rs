@@ -5,10 +5,15 @@ pub fn get_consent_url(provider: Provider) -> String {
         Provider::Google {
             client_id,
             redirect_uri,
-        } => format!(
-            "https://accounts.google.com/o/oauth2/auth?client_id={}&redirect_uri={}&scope=email%20profile&response_type=code&access_type=offline",
-            client_id, redirect_uri
-        ),
+            state,
+        } => {
+            let state_param = state.unwrap_or_else(|| Uuid::new_v4().to_string());
+
+            format!(
+                "https://accounts.google.com/o/oauth2/auth?client_id={}&redirect_uri={}&scope=openid%20email%20profile&response_type=code&access_type=offline&state={}",
+                client_id, redirect_uri, state_param
+            )
+        }
         Provider::Apple {
             client_id,
             redirect_uri,
@@ -1,18 +1,18 @@
 use {
     log::debug,
-    reqwest::Client,
     serde_json::json,
     smbcloud_model::{
         error_codes::ErrorResponse,
         oauth::{OauthRedirect, TokenResponse},
     },
-    smbcloud_network::network::{self},
+    smbcloud_network::{api_client::shared_client, network},
 };
 
 pub async fn get_token(
     oauth_redirect: OauthRedirect,
     client_id: String,
     client_secret: String,
+    redirect_uri: &str,
 ) -> Result<TokenResponse, ErrorResponse> {
     let base_url = "https://oauth2.googleapis.com/token".to_string();
     debug!("Exchange code with token: {:?}", oauth_redirect.code);
@@ -21,8 +21,8 @@ pub async fn get_token(
         "client_secret": client_secret,
         "code": oauth_redirect.code,
         "grant_type": "authorization_code",
-        "redirect_uri": "http://localhost:8000"
+        "redirect_uri": redirect_uri
     });
-    let builder = Client::new().post(base_url).json(&paylod);
+    let builder = shared_client().post(base_url).json(&paylod);
     network::request(builder).await
 }
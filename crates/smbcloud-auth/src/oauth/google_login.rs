@@ -0,0 +1,97 @@
+//! smbCloud sessions from a Google sign-in. The ID token goes to the API,
+//! which checks it with Google before it answers for the account behind it.
+
+use {
+    smbcloud_model::{
+        error_codes::ErrorResponse, login::AccountStatus, oauth::GoogleAuthorization,
+    },
+    smbcloud_network::{
+        api_client::shared_client, environment::Environment, network::request_login,
+    },
+    smbcloud_networking::{
+        constants::{PATH_AUTHORIZE_GOOGLE, SMB_USER_AGENT},
+        smb_base_url_builder,
+        smb_client::SmbClient,
+    },
+};
+
+/// Logs in the account with the email Google verified in `id_token`. An
+/// account that doesn't exist yet is [`AccountStatus::NotFound`].
+pub async fn google_login(
+    env: Environment,
+    client: (&SmbClient, &str),
+    id_token: &str,
+) -> Result<AccountStatus, ErrorResponse> {
+    authorize(env, client, id_token, false).await
+}
+
+/// Like [`google_login`], creating the account first when there is none.
+pub async fn google_signup(
+    env: Environment,
+    client: (&SmbClient, &str),
+    id_token: &str,
+) -> Result<AccountStatus, ErrorResponse> {
+    authorize(env, client, id_token, true).await
+}
+
+async fn authorize(
+    env: Environment,
+    client: (&SmbClient, &str),
+    id_token: &str,
+    sign_up: bool,
+) -> Result<AccountStatus, ErrorResponse> {
+    let builder = shared_client()
+        .post(build_url(env, client))
+        .json(&GoogleAuthorization {
+            id_token: id_token.to_string(),
+            sign_up,
+        })
+        .header("User-agent", SMB_USER_AGENT);
+    request_login(builder).await
+}
+
+fn build_url(env: Environment, client: (&SmbClient, &str)) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route(PATH_AUTHORIZE_GOOGLE);
+    url_builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::me::me, smbcloud_mock_server::MockServer,
+        smbcloud_model::error_codes::ErrorCode,
+    };
+
+    const CLIENT: (&SmbClient, &str) = (&SmbClient::Cli, "secret");
+
+    #[tokio::test]
+    async fn google_id_token_logs_in_only_the_account_it_proves() {
+        let server = MockServer::shared();
+        let env = Environment::Custom;
+        let email = "google@smbcloud.xyz";
+        let id_token = server.state().add_google_id_token(email);
+
+        assert!(matches!(
+            google_login(env, CLIENT, &id_token).await,
+            Ok(AccountStatus::NotFound)
+        ));
+        let Ok(AccountStatus::Ready { access_token }) = google_signup(env, CLIENT, &id_token).await
+        else {
+            panic!("signing up should log the new account in");
+        };
+        assert_eq!(me(env, CLIENT, &access_token).await.unwrap().email, email);
+        assert!(matches!(
+            google_login(env, CLIENT, &id_token).await,
+            Ok(AccountStatus::Ready { .. })
+        ));
+
+        assert!(matches!(
+            google_signup(env, CLIENT, "forged-id-token").await,
+            Err(ErrorResponse::Error {
+                error_code: ErrorCode::Unauthorized,
+                ..
+            })
+        ));
+    }
+}
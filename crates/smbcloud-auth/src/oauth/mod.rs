@@ -2,4 +2,5 @@ pub mod get_account_status;
 pub mod get_consent_url;
 pub mod get_profile;
 pub mod get_token;
+pub mod google_login;
 pub mod provider;
@@ -2,6 +2,7 @@ pub enum Provider {
     Google {
         client_id: String,
         redirect_uri: String,
+        state: Option<String>,
     },
     Apple {
         client_id: String,
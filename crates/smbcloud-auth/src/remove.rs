@@ -1,20 +1,22 @@
 use {
-    reqwest::Client,
     smbcloud_model::error_codes::ErrorResponse,
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{
+        api_client::shared_client, environment::Environment, network::request_empty,
+    },
     smbcloud_networking::{constants::PATH_USERS, smb_base_url_builder, smb_client::SmbClient},
 };
 
+/// Deletes the signed-in account. The server replies `204 No Content`.
 pub async fn remove(
     env: Environment,
     client: (&SmbClient, &str),
     access_token: &str,
 ) -> Result<(), ErrorResponse> {
-    let builder = Client::new()
+    let builder = shared_client()
         .delete(build_smb_remove_url(env, client))
         .header("Authorization", access_token)
         .header("User-agent", client.0.id());
-    request(builder).await
+    request_empty(builder).await
 }
 
 fn build_smb_remove_url(env: Environment, client: (&SmbClient, &str)) -> String {
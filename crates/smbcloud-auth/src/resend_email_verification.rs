@@ -1,7 +1,6 @@
 use {
-    reqwest::Client,
     smbcloud_model::{account::SmbAuthorization, error_codes::ErrorResponse},
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
     smbcloud_networking::{
         constants::PATH_RESEND_CONFIRMATION, smb_base_url_builder, smb_client::SmbClient,
     },
@@ -12,7 +11,7 @@ pub async fn resend_email_verification(
     client: (&SmbClient, &str),
     email: String,
 ) -> Result<SmbAuthorization, ErrorResponse> {
-    let builder = Client::new()
+    let builder = shared_client()
         .post(build_smb_resend_email_verification_url(env, client))
         .body(format!("email={}", email))
         .header("User-agent", client.0.id())
@@ -1,7 +1,6 @@
 use {
-    reqwest::Client,
     smbcloud_model::{account::SmbAuthorization, error_codes::ErrorResponse},
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
     smbcloud_networking::{constants::PATH_RESET_PASSWORD_INSTRUCTIONS, smb_client::SmbClient},
     url_builder::URLBuilder,
 };
@@ -11,7 +10,7 @@ pub async fn resend_reset_password_instruction(
     client: (&SmbClient, &str),
     email: String,
 ) -> Result<SmbAuthorization, ErrorResponse> {
-    let builder = Client::new()
+    let builder = shared_client()
         .post(build_smb_resend_reset_password_instructions_url(
             env, client,
         ))
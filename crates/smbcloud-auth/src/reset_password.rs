@@ -1,11 +1,10 @@
 use {
-    reqwest::Client,
     smbcloud_model::{
         error_codes::ErrorResponse,
         forgot::{Param, UserUpdatePassword},
         reset_password_response::ResetPasswordResponse,
     },
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
     smbcloud_networking::{
         constants::PATH_USERS_PASSWORD, smb_base_url_builder, smb_client::SmbClient,
     },
@@ -26,7 +25,7 @@ pub async fn reset_password(
         },
     };
 
-    let builder = Client::new()
+    let builder = shared_client()
         .put(build_smb_reset_password_url(env, client))
         .json(&params)
         .header("Accept", "application/json")
@@ -1,10 +1,9 @@
 use {
-    reqwest::Client,
     smbcloud_model::{
         error_codes::ErrorResponse,
         signup::{SignupEmailParams, SignupResult, SignupUserEmail},
     },
-    smbcloud_network::{environment::Environment, network::request},
+    smbcloud_network::{api_client::shared_client, environment::Environment, network::request},
     smbcloud_networking::{constants::PATH_USERS, smb_base_url_builder, smb_client::SmbClient},
 };
 
@@ -17,7 +16,7 @@ pub async fn signup(
     let params = SignupEmailParams {
         user: SignupUserEmail { email, password },
     };
-    let builder = Client::new()
+    let builder = shared_client()
         .post(build_smb_signup_url(env, client))
         .json(&params)
         .header("User-agent", client.0.id());
@@ -29,3 +28,78 @@ fn build_smb_signup_url(env: Environment, client: (&SmbClient, &str)) -> String
     url_builder.add_route(PATH_USERS);
     url_builder.build()
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            check_email::check_email, login::login, me::me, remove::remove,
+            update_account::update_password,
+        },
+        smbcloud_mock_server::{MOCK_EMAIL, MockServer},
+        smbcloud_model::{account::ErrorCode as AccountErrorCode, login::AccountStatus},
+    };
+
+    const CLIENT: (&SmbClient, &str) = (&SmbClient::Cli, "secret");
+
+    #[tokio::test]
+    async fn account_lifecycle_against_the_mock() {
+        MockServer::shared();
+        let env = Environment::Custom;
+        let email = "new@smbcloud.xyz";
+
+        let unknown = check_email(env, CLIENT, email).await.unwrap();
+        assert_eq!(unknown.error_code, Some(AccountErrorCode::EmailNotFound));
+
+        let signed_up = signup(env, CLIENT, email.to_string(), "first".to_string())
+            .await
+            .unwrap();
+        assert_eq!(signed_up.data.unwrap().email, email);
+        assert!(
+            signup(env, CLIENT, MOCK_EMAIL.to_string(), "x".to_string())
+                .await
+                .is_err()
+        );
+        let known = check_email(env, CLIENT, email).await.unwrap();
+        assert!(known.error_code.is_none());
+
+        let Ok(AccountStatus::Ready { access_token }) =
+            login(env, CLIENT, email.to_string(), "first".to_string()).await
+        else {
+            panic!("the new account should log in");
+        };
+        assert_eq!(me(env, CLIENT, &access_token).await.unwrap().email, email);
+
+        update_password(
+            env,
+            CLIENT,
+            access_token.clone(),
+            "first".to_string(),
+            "second".to_string(),
+        )
+        .await
+        .unwrap();
+        assert!(
+            update_password(
+                env,
+                CLIENT,
+                access_token.clone(),
+                "first".to_string(),
+                "third".to_string(),
+            )
+            .await
+            .is_err()
+        );
+        assert!(matches!(
+            login(env, CLIENT, email.to_string(), "second".to_string()).await,
+            Ok(AccountStatus::Ready { .. })
+        ));
+
+        remove(env, CLIENT, &access_token).await.unwrap();
+        assert!(matches!(
+            login(env, CLIENT, email.to_string(), "second".to_string()).await,
+            Ok(AccountStatus::NotFound)
+        ));
+    }
+}
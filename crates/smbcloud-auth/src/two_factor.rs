@@ -0,0 +1,145 @@
+use {
+    smbcloud_model::{
+        error_codes::ErrorResponse,
+        two_factor::{OtpParams, RecoveryCodes, TwoFactorSetup},
+    },
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::{
+        api_client,
+        constants::{PATH_USERS_TWO_FACTOR, PATH_USERS_TWO_FACTOR_RECOVERY_CODES},
+        smb_base_url_builder,
+        smb_client::SmbClient,
+    },
+};
+
+/// Starts enrolment with a new TOTP secret. Calling it again replaces a
+/// secret that was never confirmed; an active one is left alone.
+pub async fn setup_two_factor(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+) -> Result<TwoFactorSetup, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.post(build_two_factor_url(env, client, PATH_USERS_TWO_FACTOR));
+    api.send(builder).await
+}
+
+/// Turns two-factor authentication on with a code from the secret of
+/// [`setup_two_factor`]. The recovery codes are only in this response.
+pub async fn enable_two_factor(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    otp: String,
+) -> Result<RecoveryCodes, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .put(build_two_factor_url(env, client, PATH_USERS_TWO_FACTOR))
+        .json(&OtpParams { otp_attempt: otp });
+    api.send(builder).await
+}
+
+/// Turns two-factor authentication off. Needs a current TOTP or recovery code.
+pub async fn disable_two_factor(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    otp: String,
+) -> Result<(), ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .delete(build_two_factor_url(env, client, PATH_USERS_TWO_FACTOR))
+        .json(&OtpParams { otp_attempt: otp });
+    api.send_empty(builder).await
+}
+
+/// Replaces all recovery codes, used or not, with new ones.
+pub async fn regenerate_recovery_codes(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    otp: String,
+) -> Result<RecoveryCodes, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .post(build_two_factor_url(
+            env,
+            client,
+            PATH_USERS_TWO_FACTOR_RECOVERY_CODES,
+        ))
+        .json(&OtpParams { otp_attempt: otp });
+    api.send(builder).await
+}
+
+fn build_two_factor_url(env: Environment, client: (&SmbClient, &str), path: &str) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route(path);
+    url_builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::login::login_with_otp,
+        smbcloud_mock_server::{MOCK_OTP, MockServer},
+        smbcloud_model::{error_codes::ErrorCode, login::AccountStatus},
+    };
+
+    const CLIENT: (&SmbClient, &str) = (&SmbClient::Cli, "secret");
+
+    #[tokio::test]
+    async fn two_factor_login_needs_a_code_until_it_is_turned_off() {
+        let server = MockServer::shared();
+        let env = Environment::Custom;
+        let (email, password, token) = ("two-factor@smbcloud.xyz", "password", "two-factor-token");
+        server.state().add_account(email, password, token);
+        let login = |otp: Option<&str>| {
+            login_with_otp(
+                env,
+                CLIENT,
+                email.to_string(),
+                password.to_string(),
+                otp.map(str::to_string),
+            )
+        };
+
+        let setup = setup_two_factor(env, CLIENT, token.to_string())
+            .await
+            .unwrap();
+        assert!(setup.provisioning_uri.contains(&setup.secret));
+        assert!(matches!(
+            enable_two_factor(env, CLIENT, token.to_string(), "000000".to_string()).await,
+            Err(ErrorResponse::Error {
+                error_code: ErrorCode::InvalidOtp,
+                ..
+            })
+        ));
+        let codes = enable_two_factor(env, CLIENT, token.to_string(), MOCK_OTP.to_string())
+            .await
+            .unwrap();
+
+        assert!(matches!(login(None).await, Ok(AccountStatus::OtpRequired)));
+        assert!(login(Some("000000")).await.is_err());
+        assert!(matches!(
+            login(Some(MOCK_OTP)).await,
+            Ok(AccountStatus::Ready { .. })
+        ));
+
+        // Regenerating retires the old recovery codes.
+        let new_codes =
+            regenerate_recovery_codes(env, CLIENT, token.to_string(), MOCK_OTP.to_string())
+                .await
+                .unwrap();
+        assert!(login(Some(&codes.recovery_codes[0])).await.is_err());
+        assert!(matches!(
+            login(Some(&new_codes.recovery_codes[0])).await,
+            Ok(AccountStatus::Ready { .. })
+        ));
+
+        disable_two_factor(env, CLIENT, token.to_string(), MOCK_OTP.to_string())
+            .await
+            .unwrap();
+        assert!(matches!(login(None).await, Ok(AccountStatus::Ready { .. })));
+    }
+}
@@ -0,0 +1,65 @@
+use {
+    smbcloud_model::{
+        account::{AccountUpdate, AccountUpdateParams},
+        error_codes::ErrorResponse,
+    },
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::{
+        api_client, constants::PATH_USERS, smb_base_url_builder, smb_client::SmbClient,
+    },
+};
+
+/// Changes the password of the signed-in account.
+pub async fn update_password(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    current_password: String,
+    password: String,
+) -> Result<(), ErrorResponse> {
+    let update = AccountUpdate {
+        current_password,
+        email: None,
+        password: Some(password.clone()),
+        password_confirmation: Some(password),
+    };
+    update_account(env, client, access_token, update).await
+}
+
+/// Asks to move the signed-in account to `email`. The login email only
+/// changes once the new address is verified, see
+/// [`resend_email_verification`](crate::resend_email_verification::resend_email_verification).
+pub async fn update_email(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    current_password: String,
+    email: String,
+) -> Result<(), ErrorResponse> {
+    let update = AccountUpdate {
+        current_password,
+        email: Some(email),
+        password: None,
+        password_confirmation: None,
+    };
+    update_account(env, client, access_token, update).await
+}
+
+async fn update_account(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    update: AccountUpdate,
+) -> Result<(), ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
+        .put(build_smb_update_account_url(env, client))
+        .json(&AccountUpdateParams { user: update });
+    api.send_empty(builder).await
+}
+
+fn build_smb_update_account_url(env: Environment, client: (&SmbClient, &str)) -> String {
+    let mut url_builder = smb_base_url_builder(env, client);
+    url_builder.add_route(PATH_USERS);
+    url_builder.build()
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 /// Pinned SSH host keys for smbCloud deployment servers.
 ///
 /// These are **public** keys — safe to commit to a public repository.
@@ -37,15 +39,44 @@ pub const API_SMBCLOUD_XYZ: &str =
 pub const API_1_SMBCLOUD_XYZ: &str =
     "api-1.smbcloud.xyz ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAII1G3JyS66+yIFGrN3Vgc/UlvBm/oS98qq5pS96UYxcz";
 
+/// Host key for a self-hosted or staging deploy host (`SMB_DEPLOY_HOST`),
+/// either a full `known_hosts` line or just `<type> <base64>`.
+pub const DEPLOY_HOST_KEY_ENV: &str = "SMB_DEPLOY_HOST_KEY";
+
 /// Returns the pinned host key line for the given rsync hostname.
 ///
 /// The returned string is in `known_hosts` format and can be written
 /// directly to a temp file passed to SSH via `-o UserKnownHostsFile=`.
-pub fn for_host(rsync_host: &str) -> &'static str {
+///
+/// Hosts outside `smbcloud.xyz` are never matched against the smbCloud keys;
+/// they get the key from `SMB_DEPLOY_HOST_KEY`, or an empty line so SSH
+/// refuses the connection.
+pub fn for_host(rsync_host: &str) -> Cow<'static, str> {
+    if !rsync_host.ends_with(".smbcloud.xyz") {
+        return Cow::Owned(custom_host_key(
+            rsync_host,
+            std::env::var(DEPLOY_HOST_KEY_ENV).ok(),
+        ));
+    }
     if rsync_host.starts_with("api-1.") {
-        API_1_SMBCLOUD_XYZ
+        Cow::Borrowed(API_1_SMBCLOUD_XYZ)
     } else {
-        API_SMBCLOUD_XYZ
+        Cow::Borrowed(API_SMBCLOUD_XYZ)
+    }
+}
+
+fn custom_host_key(rsync_host: &str, key: Option<String>) -> String {
+    let Some(key) = key
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+    else {
+        return String::new();
+    };
+    // `ssh-ed25519 AAAA…` has no host field yet; `host ssh-ed25519 AAAA…` does.
+    if key.split_whitespace().count() >= 3 {
+        key
+    } else {
+        format!("{rsync_host} {key}")
     }
 }
 
@@ -66,6 +97,15 @@ mod tests {
         assert!(key.starts_with("api-1.smbcloud.xyz ssh-ed25519 "));
     }
 
+    #[test]
+    fn custom_host_key_gets_host_prefix() {
+        let key = custom_host_key("deploy.example.com", Some("ssh-ed25519 AAAAC3Nz".into()));
+        assert_eq!(key, "deploy.example.com ssh-ed25519 AAAAC3Nz");
+        let line = custom_host_key("deploy.example.com", Some("other ssh-ed25519 AAAA".into()));
+        assert_eq!(line, "other ssh-ed25519 AAAA");
+        assert_eq!(custom_host_key("deploy.example.com", None), "");
+    }
+
     #[test]
     fn keys_are_distinct() {
         assert_ne!(API_SMBCLOUD_XYZ, API_1_SMBCLOUD_XYZ);
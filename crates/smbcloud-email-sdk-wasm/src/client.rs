@@ -0,0 +1,91 @@
+use {
+    crate::{js_error, to_js},
+    smbcloud_email_sdk::{EmailCredentials, SendEmail},
+    smbcloud_network::environment::Environment,
+    wasm_bindgen::{JsValue, prelude::wasm_bindgen},
+};
+
+/// Sends and reads transactional email with a Mail app API key.
+#[wasm_bindgen]
+pub struct EmailClient {
+    inner: smbcloud_email_sdk::EmailClient,
+}
+
+#[wasm_bindgen]
+impl EmailClient {
+    #[wasm_bindgen(constructor)]
+    pub fn new(env: Environment, api_key: String) -> EmailClient {
+        EmailClient {
+            inner: smbcloud_email_sdk::EmailClient::from_credentials(
+                env,
+                EmailCredentials { api_key: &api_key },
+            ),
+        }
+    }
+
+    /// A client against an explicit base URL, e.g. a local mock server.
+    pub fn with_base_url(base_url: String, api_key: String) -> EmailClient {
+        EmailClient {
+            inner: smbcloud_email_sdk::EmailClient::with_base_url(
+                &base_url,
+                EmailCredentials { api_key: &api_key },
+            ),
+        }
+    }
+
+    /// Sends `message`. `from` must be on the key's verified domain, and at
+    /// least one of `html` or `text` is required.
+    #[wasm_bindgen(unchecked_return_type = "EmailMessage")]
+    pub async fn send(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "SendEmail")] message: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let message: SendEmail = serde_wasm_bindgen::from_value(message)?;
+        match self.inner.send(&message).await {
+            Ok(message) => Ok(to_js(&message)),
+            Err(error) => Err(js_error(error)),
+        }
+    }
+
+    /// One message with its delivery events. Needs a read-scope key.
+    #[wasm_bindgen(unchecked_return_type = "EmailMessage")]
+    pub async fn get_message(&self, id: String) -> Result<JsValue, JsValue> {
+        match self.inner.get_message(&id).await {
+            Ok(message) => Ok(to_js(&message)),
+            Err(error) => Err(js_error(error)),
+        }
+    }
+
+    /// The newest messages, optionally filtered by status name (e.g.
+    /// `"bounced"`). Needs a read-scope key.
+    #[wasm_bindgen(unchecked_return_type = "EmailMessage[]")]
+    pub async fn list_messages(
+        &self,
+        status: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<JsValue, JsValue> {
+        match self.inner.list_messages(status.as_deref(), limit).await {
+            Ok(messages) => Ok(to_js(&messages)),
+            Err(error) => Err(js_error(error)),
+        }
+    }
+
+    /// One page of [`list_messages`](Self::list_messages), starting at
+    /// `cursor` (the `next_cursor` of the page before).
+    #[wasm_bindgen(unchecked_return_type = "EmailMessagePage")]
+    pub async fn list_messages_page(
+        &self,
+        status: Option<String>,
+        limit: Option<u32>,
+        cursor: Option<String>,
+    ) -> Result<JsValue, JsValue> {
+        match self
+            .inner
+            .list_messages_page(status.as_deref(), limit, cursor.as_deref())
+            .await
+        {
+            Ok(page) => Ok(to_js(&page)),
+            Err(error) => Err(js_error(error)),
+        }
+    }
+}
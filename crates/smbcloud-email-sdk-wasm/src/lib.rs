@@ -0,0 +1,100 @@
+mod client;
+
+use {
+    serde::Serialize,
+    smbcloud_email_sdk::EmailError,
+    wasm_bindgen::{JsValue, prelude::wasm_bindgen},
+};
+
+pub use client::EmailClient;
+
+// Shapes of the objects the methods take, resolve and reject with. `status`
+// is the numeric delivery status: 0 queued, 1 sent, 2 delivered, 3 bounced,
+// 4 complained, 5 failed.
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+export interface SmbcloudEmailError {
+  kind: "network" | "api";
+  status?: number;
+  message: string;
+}
+
+export interface Attachment {
+  filename: string;
+  content_base64: string;
+}
+
+export interface SendEmail {
+  from: string;
+  to: string[];
+  cc?: string[];
+  bcc?: string[];
+  reply_to?: string[];
+  subject?: string;
+  html?: string;
+  text?: string;
+  attachments?: Attachment[];
+  headers?: Record<string, string>;
+  tags?: Record<string, string>;
+  idempotency_key?: string;
+}
+
+export interface EmailEvent {
+  id: string;
+  event_type: number;
+  payload: any;
+  occurred_at: string;
+}
+
+export interface EmailMessage {
+  id: string;
+  status: number;
+  provider_message_id?: string;
+  idempotency_key?: string;
+  from_email: string;
+  to_emails: string[];
+  subject?: string;
+  sent_at?: string;
+  created_at?: string;
+  events: EmailEvent[];
+}
+
+export interface EmailMessagePage {
+  messages: EmailMessage[];
+  next_cursor: string | null;
+}
+"#;
+
+/// What a rejected promise carries: `network` when the request got no reply,
+/// `api` with the HTTP `status` otherwise.
+#[derive(Serialize)]
+struct ErrorPayload {
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    message: String,
+}
+
+fn js_error(error: EmailError) -> JsValue {
+    let payload = match error {
+        EmailError::Http(error) => ErrorPayload {
+            kind: "network",
+            status: None,
+            message: error.to_string(),
+        },
+        EmailError::Api { status, message } => ErrorPayload {
+            kind: "api",
+            status: Some(status),
+            message,
+        },
+    };
+    to_js(&payload)
+}
+
+/// Event payloads are free-form JSON, so objects must come out as plain JS
+/// objects rather than `Map`s.
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or_else(|error| JsValue::from_str(&error.to_string()))
+}
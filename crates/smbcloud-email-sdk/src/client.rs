@@ -1,7 +1,9 @@
 use crate::client_credentials::EmailCredentials;
 use crate::error::EmailError;
-use crate::message::{EmailMessage, SendEmail};
-use smbcloud_network::environment::Environment;
+use crate::message::{EmailMessage, EmailMessagePage, SendEmail};
+use futures_util::stream::{self, Stream, TryStreamExt};
+use smbcloud_model::pagination::NEXT_CURSOR_HEADER;
+use smbcloud_network::{api_client::shared_client, environment::Environment};
 
 /// Talks to the smbCloud transactional email API.
 ///
@@ -27,11 +29,21 @@ impl EmailClient {
     /// The base URL is resolved from the environment:
     /// - `Environment::Dev` → `http://localhost:8088`
     /// - `Environment::Production` → `https://api.smbcloud.xyz`
+    /// - `Environment::Custom` → `SMB_API_URL`
     pub fn from_credentials(environment: Environment, credentials: EmailCredentials<'_>) -> Self {
+        Self::with_base_url(
+            &crate::client_credentials::base_url(&environment),
+            credentials,
+        )
+    }
+
+    /// Build a client against an explicit base URL, e.g. a staging API, a
+    /// self-hosted instance or a local mock server (`http://127.0.0.1:4010`).
+    pub fn with_base_url(base_url: &str, credentials: EmailCredentials<'_>) -> Self {
         EmailClient {
-            base_url: crate::client_credentials::base_url(&environment),
+            base_url: base_url.trim_end_matches('/').to_string(),
             api_key: credentials.api_key.to_string(),
-            http: reqwest::Client::new(),
+            http: shared_client(),
         }
     }
 
@@ -69,11 +81,26 @@ impl EmailClient {
     /// List recent messages: `GET /v1/email/messages`. Requires a read-scope
     /// key. `status` filters by delivery status name (e.g. `"delivered"`);
     /// `limit` is clamped server-side to `1..=100`.
+    ///
+    /// Only the first page comes back; use
+    /// [`list_messages_stream`](Self::list_messages_stream) to read further.
     pub async fn list_messages(
         &self,
         status: Option<&str>,
         limit: Option<u32>,
     ) -> Result<Vec<EmailMessage>, EmailError> {
+        Ok(self.list_messages_page(status, limit, None).await?.messages)
+    }
+
+    /// One page of [`list_messages`](Self::list_messages), starting at
+    /// `cursor` (the `next_cursor` of the page before, or `None` for the
+    /// newest messages).
+    pub async fn list_messages_page(
+        &self,
+        status: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+    ) -> Result<EmailMessagePage, EmailError> {
         let url = format!("{}/v1/email/messages", self.base_url);
 
         let mut params: Vec<(&str, String)> = Vec::new();
@@ -83,6 +110,9 @@ impl EmailClient {
         if let Some(limit) = limit {
             params.push(("limit", limit.to_string()));
         }
+        if let Some(cursor) = cursor {
+            params.push(("cursor", cursor.to_string()));
+        }
 
         let response = self
             .authed(self.http.get(&url))
@@ -90,10 +120,55 @@ impl EmailClient {
             .send()
             .await?;
 
-        if response.status().is_success() {
-            return Ok(response.json().await?);
+        if !response.status().is_success() {
+            return Err(self.api_error(response).await);
         }
-        Err(self.api_error(response).await)
+        let next_cursor = response
+            .headers()
+            .get(NEXT_CURSOR_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|cursor| !cursor.is_empty())
+            .map(str::to_string);
+        Ok(EmailMessagePage {
+            messages: response.json().await?,
+            next_cursor,
+        })
+    }
+
+    /// Every message matching `status`, newest first, fetched `page_size`
+    /// at a time as the stream is polled.
+    ///
+    /// ```no_run
+    /// # use futures_util::TryStreamExt;
+    /// # async fn run(client: smbcloud_email_sdk::EmailClient) -> Result<(), smbcloud_email_sdk::EmailError> {
+    /// let bounced: Vec<_> = client
+    ///     .list_messages_stream(Some("bounced"), Some(100))
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_messages_stream<'a>(
+        &'a self,
+        status: Option<&'a str>,
+        page_size: Option<u32>,
+    ) -> impl Stream<Item = Result<EmailMessage, EmailError>> + 'a {
+        // `None` once the last page is in; `Some(None)` is the first page.
+        stream::try_unfold(Some(None::<String>), move |cursor| async move {
+            let Some(cursor) = cursor else {
+                return Ok::<_, EmailError>(None);
+            };
+            let page = self
+                .list_messages_page(status, page_size, cursor.as_deref())
+                .await?;
+            // A repeated cursor would loop forever.
+            let next = page
+                .next_cursor
+                .filter(|next| Some(next) != cursor.as_ref());
+            let messages = stream::iter(page.messages.into_iter().map(Ok));
+            Ok(Some((messages, next.map(Some))))
+        })
+        .try_flatten()
     }
 
     fn authed(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
@@ -109,3 +184,61 @@ impl EmailClient {
         EmailError::Api { status, message }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::message::EmailStatus,
+        smbcloud_mock_server::{MockServer, MOCK_EMAIL_API_KEY},
+    };
+
+    #[tokio::test]
+    async fn stream_follows_the_cursor_to_the_last_page() {
+        let server = MockServer::start().await.unwrap();
+        {
+            let mut state = server.state();
+            for subject in ["one", "two", "three"] {
+                state.add_email_message(
+                    "a@example.com",
+                    &["b@example.com"],
+                    Some(subject),
+                    "bounced",
+                );
+            }
+            state.add_email_message("a@example.com", &["c@example.com"], None, "delivered");
+        }
+        let client = EmailClient::with_base_url(
+            &server.url(),
+            EmailCredentials {
+                api_key: MOCK_EMAIL_API_KEY,
+            },
+        );
+
+        let first = client
+            .list_messages_page(Some("bounced"), Some(2), None)
+            .await
+            .unwrap();
+        assert_eq!(first.messages.len(), 2);
+        let last = client
+            .list_messages_page(Some("bounced"), Some(2), first.next_cursor.as_deref())
+            .await
+            .unwrap();
+        assert_eq!(last.messages.len(), 1);
+        assert!(last.next_cursor.is_none());
+
+        let bounced: Vec<_> = client
+            .list_messages_stream(Some("bounced"), Some(2))
+            .try_collect()
+            .await
+            .unwrap();
+        let subjects: Vec<_> = bounced
+            .iter()
+            .map(|message| message.subject.as_deref().unwrap())
+            .collect();
+        assert_eq!(subjects, ["three", "two", "one"]);
+        assert!(bounced
+            .iter()
+            .all(|message| message.status == EmailStatus::Bounced));
+    }
+}
@@ -148,3 +148,11 @@ pub struct EmailMessage {
     #[serde(default)]
     pub events: Vec<EmailEvent>,
 }
+
+/// One page from [`crate::EmailClient::list_messages_page`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailMessagePage {
+    pub messages: Vec<EmailMessage>,
+    /// Pass back as `cursor` for the next page; `None` on the last one.
+    pub next_cursor: Option<String>,
+}
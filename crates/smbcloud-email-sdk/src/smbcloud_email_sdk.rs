@@ -38,5 +38,5 @@ mod message;
 pub use client::EmailClient;
 pub use client_credentials::{base_url, EmailCredentials};
 pub use error::EmailError;
-pub use message::{Attachment, EmailEvent, EmailMessage, EmailStatus, SendEmail};
+pub use message::{Attachment, EmailEvent, EmailMessage, EmailMessagePage, EmailStatus, SendEmail};
 pub use smbcloud_network::environment::Environment;
@@ -0,0 +1,116 @@
+use {
+    crate::{js_error, to_js},
+    serde_json::Value,
+    smbcloud_gresiq_sdk::{DocumentQuery, GresiqCredentials},
+    smbcloud_network::environment::Environment,
+    wasm_bindgen::{JsValue, prelude::wasm_bindgen},
+};
+
+#[wasm_bindgen]
+extern "C" {
+    /// A `DocumentQuery` object from JS; typed so the parameter can be left
+    /// out.
+    #[wasm_bindgen(typescript_type = "DocumentQuery")]
+    pub type JsDocumentQuery;
+}
+
+/// Talks to the smbCloud GresIQ gateway with a database's API key and secret.
+#[wasm_bindgen]
+pub struct GresiqClient {
+    inner: smbcloud_gresiq_sdk::GresiqClient,
+}
+
+#[wasm_bindgen]
+impl GresiqClient {
+    #[wasm_bindgen(constructor)]
+    pub fn new(env: Environment, api_key: String, api_secret: String) -> GresiqClient {
+        GresiqClient {
+            inner: smbcloud_gresiq_sdk::GresiqClient::from_credentials(
+                env,
+                GresiqCredentials {
+                    api_key: &api_key,
+                    api_secret: &api_secret,
+                },
+            ),
+        }
+    }
+
+    /// A client against an explicit base URL, e.g. a local mock server.
+    pub fn with_base_url(base_url: String, api_key: String, api_secret: String) -> GresiqClient {
+        GresiqClient {
+            inner: smbcloud_gresiq_sdk::GresiqClient::with_base_url(
+                &base_url,
+                GresiqCredentials {
+                    api_key: &api_key,
+                    api_secret: &api_secret,
+                },
+            ),
+        }
+    }
+
+    /// POSTs `record` into a GresIQ-managed table, e.g.
+    /// `"pulse_inference_events"`.
+    pub async fn insert(&self, table: String, record: JsValue) -> Result<(), JsValue> {
+        let record: Value = serde_wasm_bindgen::from_value(record)?;
+        self.inner.insert(&table, &record).await.map_err(js_error)
+    }
+
+    /// Upserts `doc` on `key`, or appends it under a server-generated key
+    /// when `key` is omitted.
+    pub async fn upsert_document(
+        &self,
+        collection: String,
+        doc: JsValue,
+        key: Option<String>,
+    ) -> Result<(), JsValue> {
+        let doc: Value = serde_wasm_bindgen::from_value(doc)?;
+        self.inner
+            .upsert_document(&collection, key.as_deref(), &doc)
+            .await
+            .map_err(js_error)
+    }
+
+    /// The first page of documents in `collection` matching `query`.
+    #[wasm_bindgen(unchecked_return_type = "GresiqDocument[]")]
+    pub async fn get_collection(
+        &self,
+        collection: String,
+        query: Option<JsDocumentQuery>,
+    ) -> Result<JsValue, JsValue> {
+        let query = document_query(query)?;
+        match self
+            .inner
+            .get_collection::<Value>(&collection, &query)
+            .await
+        {
+            Ok(documents) => Ok(to_js(&documents)),
+            Err(error) => Err(js_error(error)),
+        }
+    }
+
+    /// One page of [`get_collection`](Self::get_collection). Pass
+    /// `next_cursor` back as `query.cursor` for the next one.
+    #[wasm_bindgen(unchecked_return_type = "DocumentPage")]
+    pub async fn get_collection_page(
+        &self,
+        collection: String,
+        query: Option<JsDocumentQuery>,
+    ) -> Result<JsValue, JsValue> {
+        let query = document_query(query)?;
+        match self
+            .inner
+            .get_collection_page::<Value>(&collection, &query)
+            .await
+        {
+            Ok(page) => Ok(to_js(&page)),
+            Err(error) => Err(js_error(error)),
+        }
+    }
+}
+
+fn document_query(query: Option<JsDocumentQuery>) -> Result<DocumentQuery, JsValue> {
+    match query {
+        Some(query) => Ok(serde_wasm_bindgen::from_value(query.into())?),
+        None => Ok(DocumentQuery::default()),
+    }
+}
@@ -0,0 +1,76 @@
+mod client;
+
+use {
+    serde::Serialize,
+    smbcloud_gresiq_sdk::GresiqError,
+    wasm_bindgen::{JsValue, prelude::wasm_bindgen},
+};
+
+pub use client::GresiqClient;
+
+// Shapes of the objects the methods take, resolve and reject with. `doc` is
+// whatever JSON the caller stored.
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+export interface SmbcloudGresiqError {
+  kind: "network" | "api";
+  status?: number;
+  message: string;
+}
+
+export interface DocumentQuery {
+  filter?: Record<string, unknown>;
+  order?: "created_at" | "updated_at";
+  dir?: "asc" | "desc";
+  limit?: number;
+  cursor?: string;
+}
+
+export interface GresiqDocument {
+  id: string;
+  key: string;
+  collection: string;
+  doc: any;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface DocumentPage {
+  documents: GresiqDocument[];
+  next_cursor: string | null;
+}
+"#;
+
+/// What a rejected promise carries: `network` when the request got no reply,
+/// `api` with the HTTP `status` otherwise.
+#[derive(Serialize)]
+struct ErrorPayload {
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    message: String,
+}
+
+fn js_error(error: GresiqError) -> JsValue {
+    let payload = match error {
+        GresiqError::Http(error) => ErrorPayload {
+            kind: "network",
+            status: None,
+            message: error.to_string(),
+        },
+        GresiqError::Api { status, message } => ErrorPayload {
+            kind: "api",
+            status: Some(status),
+            message,
+        },
+    };
+    to_js(&payload)
+}
+
+/// Documents are free-form JSON, so objects must come out as plain JS objects
+/// rather than `Map`s.
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or_else(|error| JsValue::from_str(&error.to_string()))
+}
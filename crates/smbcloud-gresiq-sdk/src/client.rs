@@ -1,28 +1,32 @@
 use crate::client_credentials::GresiqCredentials;
 use crate::error::GresiqError;
+use futures_util::stream::{self, Stream, TryStreamExt};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use smbcloud_network::environment::Environment;
+use smbcloud_network::{api_client::shared_client, environment::Environment};
 use std::collections::HashMap;
 
 /// Query parameters for `get_collection` against the document gateway.
 ///
 /// `filter` is a JSON containment object (`doc @> filter`); the gateway can
 /// only order by `created_at` / `updated_at`, so `order`/`dir` are limited to
-/// those columns. `limit` is clamped server-side to `1..=1000`.
-#[derive(Debug, Clone, Default)]
+/// those columns. `limit` is clamped server-side to `1..=1000` and doubles
+/// as the page size; `cursor` is the `next_cursor` of the page before.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DocumentQuery {
     pub filter: Option<serde_json::Value>,
     pub order: Option<String>,
     pub dir: Option<String>,
     pub limit: Option<u32>,
+    pub cursor: Option<String>,
 }
 
 /// One row from the document gateway's `{ documents: [...] }` envelope.
 ///
 /// `T` is the caller's document shape — schema knowledge lives in the caller.
 /// The platform metadata (`id`, `key`, timestamps) rides alongside it.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GresiqDocument<T> {
     pub id: String,
     pub key: String,
@@ -32,9 +36,14 @@ pub struct GresiqDocument<T> {
     pub updated_at: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct DocumentsEnvelope<T> {
-    documents: Vec<GresiqDocument<T>>,
+/// One page from [`GresiqClient::get_collection_page`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentPage<T> {
+    pub documents: Vec<GresiqDocument<T>>,
+    /// Set as [`DocumentQuery::cursor`] to fetch the next page; `None` on the
+    /// last one. Gateways that predate paging never send it.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 /// Talks to the smbCloud GresIQ REST gateway.
@@ -65,14 +74,23 @@ impl GresiqClient {
     /// The base URL is resolved automatically from the environment:
     /// - `Environment::Dev` → `http://localhost:8088`
     /// - `Environment::Production` → `https://api.smbcloud.xyz`
+    /// - `Environment::Custom` → `SMB_API_URL`
     pub fn from_credentials(environment: Environment, credentials: GresiqCredentials<'_>) -> Self {
-        let base_url = crate::client_credentials::base_url(&environment);
+        Self::with_base_url(
+            &crate::client_credentials::base_url(&environment),
+            credentials,
+        )
+    }
+
+    /// Build a client against an explicit base URL, e.g. a staging API, a
+    /// self-hosted instance or a local mock server (`http://127.0.0.1:4010`).
+    pub fn with_base_url(base_url: &str, credentials: GresiqCredentials<'_>) -> Self {
         GresiqClient {
-            base_url,
+            base_url: base_url.trim_end_matches('/').to_string(),
             api_key: credentials.api_key.to_string(),
             api_secret: credentials.api_secret.to_string(),
             extra_headers: HashMap::new(),
-            http: reqwest::Client::new(),
+            http: shared_client(),
         }
     }
 
@@ -162,12 +180,23 @@ impl GresiqClient {
     /// filter, ordering, and limit (see [`DocumentQuery`]).
     ///
     /// Each returned [`GresiqDocument`] deserializes its `doc` into `T`; the
-    /// caller owns the document shape.
+    /// caller owns the document shape. Only one page comes back; use
+    /// [`collection_stream`](Self::collection_stream) to read past it.
     pub async fn get_collection<T: DeserializeOwned>(
         &self,
         collection: &str,
         query: &DocumentQuery,
     ) -> Result<Vec<GresiqDocument<T>>, GresiqError> {
+        Ok(self.get_collection_page(collection, query).await?.documents)
+    }
+
+    /// One page of [`get_collection`](Self::get_collection), starting at
+    /// `query.cursor`.
+    pub async fn get_collection_page<T: DeserializeOwned>(
+        &self,
+        collection: &str,
+        query: &DocumentQuery,
+    ) -> Result<DocumentPage<T>, GresiqError> {
         let url = format!("{}/gresiq/v1/collections/{}", self.base_url, collection);
 
         let mut params: Vec<(&str, String)> = Vec::new();
@@ -183,6 +212,9 @@ impl GresiqClient {
         if let Some(limit) = query.limit {
             params.push(("limit", limit.to_string()));
         }
+        if let Some(cursor) = &query.cursor {
+            params.push(("cursor", cursor.clone()));
+        }
 
         let response = self.authed_get(&url).query(&params).send().await?;
 
@@ -190,8 +222,34 @@ impl GresiqClient {
             return Err(self.api_error(response).await);
         }
 
-        let envelope: DocumentsEnvelope<T> = response.json().await?;
-        Ok(envelope.documents)
+        Ok(response.json().await?)
+    }
+
+    /// Every document in `collection` matching `query`, fetched a page
+    /// (`query.limit` documents) at a time as the stream is polled.
+    pub fn collection_stream<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        collection: &'a str,
+        query: DocumentQuery,
+    ) -> impl Stream<Item = Result<GresiqDocument<T>, GresiqError>> + 'a {
+        // `None` once the last page is in.
+        stream::try_unfold(Some(query), move |query| async move {
+            let Some(query) = query else {
+                return Ok::<_, GresiqError>(None);
+            };
+            let page = self.get_collection_page::<T>(collection, &query).await?;
+            // A repeated cursor would loop forever.
+            let next = page
+                .next_cursor
+                .filter(|next| Some(next) != query.cursor.as_ref())
+                .map(|cursor| DocumentQuery {
+                    cursor: Some(cursor),
+                    ..query
+                });
+            let documents = stream::iter(page.documents.into_iter().map(Ok));
+            Ok(Some((documents, next)))
+        })
+        .try_flatten()
     }
 
     /// Build a POST request carrying the GresIQ credentials and extra headers.
@@ -224,3 +282,65 @@ impl GresiqClient {
         GresiqError::Api { status, message }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        smbcloud_mock_server::{MockServer, MOCK_GRESIQ_API_KEY, MOCK_GRESIQ_API_SECRET},
+    };
+
+    #[tokio::test]
+    async fn stream_follows_the_cursor_to_the_last_page() {
+        let server = MockServer::start().await.unwrap();
+        let client = GresiqClient::with_base_url(
+            &server.url(),
+            GresiqCredentials {
+                api_key: MOCK_GRESIQ_API_KEY,
+                api_secret: MOCK_GRESIQ_API_SECRET,
+            },
+        );
+        for n in 1..=5 {
+            let key = n.to_string();
+            let doc = serde_json::json!({ "n": n, "odd": n % 2 == 1 });
+            client
+                .upsert_document("numbers", Some(&key), &doc)
+                .await
+                .unwrap();
+        }
+
+        let query = DocumentQuery {
+            filter: Some(serde_json::json!({ "odd": true })),
+            limit: Some(2),
+            ..DocumentQuery::default()
+        };
+        let first = client
+            .get_collection_page::<serde_json::Value>("numbers", &query)
+            .await
+            .unwrap();
+        assert_eq!(first.documents.len(), 2);
+        let last = client
+            .get_collection_page::<serde_json::Value>(
+                "numbers",
+                &DocumentQuery {
+                    cursor: first.next_cursor,
+                    ..query.clone()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(last.documents.len(), 1);
+        assert!(last.next_cursor.is_none());
+
+        let odd: Vec<GresiqDocument<serde_json::Value>> = client
+            .collection_stream("numbers", query)
+            .try_collect()
+            .await
+            .unwrap();
+        let numbers: Vec<_> = odd
+            .iter()
+            .map(|document| document.doc["n"].clone())
+            .collect();
+        assert_eq!(numbers, [1, 3, 5]);
+    }
+}
@@ -14,10 +14,7 @@ pub struct GresiqCredentials<'a> {
 ///
 /// - **Dev** → `http://localhost:8088`
 /// - **Production** → `https://api.smbcloud.xyz`
+/// - **Custom** → `SMB_API_URL` (default `http://localhost:8088`)
 pub fn base_url(environment: &Environment) -> String {
-    format!(
-        "{}://{}",
-        environment.api_protocol(),
-        environment.api_host()
-    )
+    environment.api_base_url()
 }
@@ -1,7 +1,8 @@
 //! Apps and models management for Onde Inference.
 //!
-//! Each function opens its own `reqwest::Client`. These are low-frequency
-//! management calls, so there's no benefit to a shared pool.
+//! Each function borrows the process-wide pool from
+//! `smbcloud_network::api_client::shared_client`, so calls reuse connections
+//! and get its timeouts.
 //!
 //! Every request needs two things: the Onde app's client credentials as
 //! query params, and the user's bearer token as an Authorization header.
@@ -14,7 +15,7 @@
 
 use crate::error::GresiqError;
 use serde::{Deserialize, Serialize};
-use smbcloud_network::environment::Environment;
+use smbcloud_network::{api_client::shared_client, environment::Environment};
 
 /// An app registered to the user's account.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,7 +128,7 @@ pub async fn list_apps(
     access_token: &str,
 ) -> Result<Vec<OndeApp>, GresiqError> {
     let url = endpoint(environment, "apps", app_id, app_secret);
-    let response = reqwest::Client::new()
+    let response = shared_client()
         .get(&url)
         .header("Authorization", bearer(access_token))
         .header("Content-Type", "application/json")
@@ -150,7 +151,7 @@ pub async fn create_app(
     let body = CreateAppBody {
         gresiq_app: CreateAppParams { name },
     };
-    let response = reqwest::Client::new()
+    let response = shared_client()
         .post(&url)
         .header("Authorization", bearer(access_token))
         .header("Content-Type", "application/json")
@@ -174,7 +175,7 @@ pub async fn assign_model(
     let path = format!("apps/{}/model", onde_app_id);
     let url = endpoint(environment, &path, app_id, app_secret);
     let body = serde_json::json!({ "model_id": model_id });
-    let response = reqwest::Client::new()
+    let response = shared_client()
         .patch(&url)
         .header("Authorization", bearer(access_token))
         .header("Content-Type", "application/json")
@@ -195,7 +196,7 @@ pub async fn list_models(
     access_token: &str,
 ) -> Result<Vec<OndeModel>, GresiqError> {
     let url = endpoint(environment, "models", app_id, app_secret);
-    let response = reqwest::Client::new()
+    let response = shared_client()
         .get(&url)
         .header("Authorization", bearer(access_token))
         .header("Content-Type", "application/json")
@@ -225,7 +226,7 @@ pub async fn create_model(
     let body = CreateModelBody {
         gresiq_model: params,
     };
-    let response = reqwest::Client::new()
+    let response = shared_client()
         .post(&url)
         .header("Authorization", bearer(access_token))
         .header("Content-Type", "application/json")
@@ -251,7 +252,7 @@ pub async fn rename_app(
     let body = CreateAppBody {
         gresiq_app: CreateAppParams { name: new_name },
     };
-    let response = reqwest::Client::new()
+    let response = shared_client()
         .patch(&url)
         .header("Authorization", bearer(access_token))
         .header("Content-Type", "application/json")
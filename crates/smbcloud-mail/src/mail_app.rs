@@ -1,15 +1,11 @@
-use crate::{
-    request::request_empty,
-    url_builder::{build_mail_app_url, build_mail_apps_url},
-};
-use reqwest::Client;
+use crate::url_builder::{build_mail_app_url, build_mail_apps_url};
 use serde::Serialize;
 use smbcloud_model::{
     error_codes::ErrorResponse,
     mail::{MailApp, MailAppCreate, MailAppUpdate},
 };
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 #[derive(Serialize)]
 struct MailAppEnvelope<T> {
@@ -22,11 +18,9 @@ pub async fn get_mail_apps(
     access_token: String,
     project_id: Option<String>,
 ) -> Result<Vec<MailApp>, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_mail_apps_url(env, client, project_id.as_deref()))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.get(build_mail_apps_url(env, client, project_id.as_deref()));
+    api.send(builder).await
 }
 
 pub async fn get_mail_app(
@@ -35,11 +29,9 @@ pub async fn get_mail_app(
     access_token: String,
     mail_app_id: String,
 ) -> Result<MailApp, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_mail_app_url(env, client, &mail_app_id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.get(build_mail_app_url(env, client, &mail_app_id));
+    api.send(builder).await
 }
 
 pub async fn create_mail_app(
@@ -48,12 +40,11 @@ pub async fn create_mail_app(
     access_token: String,
     mail_app: MailAppCreate,
 ) -> Result<MailApp, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .post(build_mail_apps_url(env, client, None))
-        .json(&MailAppEnvelope { mail_app })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&MailAppEnvelope { mail_app });
+    api.send(builder).await
 }
 
 pub async fn update_mail_app(
@@ -63,12 +54,11 @@ pub async fn update_mail_app(
     mail_app_id: String,
     mail_app: MailAppUpdate,
 ) -> Result<MailApp, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .put(build_mail_app_url(env, client, &mail_app_id))
-        .json(&MailAppEnvelope { mail_app })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&MailAppEnvelope { mail_app });
+    api.send(builder).await
 }
 
 pub async fn delete_mail_app(
@@ -77,9 +67,7 @@ pub async fn delete_mail_app(
     access_token: String,
     mail_app_id: String,
 ) -> Result<(), ErrorResponse> {
-    let builder = Client::new()
-        .delete(build_mail_app_url(env, client, &mail_app_id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request_empty(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.delete(build_mail_app_url(env, client, &mail_app_id));
+    api.send_empty(builder).await
 }
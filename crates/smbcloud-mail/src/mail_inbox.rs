@@ -1,8 +1,4 @@
-use crate::{
-    request::request_empty,
-    url_builder::{build_mail_inbox_test_url, build_mail_inbox_url, build_mail_inboxes_url},
-};
-use reqwest::Client;
+use crate::url_builder::{build_mail_inbox_test_url, build_mail_inbox_url, build_mail_inboxes_url};
 use serde::Serialize;
 use smbcloud_model::{
     error_codes::ErrorResponse,
@@ -10,8 +6,8 @@ use smbcloud_model::{
         MailInbox, MailInboxCreate, MailInboxUpdate, MailTestEmailDelivery, MailTestEmailRequest,
     },
 };
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 #[derive(Serialize)]
 struct MailInboxEnvelope<T> {
@@ -25,12 +21,11 @@ pub async fn create_mail_inbox(
     mail_app_id: String,
     mail_inbox: MailInboxCreate,
 ) -> Result<MailInbox, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .post(build_mail_inboxes_url(env, client, &mail_app_id))
-        .json(&MailInboxEnvelope { mail_inbox })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&MailInboxEnvelope { mail_inbox });
+    api.send(builder).await
 }
 
 pub async fn update_mail_inbox(
@@ -41,12 +36,11 @@ pub async fn update_mail_inbox(
     inbox_id: String,
     mail_inbox: MailInboxUpdate,
 ) -> Result<MailInbox, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .put(build_mail_inbox_url(env, client, &mail_app_id, &inbox_id))
-        .json(&MailInboxEnvelope { mail_inbox })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&MailInboxEnvelope { mail_inbox });
+    api.send(builder).await
 }
 
 pub async fn delete_mail_inbox(
@@ -56,11 +50,9 @@ pub async fn delete_mail_inbox(
     mail_app_id: String,
     inbox_id: String,
 ) -> Result<(), ErrorResponse> {
-    let builder = Client::new()
-        .delete(build_mail_inbox_url(env, client, &mail_app_id, &inbox_id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request_empty(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.delete(build_mail_inbox_url(env, client, &mail_app_id, &inbox_id));
+    api.send_empty(builder).await
 }
 
 pub async fn send_test_email(
@@ -71,15 +63,14 @@ pub async fn send_test_email(
     inbox_id: String,
     mail_inbox: MailTestEmailRequest,
 ) -> Result<MailTestEmailDelivery, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .post(build_mail_inbox_test_url(
             env,
             client,
             &mail_app_id,
             &inbox_id,
         ))
-        .json(&MailInboxEnvelope { mail_inbox })
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&MailInboxEnvelope { mail_inbox });
+    api.send(builder).await
 }
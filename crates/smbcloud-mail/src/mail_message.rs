@@ -1,8 +1,11 @@
 use crate::url_builder::{build_mail_message_url, build_mail_messages_url};
-use reqwest::Client;
-use smbcloud_model::{error_codes::ErrorResponse, mail::MailMessage};
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_model::{
+    error_codes::ErrorResponse,
+    mail::MailMessage,
+    pagination::{Page, PageRequest},
+};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 pub async fn get_mail_messages(
     env: Environment,
@@ -12,17 +15,38 @@ pub async fn get_mail_messages(
     inbox_id: String,
     limit: Option<u32>,
 ) -> Result<Vec<MailMessage>, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api.get(build_mail_messages_url(
+        env,
+        client,
+        &mail_app_id,
+        &inbox_id,
+        limit,
+    ));
+    api.send(builder).await
+}
+
+/// One page of an inbox's messages, newest first. The page's `limit` is
+/// clamped server-side to `1..=100`.
+pub async fn get_mail_messages_page(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    mail_app_id: String,
+    inbox_id: String,
+    page: &PageRequest,
+) -> Result<Page<MailMessage>, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
         .get(build_mail_messages_url(
             env,
             client,
             &mail_app_id,
             &inbox_id,
-            limit,
+            None,
         ))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .query(page);
+    api.send_page(builder).await
 }
 
 pub async fn get_mail_message(
@@ -33,15 +57,105 @@ pub async fn get_mail_message(
     inbox_id: String,
     message_id: String,
 ) -> Result<MailMessage, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_mail_message_url(
+    let api = api_client(env, access_token);
+    let builder = api.get(build_mail_message_url(
+        env,
+        client,
+        &mail_app_id,
+        &inbox_id,
+        &message_id,
+    ));
+    api.send(builder).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            mail_app::{create_mail_app, delete_mail_app, get_mail_apps},
+            mail_inbox::create_mail_inbox,
+        },
+        smbcloud_mock_server::{MockServer, MOCK_ACCESS_TOKEN},
+        smbcloud_model::mail::{MailAppCreate, MailInboxCreate},
+        smbcloud_network::{environment::API_URL_ENV, pagination::collect_pages},
+    };
+
+    const CLIENT: (&SmbClient, &str) = (&SmbClient::Cli, "secret");
+
+    fn token() -> String {
+        MOCK_ACCESS_TOKEN.to_string()
+    }
+
+    /// The only test in this crate, since it points `SMB_API_URL` at its own
+    /// mock.
+    #[tokio::test]
+    async fn mail_app_lifecycle_against_the_mock() {
+        let server = MockServer::start().await.unwrap();
+        std::env::set_var(API_URL_ENV, server.url());
+        let env = Environment::Custom;
+        let project = server.state().add_project("site", "", None);
+
+        let mail_app = create_mail_app(
             env,
-            client,
-            &mail_app_id,
-            &inbox_id,
-            &message_id,
-        ))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+            CLIENT,
+            token(),
+            MailAppCreate {
+                name: "Support".to_string(),
+                project_id: project.id.to_string(),
+                domain: "example.com".to_string(),
+                aws_region: None,
+            },
+        )
+        .await
+        .unwrap();
+        let inbox = create_mail_inbox(
+            env,
+            CLIENT,
+            token(),
+            mail_app.id.to_string(),
+            MailInboxCreate {
+                local_part: "help".to_string(),
+                forward_to_email: "me@example.org".to_string(),
+                sender_email: None,
+            },
+        )
+        .await
+        .unwrap();
+        for subject in ["first", "second", "third"] {
+            server
+                .state()
+                .add_mail_message(inbox.id, "someone@example.org", subject, "Hi")
+                .unwrap();
+        }
+
+        let messages = collect_pages(Some(2), |page| {
+            let (mail_app_id, inbox_id) = (mail_app.id.to_string(), inbox.id.to_string());
+            async move {
+                get_mail_messages_page(env, CLIENT, token(), mail_app_id, inbox_id, &page).await
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(messages.len(), 3);
+        let message = get_mail_message(
+            env,
+            CLIENT,
+            token(),
+            mail_app.id.to_string(),
+            inbox.id.to_string(),
+            messages[0].id.to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(message.id, messages[0].id);
+
+        delete_mail_app(env, CLIENT, token(), mail_app.id.to_string())
+            .await
+            .unwrap();
+        assert!(get_mail_apps(env, CLIENT, token(), None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
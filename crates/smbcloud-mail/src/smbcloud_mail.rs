@@ -1,5 +1,4 @@
 pub mod mail_app;
 pub mod mail_inbox;
 pub mod mail_message;
-mod request;
 mod url_builder;
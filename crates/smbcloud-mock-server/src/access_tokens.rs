@@ -0,0 +1,67 @@
+//! `v1/access_tokens`. Bodies are wrapped as `{ "access_token": { … } }`.
+//! Only a login session gets here; see `extract::Authed`.
+
+use {
+    crate::{error::ApiError, extract::Authed, state::SharedState},
+    axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        Json,
+    },
+    serde::Deserialize,
+    smbcloud_model::access_token::{AccessToken, AccessTokenCreate, CreatedAccessToken},
+};
+
+#[derive(Deserialize)]
+pub(crate) struct AccessTokenEnvelope {
+    access_token: AccessTokenCreate,
+}
+
+pub(crate) async fn index(
+    State(state): State<SharedState>,
+    authed: Authed,
+) -> Json<Vec<AccessToken>> {
+    let state = state.lock().expect("mock state lock");
+    Json(
+        state
+            .access_tokens
+            .iter()
+            .filter(|access_token| access_token.user_id == authed.user_id)
+            .map(|access_token| access_token.record.clone())
+            .collect(),
+    )
+}
+
+pub(crate) async fn create(
+    State(state): State<SharedState>,
+    authed: Authed,
+    Json(body): Json<AccessTokenEnvelope>,
+) -> Result<(StatusCode, Json<CreatedAccessToken>), ApiError> {
+    let params = body.access_token;
+    let name = params.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::invalid_params("Name can't be blank."));
+    }
+    if params.scopes.is_empty() {
+        return Err(ApiError::invalid_params("Pick at least one scope."));
+    }
+    let mut state = state.lock().expect("mock state lock");
+    let created = state.add_access_token(authed.user_id, name, params.scopes, params.expires_at);
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+pub(crate) async fn destroy(
+    State(state): State<SharedState>,
+    authed: Authed,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let before = state.access_tokens.len();
+    state.access_tokens.retain(|access_token| {
+        access_token.record.id != id || access_token.user_id != authed.user_id
+    });
+    if state.access_tokens.len() == before {
+        return Err(ApiError::not_found("Access token"));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
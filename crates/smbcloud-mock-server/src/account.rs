@@ -0,0 +1,258 @@
+//! `v1/users/sign_in`, `v1/users/sign_out`, `v1/me`, the pre-login lookup
+//! `v1/check_email`, the Google exchange `v1/authorize/google`, and
+//! `v1/users` (sign up, update, delete).
+
+use {
+    crate::{
+        error::ApiError,
+        extract::Authed,
+        state::{MockAccount, SharedState},
+    },
+    axum::{
+        extract::{Query, State},
+        http::{header::AUTHORIZATION, StatusCode},
+        response::{IntoResponse, Response},
+        Json,
+    },
+    serde::Deserialize,
+    serde_json::json,
+    smbcloud_model::{
+        account::ErrorCode, error_codes::ErrorCode as ApiErrorCode, oauth::GoogleAuthorization,
+    },
+};
+
+#[derive(Deserialize)]
+pub(crate) struct SignInBody {
+    user: SignInUser,
+}
+
+#[derive(Deserialize)]
+struct SignInUser {
+    email: String,
+    password: String,
+    #[serde(default)]
+    otp_attempt: Option<String>,
+}
+
+/// Mirrors the Devise session endpoint: the token rides back in the
+/// `Authorization` header, unknown emails are a bare 404 and a wrong password
+/// is a 401 carrying the account error code. With two-factor authentication
+/// on, a missing or wrong `otp_attempt` is a 401 as well.
+pub(crate) async fn sign_in(
+    State(state): State<SharedState>,
+    Json(body): Json<SignInBody>,
+) -> Response {
+    let mut state = state.lock().expect("mock state lock");
+    let Some(account) = state
+        .accounts
+        .iter_mut()
+        .find(|account| account.user.email == body.user.email)
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    if account.password != body.user.password {
+        return sign_in_error(ErrorCode::InvalidPassword);
+    }
+    if account.two_factor.enabled {
+        match &body.user.otp_attempt {
+            None => return sign_in_error(ErrorCode::OtpRequired),
+            Some(otp) if !account.two_factor.verify(otp) => {
+                return sign_in_error(ErrorCode::InvalidOtp)
+            }
+            Some(_) => {}
+        }
+    }
+    session(account)
+}
+
+fn sign_in_error(error_code: ErrorCode) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({ "message": error_code.to_string(), "error_code": error_code })),
+    )
+        .into_response()
+}
+
+/// A logged-in account: its token in the `Authorization` header, its user as
+/// the body.
+fn session(account: &MockAccount) -> Response {
+    (
+        [(AUTHORIZATION, format!("Bearer {}", account.access_token))],
+        Json(account.user.clone()),
+    )
+        .into_response()
+}
+
+pub(crate) async fn sign_out(_authed: Authed) -> Json<serde_json::Value> {
+    Json(json!({ "message": "Signed out." }))
+}
+
+pub(crate) async fn me(
+    State(state): State<SharedState>,
+    authed: Authed,
+) -> Result<impl IntoResponse, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    state
+        .accounts
+        .iter()
+        .find(|account| account.user.id == authed.user_id)
+        .map(|account| Json(account.user.clone()))
+        .ok_or_else(ApiError::unauthorized)
+}
+
+#[derive(Deserialize)]
+pub(crate) struct EmailQuery {
+    email: String,
+}
+
+/// The step before the password prompt: a known email comes back with its
+/// user, an unknown one with `EmailNotFound`, both as `200`.
+pub(crate) async fn check_email(
+    State(state): State<SharedState>,
+    Query(query): Query<EmailQuery>,
+) -> Json<serde_json::Value> {
+    let state = state.lock().expect("mock state lock");
+    match state
+        .accounts
+        .iter()
+        .find(|account| account.user.email == query.email)
+    {
+        Some(account) => Json(json!({ "message": "Email found.", "user": account.user })),
+        None => {
+            let error_code = ErrorCode::EmailNotFound;
+            Json(json!({ "message": error_code.to_string(), "error_code": error_code }))
+        }
+    }
+}
+
+/// Stands in for the server checking a Google ID token with Google: only
+/// tokens from [`MockState::add_google_id_token`] pass, anything else is a
+/// 401. The account is the one with the token's email, logged in like
+/// `sign_in`. Without one it is a bare 404, or with `sign_up` it is created.
+///
+/// [`MockState::add_google_id_token`]: crate::MockState::add_google_id_token
+pub(crate) async fn authorize_google(
+    State(state): State<SharedState>,
+    Json(body): Json<GoogleAuthorization>,
+) -> Response {
+    let mut state = state.lock().expect("mock state lock");
+    let Some(email) = state.google_id_tokens.get(&body.id_token).cloned() else {
+        return ApiError::unauthorized().into_response();
+    };
+    if !state
+        .accounts
+        .iter()
+        .any(|account| account.user.email == email)
+    {
+        if !body.sign_up {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+        let access_token = format!("mock-access-token-{}", state.accounts.len() + 1);
+        state.add_account(&email, "", &access_token);
+    }
+    let account = state
+        .accounts
+        .iter()
+        .find(|account| account.user.email == email)
+        .expect("the account was just found or created");
+    session(account)
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SignUpBody {
+    user: SignInUser,
+}
+
+/// Creates an account. Its token is made up from the new id, since the real
+/// API only hands one out on sign-in.
+pub(crate) async fn sign_up(
+    State(state): State<SharedState>,
+    Json(body): Json<SignUpBody>,
+) -> Result<impl IntoResponse, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    if state
+        .accounts
+        .iter()
+        .any(|account| account.user.email == body.user.email)
+    {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ApiErrorCode::EmailAlreadyExist,
+            ApiErrorCode::EmailAlreadyExist.message(None),
+        ));
+    }
+    let access_token = format!("mock-access-token-{}", state.accounts.len() + 1);
+    let user = state.add_account(&body.user.email, &body.user.password, &access_token);
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "code": null,
+            "message": "Signed up. Check your email to verify your account.",
+            "data": {
+                "id": user.id,
+                "email": user.email,
+                "created_at": user.created_at.to_rfc3339(),
+            },
+        })),
+    ))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AccountUpdateBody {
+    user: AccountUpdate,
+}
+
+#[derive(Deserialize)]
+struct AccountUpdate {
+    current_password: String,
+    email: Option<String>,
+    password: Option<String>,
+    password_confirmation: Option<String>,
+}
+
+/// Devise's registration update: every change needs the current password. A
+/// new email waits in `unconfirmed_email` until verified.
+pub(crate) async fn update(
+    State(state): State<SharedState>,
+    authed: Authed,
+    Json(body): Json<AccountUpdateBody>,
+) -> Result<StatusCode, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let account = state
+        .accounts
+        .iter_mut()
+        .find(|account| account.user.id == authed.user_id)
+        .ok_or_else(ApiError::unauthorized)?;
+    let update = body.user;
+    if account.password != update.current_password {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ApiErrorCode::InvalidPassword,
+            ApiErrorCode::InvalidPassword.message(None),
+        ));
+    }
+    if let Some(password) = update.password {
+        if update.password_confirmation.as_ref() != Some(&password) {
+            return Err(ApiError::invalid_params(
+                "Password confirmation doesn't match Password.",
+            ));
+        }
+        account.password = password;
+    }
+    if let Some(email) = update.email {
+        account.unconfirmed_email = Some(email);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Deletes the caller's account and its personal access tokens.
+pub(crate) async fn destroy(State(state): State<SharedState>, authed: Authed) -> StatusCode {
+    let mut state = state.lock().expect("mock state lock");
+    state
+        .accounts
+        .retain(|account| account.user.id != authed.user_id);
+    state
+        .access_tokens
+        .retain(|access_token| access_token.user_id != authed.user_id);
+    StatusCode::NO_CONTENT
+}
@@ -0,0 +1,132 @@
+//! `v1/auth_apps`. Bodies are wrapped as `{ "auth_app": { … } }`.
+
+use {
+    crate::{
+        error::ApiError,
+        extract::Authed,
+        state::{with_rails_timestamps, SharedState},
+    },
+    axum::{
+        extract::{Path, Query, State},
+        http::StatusCode,
+        Json,
+    },
+    chrono::Utc,
+    serde::Deserialize,
+    serde_json::Value,
+    smbcloud_model::app_auth::{AuthApp, AuthAppCreate, AuthAppUpdate},
+};
+
+#[derive(Deserialize)]
+pub(crate) struct AuthAppEnvelope<T> {
+    auth_app: T,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AuthAppQuery {
+    project_id: Option<String>,
+}
+
+pub(crate) async fn index(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Query(query): Query<AuthAppQuery>,
+) -> Json<Vec<Value>> {
+    let state = state.lock().expect("mock state lock");
+    let auth_apps = state
+        .auth_apps
+        .iter()
+        .filter(|auth_app| match &query.project_id {
+            Some(project_id) => auth_app.project_id.as_ref() == Some(project_id),
+            None => true,
+        })
+        .map(auth_app_json)
+        .collect();
+    Json(auth_apps)
+}
+
+pub(crate) async fn create(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Json(body): Json<AuthAppEnvelope<AuthAppCreate>>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let payload = body.auth_app;
+    let mut state = state.lock().expect("mock state lock");
+    if !state
+        .projects
+        .iter()
+        .any(|project| project.id.to_string() == payload.project_id)
+    {
+        return Err(ApiError::not_found("Project"));
+    }
+    let id = state.next_id();
+    let now = Utc::now();
+    let auth_app = AuthApp {
+        id: format!("aa-{id}"),
+        secret: Some(format!("mock-secret-{id}")),
+        name: payload.name,
+        project_id: Some(payload.project_id),
+        support_email: payload.support_email,
+        created_at: now,
+        updated_at: now,
+    };
+    let json = auth_app_json(&auth_app);
+    state.auth_apps.push(auth_app);
+    Ok((StatusCode::CREATED, Json(json)))
+}
+
+pub(crate) async fn show(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    state
+        .auth_apps
+        .iter()
+        .find(|auth_app| auth_app.id == id)
+        .map(|auth_app| Json(auth_app_json(auth_app)))
+        .ok_or_else(|| ApiError::not_found("Auth app"))
+}
+
+pub(crate) async fn update(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<String>,
+    Json(body): Json<AuthAppEnvelope<AuthAppUpdate>>,
+) -> Result<Json<Value>, ApiError> {
+    let payload = body.auth_app;
+    let mut state = state.lock().expect("mock state lock");
+    let auth_app = state
+        .auth_apps
+        .iter_mut()
+        .find(|auth_app| auth_app.id == id)
+        .ok_or_else(|| ApiError::not_found("Auth app"))?;
+    if let Some(name) = payload.name {
+        auth_app.name = name;
+    }
+    if let Some(support_email) = payload.support_email {
+        auth_app.support_email = Some(support_email);
+    }
+    auth_app.updated_at = Utc::now();
+    Ok(Json(auth_app_json(auth_app)))
+}
+
+pub(crate) async fn destroy(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let index = state
+        .auth_apps
+        .iter()
+        .position(|auth_app| auth_app.id == id)
+        .ok_or_else(|| ApiError::not_found("Auth app"))?;
+    state.auth_apps.remove(index);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn auth_app_json(auth_app: &AuthApp) -> Value {
+    with_rails_timestamps(auth_app, auth_app.created_at, auth_app.updated_at)
+}
@@ -0,0 +1,112 @@
+//! `v1/oauth/device/code` and `v1/oauth/device/token` (RFC 8628). Tests
+//! approve or deny a login through `MockState::approve_device`/`deny_device`.
+
+use {
+    crate::{
+        error::ApiError,
+        state::{MockDeviceAuthorization, SharedState},
+    },
+    axum::{
+        extract::State,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        Form, Json,
+    },
+    chrono::{Duration, Utc},
+    smbcloud_model::device_authorization::{
+        DeviceAuthorization, DeviceToken, DeviceTokenError, DeviceTokenErrorCode,
+        DeviceTokenRequest, DEVICE_CODE_GRANT_TYPE,
+    },
+};
+
+/// How long a device code stays valid.
+const EXPIRES_IN: u64 = 600;
+
+pub(crate) async fn code(State(state): State<SharedState>) -> Json<DeviceAuthorization> {
+    let mut state = state.lock().expect("mock state lock");
+    let id = state.next_id();
+    let device_code = format!("mock-device-{id}");
+    let user_code = format!("MOCK-{id:04}");
+    state.device_authorizations.push(MockDeviceAuthorization {
+        device_code: device_code.clone(),
+        user_code: user_code.clone(),
+        expires_at: Utc::now() + Duration::seconds(EXPIRES_IN as i64),
+        approved_user_id: None,
+        denied: false,
+        last_polled_at: None,
+    });
+    Json(DeviceAuthorization {
+        device_code,
+        verification_uri: "https://smbcloud.xyz/device".to_string(),
+        verification_uri_complete: Some(format!(
+            "https://smbcloud.xyz/device?user_code={user_code}"
+        )),
+        user_code,
+        expires_in: EXPIRES_IN,
+        interval: state.device_poll_interval,
+    })
+}
+
+/// Takes a form-urlencoded body like RFC 8628 §3.4 asks for; a JSON body is
+/// rejected with `415`.
+pub(crate) async fn token(
+    State(state): State<SharedState>,
+    Form(body): Form<DeviceTokenRequest>,
+) -> Result<Response, ApiError> {
+    if body.grant_type != DEVICE_CODE_GRANT_TYPE {
+        return Err(ApiError::invalid_params("Unsupported grant_type."));
+    }
+    if body.client_id.is_empty() {
+        return Err(ApiError::invalid_params("client_id is required."));
+    }
+    let mut state = state.lock().expect("mock state lock");
+    let interval = Duration::seconds(state.device_poll_interval as i64);
+    let now = Utc::now();
+    let index = state
+        .device_authorizations
+        .iter()
+        .position(|device| device.device_code == body.device_code)
+        .ok_or_else(|| ApiError::not_found("Device code"))?;
+    let device = &mut state.device_authorizations[index];
+
+    let too_fast = device
+        .last_polled_at
+        .is_some_and(|last| now - last < interval);
+    device.last_polled_at = Some(now);
+    let outcome = if device.expires_at <= now {
+        Err(DeviceTokenErrorCode::ExpiredToken)
+    } else if device.denied {
+        Err(DeviceTokenErrorCode::AccessDenied)
+    } else if too_fast {
+        Err(DeviceTokenErrorCode::SlowDown)
+    } else {
+        device
+            .approved_user_id
+            .ok_or(DeviceTokenErrorCode::AuthorizationPending)
+    };
+
+    match outcome {
+        Ok(user_id) => {
+            state.device_authorizations.remove(index);
+            let access_token = state
+                .accounts
+                .iter()
+                .find(|account| account.user.id == user_id)
+                .map(|account| account.access_token.clone())
+                .ok_or_else(ApiError::unauthorized)?;
+            Ok(Json(DeviceToken {
+                access_token,
+                token_type: "Bearer".to_string(),
+            })
+            .into_response())
+        }
+        Err(error) => Ok((
+            StatusCode::BAD_REQUEST,
+            Json(DeviceTokenError {
+                error,
+                error_description: None,
+            }),
+        )
+            .into_response()),
+    }
+}
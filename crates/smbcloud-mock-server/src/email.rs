@@ -0,0 +1,115 @@
+//! `v1/email/messages`, the transactional email API the email SDK talks to.
+//! It authenticates with a Mail app API key rather than a user session.
+
+use {
+    crate::{
+        error::ApiError,
+        pagination::paginate,
+        state::{email_status, SharedState},
+    },
+    axum::{
+        extract::{Path, Query, State},
+        http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+        response::IntoResponse,
+        Json,
+    },
+    serde::Deserialize,
+    serde_json::Value,
+    smbcloud_model::pagination::PageRequest,
+};
+
+/// Rejects anything but `Bearer <MOCK_EMAIL_API_KEY>`.
+fn authorize(headers: &HeaderMap) -> Result<(), ApiError> {
+    let expected = format!("Bearer {}", crate::MOCK_EMAIL_API_KEY);
+    match headers.get(AUTHORIZATION) {
+        Some(value) if value.as_bytes() == expected.as_bytes() => Ok(()),
+        _ => Err(ApiError::unauthorized()),
+    }
+}
+
+/// Sends nothing; records the message as `sent`. A repeated
+/// `idempotency_key` returns the first message again.
+pub(crate) async fn send(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    authorize(&headers)?;
+    let mut state = state.lock().expect("mock state lock");
+    let idempotency_key = body["idempotency_key"].as_str();
+    if let Some(message) = idempotency_key.and_then(|key| {
+        state
+            .email_messages
+            .iter()
+            .find(|message| message["idempotency_key"] == key)
+    }) {
+        return Ok((StatusCode::OK, Json(message.clone())));
+    }
+    let to: Vec<&str> = body["to"]
+        .as_array()
+        .map(|to| to.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let mut message = state.add_email_message(
+        body["from"].as_str().unwrap_or_default(),
+        &to,
+        body["subject"].as_str(),
+        "sent",
+    );
+    if let Some(key) = idempotency_key {
+        message["idempotency_key"] = Value::from(key);
+        if let Some(stored) = state.email_messages.last_mut() {
+            *stored = message.clone();
+        }
+    }
+    Ok((StatusCode::CREATED, Json(message)))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct MessagesQuery {
+    status: Option<String>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Newest first, optionally only one delivery status. Paged like the other
+/// list routes, with the cursor in `X-Next-Cursor`.
+pub(crate) async fn index(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(query): Query<MessagesQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&headers)?;
+    let status = query
+        .status
+        .map(|name| email_status(&name).ok_or_else(|| ApiError::invalid_params("Unknown status.")))
+        .transpose()?;
+    let state = state.lock().expect("mock state lock");
+    let messages: Vec<Value> = state
+        .email_messages
+        .iter()
+        .rev()
+        .filter(|message| status.is_none_or(|status| message["status"] == status))
+        .cloned()
+        .collect();
+    let page = PageRequest {
+        cursor: query.cursor,
+        limit: query.limit,
+    };
+    paginate(messages, &page)
+}
+
+pub(crate) async fn show(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    authorize(&headers)?;
+    let state = state.lock().expect("mock state lock");
+    state
+        .email_messages
+        .iter()
+        .find(|message| message["id"] == id.as_str())
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Message"))
+}
@@ -0,0 +1,70 @@
+use {
+    axum::{
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        Json,
+    },
+    smbcloud_model::error_codes::{ErrorCode, ErrorResponse},
+};
+
+/// An error rendered the way the real API renders it:
+/// `{ "error_code": <i32>, "message": "…" }` with a matching status.
+#[derive(Debug)]
+pub(crate) struct ApiError {
+    status: StatusCode,
+    body: ErrorResponse,
+}
+
+impl ApiError {
+    pub(crate) fn new(
+        status: StatusCode,
+        error_code: ErrorCode,
+        message: impl Into<String>,
+    ) -> Self {
+        ApiError {
+            status,
+            body: ErrorResponse::Error {
+                error_code,
+                message: message.into(),
+            },
+        }
+    }
+
+    pub(crate) fn unauthorized() -> Self {
+        let error_code = ErrorCode::Unauthorized;
+        let message = error_code.message(None).to_string();
+        Self::new(StatusCode::UNAUTHORIZED, error_code, message)
+    }
+
+    pub(crate) fn forbidden() -> Self {
+        let error_code = ErrorCode::Forbidden;
+        let message = error_code.message(None).to_string();
+        Self::new(StatusCode::FORBIDDEN, error_code, message)
+    }
+
+    pub(crate) fn not_found(what: &str) -> Self {
+        let error_code = match what {
+            "Project" => ErrorCode::ProjectNotFound,
+            _ => ErrorCode::Unknown,
+        };
+        Self::new(
+            StatusCode::NOT_FOUND,
+            error_code,
+            format!("{what} not found."),
+        )
+    }
+
+    pub(crate) fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::InvalidParams,
+            message,
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
@@ -0,0 +1,87 @@
+use {
+    crate::{error::ApiError, state::SharedState},
+    axum::{
+        extract::FromRequestParts,
+        http::{header::AUTHORIZATION, request::Parts, Method},
+    },
+    smbcloud_model::access_token::{Access, TokenScope},
+};
+
+/// The signed-in caller, resolved from the `Authorization` header.
+/// Rejects with the API's `401 Unauthorized access.` body, or `403` when a
+/// personal access token's scopes don't cover the route.
+pub(crate) struct Authed {
+    pub(crate) user_id: i32,
+}
+
+impl FromRequestParts<SharedState> for Authed {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &SharedState,
+    ) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let access_token = header.strip_prefix("Bearer ").unwrap_or(header);
+        let caller = state
+            .lock()
+            .expect("mock state lock")
+            .authenticate(access_token)
+            .ok_or_else(ApiError::unauthorized)?;
+        if let Some(scopes) = &caller.scopes {
+            if !scopes_allow(scopes, &parts.method, parts.uri.path()) {
+                return Err(ApiError::forbidden());
+            }
+        }
+        Ok(Authed {
+            user_id: caller.user_id,
+        })
+    }
+}
+
+/// What the real API asks of a personal access token on each route: any
+/// `GET` is a read, writing a project's deployments is a deploy, managing
+/// access tokens needs a login session, and everything else is a write.
+fn scopes_allow(scopes: &[TokenScope], method: &Method, path: &str) -> bool {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    if segments.get(1) == Some(&"access_tokens") {
+        return false;
+    }
+    let project_id = match segments.as_slice() {
+        ["v1", "projects", id, ..] => id.parse().ok(),
+        _ => None,
+    };
+    let access = if method == Method::GET {
+        Access::Read
+    } else if matches!(segments.as_slice(), ["v1", "projects", _, "deployment", ..])
+        && (method == Method::POST || method == Method::PUT)
+    {
+        Access::Deploy
+    } else {
+        Access::Write
+    };
+    scopes.iter().any(|scope| scope.permits(access, project_id))
+}
+
+/// The `X-Smbcloud-Tenant-Id` header, when sent.
+pub(crate) struct TenantHeader(pub(crate) Option<i64>);
+
+impl<S: Send + Sync> FromRequestParts<S> for TenantHeader {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.headers.get("X-Smbcloud-Tenant-Id") {
+            None => Ok(TenantHeader(None)),
+            Some(value) => value
+                .to_str()
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(|tenant_id| TenantHeader(Some(tenant_id)))
+                .ok_or_else(|| ApiError::invalid_params("Invalid X-Smbcloud-Tenant-Id.")),
+        }
+    }
+}
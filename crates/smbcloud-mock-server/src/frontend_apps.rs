@@ -0,0 +1,188 @@
+//! `v1/frontend_apps`, their server deploy config and `v1/deploy_repos`.
+
+use {
+    crate::{
+        error::ApiError,
+        extract::Authed,
+        state::{from_json, MockState, SharedState},
+    },
+    axum::{
+        extract::{Path, Query, State},
+        http::StatusCode,
+        Json,
+    },
+    serde::Deserialize,
+    serde_json::json,
+    smbcloud_model::{
+        deploy_config::DeployConfig,
+        deploy_config_update::DeployConfigUpdate,
+        frontend_app::{DeployRepo, DeployRepoCreate, FrontendApp, FrontendAppCreate, RepoKind},
+    },
+};
+
+#[derive(Deserialize)]
+pub(crate) struct FrontendAppQuery {
+    project_id: Option<i32>,
+}
+
+pub(crate) async fn index(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Query(query): Query<FrontendAppQuery>,
+) -> Json<Vec<FrontendApp>> {
+    let state = state.lock().expect("mock state lock");
+    let frontend_apps = state
+        .frontend_apps
+        .iter()
+        .filter(|frontend_app| match query.project_id {
+            Some(project_id) => frontend_app.project_ids.contains(&project_id),
+            None => true,
+        })
+        .cloned()
+        .collect();
+    Json(frontend_apps)
+}
+
+pub(crate) async fn create(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Json(payload): Json<FrontendAppCreate>,
+) -> Result<(StatusCode, Json<FrontendApp>), ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    if !state
+        .projects
+        .iter()
+        .any(|project| project.id == payload.project_id)
+    {
+        return Err(ApiError::not_found("Project"));
+    }
+    let mut frontend_app =
+        state.add_frontend_app(payload.project_id, &payload.name, payload.runner);
+    frontend_app.app_type = payload.app_type;
+    frontend_app.deployment_method = payload.deployment_method;
+    frontend_app.repository = payload.repository;
+    frontend_app.description = payload.description;
+    frontend_app.deploy_repo_id = payload.deploy_repo_id;
+    frontend_app.source_path = payload.source_path;
+    save_frontend_app(&mut state, &frontend_app);
+    Ok((StatusCode::CREATED, Json(frontend_app)))
+}
+
+pub(crate) async fn deploy_config(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<String>,
+) -> Result<Json<DeployConfig>, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    state
+        .deploy_configs
+        .get(&id)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Frontend app"))
+}
+
+/// `PATCH v1/frontend_apps/:id`: only the fields present in the body change.
+pub(crate) async fn update_deploy_config(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<String>,
+    Json(payload): Json<DeployConfigUpdate>,
+) -> Result<Json<DeployConfig>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let deploy_config = state
+        .deploy_configs
+        .get_mut(&id)
+        .ok_or_else(|| ApiError::not_found("Frontend app"))?;
+    if let Some(runner) = payload.runner {
+        deploy_config.runner = from_json(json!(runner));
+    }
+    if let Some(deployment_method) = payload.deployment_method {
+        deploy_config.deployment_method = from_json(json!(deployment_method));
+    }
+    let fields = [
+        (&mut deploy_config.kind, payload.kind),
+        (&mut deploy_config.source_path, payload.source_path),
+        (&mut deploy_config.remote_path, payload.remote_path),
+        (&mut deploy_config.package_manager, payload.package_manager),
+        (&mut deploy_config.pm2_app, payload.pm2_app),
+        (&mut deploy_config.output_path, payload.output_path),
+        (&mut deploy_config.build_command, payload.build_command),
+        (&mut deploy_config.install_command, payload.install_command),
+        (&mut deploy_config.binary_name, payload.binary_name),
+        (&mut deploy_config.build_target, payload.build_target),
+        (&mut deploy_config.shared_lib_path, payload.shared_lib_path),
+    ];
+    for (field, value) in fields {
+        if value.is_some() {
+            *field = value;
+        }
+    }
+    if payload.pm2_env.is_some() {
+        deploy_config.pm2_env = payload.pm2_env;
+    }
+    if payload.port.is_some() {
+        deploy_config.port = payload.port;
+    }
+    Ok(Json(deploy_config.clone()))
+}
+
+/// A single-app repo gets its frontend app created alongside, the way the
+/// API embeds it in the response.
+pub(crate) async fn create_deploy_repo(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Json(payload): Json<DeployRepoCreate>,
+) -> Result<(StatusCode, Json<DeployRepo>), ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    if !state
+        .projects
+        .iter()
+        .any(|project| project.id == payload.project_id)
+    {
+        return Err(ApiError::not_found("Project"));
+    }
+    let id = state.next_id();
+    let frontend_apps = match payload.repo_kind {
+        RepoKind::SingleApp => {
+            let mut frontend_app =
+                state.add_frontend_app(payload.project_id, &payload.name, payload.runner);
+            frontend_app.deploy_repo_id = Some(id);
+            frontend_app.repository = Some(payload.repository.clone());
+            frontend_app.deployment_method = payload.deployment_method;
+            save_frontend_app(&mut state, &frontend_app);
+            Some(vec![frontend_app])
+        }
+        RepoKind::Monorepo => None,
+    };
+    let deploy_repo = DeployRepo {
+        id,
+        name: payload.name,
+        repository: payload.repository,
+        root_path: ".".to_string(),
+        repo_kind: payload.repo_kind,
+        runner: payload.runner,
+        deployment_method: payload.deployment_method,
+        frontend_apps,
+    };
+    state.deploy_repos.push(deploy_repo.clone());
+    Ok((StatusCode::CREATED, Json(deploy_repo)))
+}
+
+/// Writes back a frontend app edited after `add_frontend_app` and mirrors the
+/// fields the deploy config shares with it.
+fn save_frontend_app(state: &mut MockState, frontend_app: &FrontendApp) {
+    if let Some(stored) = state
+        .frontend_apps
+        .iter_mut()
+        .find(|stored| stored.id == frontend_app.id)
+    {
+        *stored = frontend_app.clone();
+    }
+    if let Some(deploy_config) = state.deploy_configs.get_mut(&frontend_app.id) {
+        deploy_config.deployment_method = frontend_app.deployment_method;
+        deploy_config.source_path = frontend_app.source_path.clone();
+        deploy_config.deploy_repo_id = frontend_app.deploy_repo_id;
+        deploy_config.repository = frontend_app.repository.clone();
+    }
+}
@@ -0,0 +1,93 @@
+//! `gresiq/v1/collections/{collection}`, the GresIQ document gateway. It
+//! authenticates with an app key and secret rather than a user session, and
+//! pages with a `next_cursor` in the body instead of `X-Next-Cursor`.
+
+use {
+    crate::{error::ApiError, pagination::paginate, state::SharedState},
+    axum::{
+        extract::{Path, Query, State},
+        http::{HeaderMap, StatusCode},
+        Json,
+    },
+    serde::Deserialize,
+    serde_json::{json, Value},
+    smbcloud_model::pagination::PageRequest,
+};
+
+/// Rejects anything but [`MOCK_GRESIQ_API_KEY`](crate::MOCK_GRESIQ_API_KEY)
+/// with its secret.
+fn authorize(headers: &HeaderMap) -> Result<(), ApiError> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    if header("X-Gresiq-Api-Key") == Some(crate::MOCK_GRESIQ_API_KEY)
+        && header("X-Gresiq-Api-Secret") == Some(crate::MOCK_GRESIQ_API_SECRET)
+    {
+        Ok(())
+    } else {
+        Err(ApiError::unauthorized())
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct UpsertBody {
+    key: Option<String>,
+    doc: Value,
+}
+
+pub(crate) async fn upsert(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(collection): Path<String>,
+    Json(body): Json<UpsertBody>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    authorize(&headers)?;
+    let mut state = state.lock().expect("mock state lock");
+    let document = state.add_document(&collection, body.key.as_deref(), body.doc);
+    Ok((StatusCode::CREATED, Json(document)))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DocumentsQuery {
+    filter: Option<String>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Oldest first. `filter` matches top-level fields of `doc` by equality;
+/// `order` and `dir` are accepted and ignored.
+pub(crate) async fn index(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(collection): Path<String>,
+    Query(query): Query<DocumentsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    authorize(&headers)?;
+    let filter = match query.filter {
+        Some(filter) => serde_json::from_str::<Value>(&filter)
+            .map_err(|_| ApiError::invalid_params("Invalid filter."))?,
+        None => json!({}),
+    };
+    let state = state.lock().expect("mock state lock");
+    let documents: Vec<Value> = state
+        .gresiq_documents
+        .iter()
+        .filter(|document| document["collection"] == collection.as_str())
+        .filter(|document| {
+            filter.as_object().is_none_or(|filter| {
+                filter
+                    .iter()
+                    .all(|(field, value)| &document["doc"][field] == value)
+            })
+        })
+        .cloned()
+        .collect();
+    let page = paginate(
+        documents,
+        &PageRequest {
+            cursor: query.cursor,
+            limit: query.limit,
+        },
+    )?;
+    Ok(Json(
+        json!({ "documents": page.items, "next_cursor": page.next_cursor }),
+    ))
+}
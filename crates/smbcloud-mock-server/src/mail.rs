@@ -0,0 +1,344 @@
+//! `v1/mail_apps`, their inboxes and the inbound messages in each inbox.
+
+use {
+    crate::{
+        error::ApiError,
+        extract::Authed,
+        pagination::{paginate, Paged},
+        state::{from_json, MockState, SharedState},
+    },
+    axum::{
+        extract::{Path, Query, State},
+        http::StatusCode,
+        Json,
+    },
+    chrono::Utc,
+    serde::Deserialize,
+    serde_json::json,
+    smbcloud_model::{
+        mail::{
+            MailApp, MailAppCreate, MailAppUpdate, MailInbox, MailInboxCreate, MailInboxUpdate,
+            MailMessage, MailTestEmailDelivery, MailTestEmailRequest,
+        },
+        pagination::PageRequest,
+    },
+};
+
+const DEFAULT_AWS_REGION: &str = "eu-west-1";
+
+#[derive(Deserialize)]
+pub(crate) struct MailAppEnvelope<T> {
+    mail_app: T,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct MailInboxEnvelope<T> {
+    mail_inbox: T,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct MailAppQuery {
+    project_id: Option<i32>,
+}
+
+pub(crate) async fn index(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Query(query): Query<MailAppQuery>,
+) -> Json<Vec<MailApp>> {
+    let state = state.lock().expect("mock state lock");
+    let mail_apps = state
+        .mail_apps
+        .iter()
+        .filter(|mail_app| match query.project_id {
+            Some(project_id) => mail_app.project_id == project_id,
+            None => true,
+        })
+        .map(|mail_app| with_inboxes(&state, mail_app))
+        .collect();
+    Json(mail_apps)
+}
+
+pub(crate) async fn create(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Json(body): Json<MailAppEnvelope<MailAppCreate>>,
+) -> Result<(StatusCode, Json<MailApp>), ApiError> {
+    let payload = body.mail_app;
+    let mut state = state.lock().expect("mock state lock");
+    let project = payload
+        .project_id
+        .parse::<i32>()
+        .ok()
+        .and_then(|project_id| {
+            state
+                .projects
+                .iter()
+                .find(|project| project.id == project_id)
+        })
+        .ok_or_else(|| ApiError::not_found("Project"))?;
+    let project_id = project.id;
+    let tenant_id = project
+        .tenant_id
+        .unwrap_or_else(|| state.personal_tenant_id());
+    if state
+        .mail_apps
+        .iter()
+        .any(|mail_app| mail_app.domain == payload.domain)
+    {
+        return Err(ApiError::invalid_params("Domain has already been taken."));
+    }
+    let aws_region = payload
+        .aws_region
+        .unwrap_or_else(|| DEFAULT_AWS_REGION.to_string());
+    let mail_app: MailApp = from_json(json!({
+        "id": state.next_id(),
+        "name": payload.name,
+        "domain": payload.domain,
+        "aws_region": aws_region,
+        "project_id": project_id,
+        "tenant_id": tenant_id,
+        "created_at": Utc::now(),
+        "updated_at": Utc::now(),
+        "status": 0,
+        "ses_inbound_mx_value": format!("inbound-smtp.{aws_region}.amazonaws.com"),
+        "inbound_bucket_name": "smbcloud-mail-inbound",
+        "inbound_email_key_prefix": format!("inbound/{}/", payload.domain),
+    }));
+    state.mail_apps.push(mail_app.clone());
+    Ok((StatusCode::CREATED, Json(mail_app)))
+}
+
+pub(crate) async fn show(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i32>,
+) -> Result<Json<MailApp>, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    state
+        .mail_apps
+        .iter()
+        .find(|mail_app| mail_app.id == id)
+        .map(|mail_app| Json(with_inboxes(&state, mail_app)))
+        .ok_or_else(|| ApiError::not_found("Mail app"))
+}
+
+pub(crate) async fn update(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i32>,
+    Json(body): Json<MailAppEnvelope<MailAppUpdate>>,
+) -> Result<Json<MailApp>, ApiError> {
+    let payload = body.mail_app;
+    let mut state = state.lock().expect("mock state lock");
+    let mail_app = state
+        .mail_apps
+        .iter_mut()
+        .find(|mail_app| mail_app.id == id)
+        .ok_or_else(|| ApiError::not_found("Mail app"))?;
+    if let Some(name) = payload.name {
+        mail_app.name = name;
+    }
+    if let Some(domain) = payload.domain {
+        mail_app.domain = domain;
+    }
+    if let Some(aws_region) = payload.aws_region {
+        mail_app.aws_region = aws_region;
+    }
+    mail_app.updated_at = Utc::now();
+    let mail_app = mail_app.clone();
+    Ok(Json(with_inboxes(&state, &mail_app)))
+}
+
+pub(crate) async fn destroy(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let index = state
+        .mail_apps
+        .iter()
+        .position(|mail_app| mail_app.id == id)
+        .ok_or_else(|| ApiError::not_found("Mail app"))?;
+    state.mail_apps.remove(index);
+    let inbox_ids: Vec<i32> = state
+        .mail_inboxes
+        .iter()
+        .filter(|inbox| inbox.mail_app_id == id)
+        .map(|inbox| inbox.id)
+        .collect();
+    state.mail_inboxes.retain(|inbox| inbox.mail_app_id != id);
+    state
+        .mail_messages
+        .retain(|message| !inbox_ids.contains(&message.mail_inbox_id));
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(crate) async fn create_inbox(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(mail_app_id): Path<i32>,
+    Json(body): Json<MailInboxEnvelope<MailInboxCreate>>,
+) -> Result<(StatusCode, Json<MailInbox>), ApiError> {
+    let payload = body.mail_inbox;
+    let mut state = state.lock().expect("mock state lock");
+    let mail_app = state
+        .mail_apps
+        .iter()
+        .find(|mail_app| mail_app.id == mail_app_id)
+        .cloned()
+        .ok_or_else(|| ApiError::not_found("Mail app"))?;
+    let full_address = format!("{}@{}", payload.local_part, mail_app.domain);
+    if state
+        .mail_inboxes
+        .iter()
+        .any(|inbox| inbox.full_address == full_address)
+    {
+        return Err(ApiError::invalid_params(
+            "Local part has already been taken.",
+        ));
+    }
+    let inbox: MailInbox = from_json(json!({
+        "id": state.next_id(),
+        "local_part": payload.local_part,
+        "full_address": full_address,
+        "inbox_email": full_address,
+        "sender_email": payload.sender_email.unwrap_or_else(|| full_address.clone()),
+        "forward_to_email": payload.forward_to_email,
+        "mail_app_id": mail_app.id,
+        "project_id": mail_app.project_id,
+        "tenant_id": mail_app.tenant_id,
+        "created_at": Utc::now(),
+        "updated_at": Utc::now(),
+        "status": 0,
+        "email_key_prefix": format!("{}{}/", mail_app.inbound_email_key_prefix, payload.local_part),
+    }));
+    state.mail_inboxes.push(inbox.clone());
+    Ok((StatusCode::CREATED, Json(inbox)))
+}
+
+pub(crate) async fn update_inbox(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path((mail_app_id, id)): Path<(i32, i32)>,
+    Json(body): Json<MailInboxEnvelope<MailInboxUpdate>>,
+) -> Result<Json<MailInbox>, ApiError> {
+    let payload = body.mail_inbox;
+    let mut state = state.lock().expect("mock state lock");
+    let domain = state
+        .mail_apps
+        .iter()
+        .find(|mail_app| mail_app.id == mail_app_id)
+        .map(|mail_app| mail_app.domain.clone())
+        .ok_or_else(|| ApiError::not_found("Mail app"))?;
+    let inbox = find_inbox_mut(&mut state, mail_app_id, id)?;
+    if let Some(local_part) = payload.local_part {
+        inbox.full_address = format!("{local_part}@{domain}");
+        inbox.inbox_email = inbox.full_address.clone();
+        inbox.local_part = local_part;
+    }
+    if let Some(forward_to_email) = payload.forward_to_email {
+        inbox.forward_to_email = forward_to_email;
+    }
+    if let Some(sender_email) = payload.sender_email {
+        inbox.sender_email = sender_email;
+    }
+    inbox.updated_at = Utc::now();
+    Ok(Json(inbox.clone()))
+}
+
+pub(crate) async fn destroy_inbox(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path((mail_app_id, id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    find_inbox_mut(&mut state, mail_app_id, id)?;
+    state.mail_inboxes.retain(|inbox| inbox.id != id);
+    state
+        .mail_messages
+        .retain(|message| message.mail_inbox_id != id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Nothing is sent; the delivery is recorded on the inbox and echoed back.
+pub(crate) async fn send_test_email(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path((mail_app_id, id)): Path<(i32, i32)>,
+    Json(body): Json<MailInboxEnvelope<MailTestEmailRequest>>,
+) -> Result<Json<MailTestEmailDelivery>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let message_id = format!("mock-test-{}", state.next_id());
+    let inbox = find_inbox_mut(&mut state, mail_app_id, id)?;
+    let now = Utc::now();
+    inbox.last_test_email_sent_at = Some(now);
+    Ok(Json(MailTestEmailDelivery {
+        message_id,
+        recipient_email: body
+            .mail_inbox
+            .recipient_email
+            .unwrap_or_else(|| inbox.full_address.clone()),
+        delivery_method: "mock".to_string(),
+        sent_at: now.to_rfc3339(),
+    }))
+}
+
+/// Newest first, capped at `limit`.
+pub(crate) async fn messages(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path((mail_app_id, inbox_id)): Path<(i32, i32)>,
+    Query(page): Query<PageRequest>,
+) -> Result<Paged<MailMessage>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    find_inbox_mut(&mut state, mail_app_id, inbox_id)?;
+    let messages = state
+        .mail_messages
+        .iter()
+        .rev()
+        .filter(|message| message.mail_inbox_id == inbox_id)
+        .cloned()
+        .collect();
+    paginate(messages, &page)
+}
+
+pub(crate) async fn message(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path((mail_app_id, inbox_id, id)): Path<(i32, i32, i32)>,
+) -> Result<Json<MailMessage>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    find_inbox_mut(&mut state, mail_app_id, inbox_id)?;
+    state
+        .mail_messages
+        .iter()
+        .find(|message| message.mail_inbox_id == inbox_id && message.id == id)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Mail message"))
+}
+
+fn with_inboxes(state: &MockState, mail_app: &MailApp) -> MailApp {
+    let mut mail_app = mail_app.clone();
+    mail_app.inboxes = state
+        .mail_inboxes
+        .iter()
+        .filter(|inbox| inbox.mail_app_id == mail_app.id)
+        .cloned()
+        .collect();
+    mail_app
+}
+
+fn find_inbox_mut(
+    state: &mut MockState,
+    mail_app_id: i32,
+    id: i32,
+) -> Result<&mut MailInbox, ApiError> {
+    state
+        .mail_inboxes
+        .iter_mut()
+        .find(|inbox| inbox.mail_app_id == mail_app_id && inbox.id == id)
+        .ok_or_else(|| ApiError::not_found("Mail inbox"))
+}
@@ -0,0 +1,31 @@
+//! Run the mock API standalone so the CLI can be pointed at it:
+//!
+//! ```sh
+//! cargo run -p smbcloud-mock-server -- 4010
+//! SMB_API_URL=http://127.0.0.1:4010 smb -e custom login
+//! ```
+
+use {
+    smbcloud_mock_server::{MockServer, MockState, MOCK_EMAIL, MOCK_PASSWORD},
+    std::net::SocketAddr,
+};
+
+const DEFAULT_PORT: u16 = 4010;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let port = std::env::args()
+        .nth(1)
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+    let server = MockServer::start_on(
+        SocketAddr::from(([127, 0, 0, 1], port)),
+        MockState::default(),
+    )
+    .await?;
+
+    println!("SMB_API_URL={}", server.url());
+    println!("Sign in as {MOCK_EMAIL} / {MOCK_PASSWORD}");
+    std::future::pending::<()>().await;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Cursor paging for list routes, the way the real API does it: the body
+//! stays a bare array and the next cursor rides in `X-Next-Cursor`.
+
+use {
+    crate::error::ApiError,
+    axum::{
+        http::HeaderValue,
+        response::{IntoResponse, Response},
+        Json,
+    },
+    serde::Serialize,
+    smbcloud_model::pagination::{PageRequest, NEXT_CURSOR_HEADER},
+};
+
+/// Largest page any list route hands out.
+const MAX_LIMIT: u32 = 100;
+
+pub(crate) struct Paged<T> {
+    pub(crate) items: Vec<T>,
+    pub(crate) next_cursor: Option<String>,
+}
+
+/// Cuts one page out of `items`. The cursor is the offset of the next record.
+/// Without a `limit` everything after the cursor comes back in one page.
+pub(crate) fn paginate<T>(items: Vec<T>, page: &PageRequest) -> Result<Paged<T>, ApiError> {
+    let offset = match &page.cursor {
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .map_err(|_| ApiError::invalid_params("Invalid cursor."))?,
+        None => 0,
+    };
+    let total = items.len();
+    let limit = page
+        .limit
+        .map(|limit| limit.clamp(1, MAX_LIMIT) as usize)
+        .unwrap_or(usize::MAX);
+    let items: Vec<T> = items.into_iter().skip(offset).take(limit).collect();
+    let end = offset + items.len();
+    Ok(Paged {
+        next_cursor: (end < total).then(|| end.to_string()),
+        items,
+    })
+}
+
+impl<T: Serialize> IntoResponse for Paged<T> {
+    fn into_response(self) -> Response {
+        let mut response = Json(self.items).into_response();
+        if let Some(cursor) = self
+            .next_cursor
+            .and_then(|c| HeaderValue::from_str(&c).ok())
+        {
+            response.headers_mut().insert(NEXT_CURSOR_HEADER, cursor);
+        }
+        response
+    }
+}
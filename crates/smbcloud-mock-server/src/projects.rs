@@ -0,0 +1,231 @@
+//! `v1/projects` and the nested `v1/projects/:id/deployment` routes.
+
+use {
+    crate::{
+        error::ApiError,
+        extract::{Authed, TenantHeader},
+        pagination::{paginate, Paged},
+        state::{with_rails_timestamps, SharedState},
+    },
+    axum::{
+        extract::{Path, Query, State},
+        http::StatusCode,
+        Json,
+    },
+    chrono::Utc,
+    serde::Deserialize,
+    serde_json::Value,
+    smbcloud_model::{
+        pagination::PageRequest,
+        project::{Deployment, DeploymentPayload, Project, ProjectCreate},
+        runner::Runner,
+    },
+};
+
+#[derive(Deserialize)]
+pub(crate) struct ProjectUpdate {
+    description: Option<String>,
+    runner: Option<Runner>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeploymentQuery {
+    environment: Option<String>,
+}
+
+pub(crate) async fn index(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Query(page): Query<PageRequest>,
+) -> Result<Paged<Project>, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    paginate(state.projects.clone(), &page)
+}
+
+pub(crate) async fn create(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    TenantHeader(tenant_id): TenantHeader,
+    Json(payload): Json<ProjectCreate>,
+) -> Result<(StatusCode, Json<Project>), ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::invalid_params("Name can't be blank."));
+    }
+    if let Some(tenant_id) = tenant_id {
+        if !state.tenants.iter().any(|tenant| tenant.id == tenant_id) {
+            return Err(ApiError::not_found("Tenant"));
+        }
+    }
+    let project = state.add_project(&payload.name, &payload.description, tenant_id);
+    Ok((StatusCode::CREATED, Json(project)))
+}
+
+pub(crate) async fn show(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i32>,
+) -> Result<Json<Project>, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    state
+        .projects
+        .iter()
+        .find(|project| project.id == id)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Project"))
+}
+
+pub(crate) async fn update(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i32>,
+    Json(payload): Json<ProjectUpdate>,
+) -> Result<Json<Project>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let project = state
+        .projects
+        .iter_mut()
+        .find(|project| project.id == id)
+        .ok_or_else(|| ApiError::not_found("Project"))?;
+    if let Some(description) = payload.description {
+        project.description = Some(description);
+    }
+    if let Some(runner) = payload.runner {
+        project.runner = runner;
+    }
+    project.updated_at = Utc::now();
+    Ok(Json(project.clone()))
+}
+
+pub(crate) async fn destroy(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let index = state
+        .projects
+        .iter()
+        .position(|project| project.id == id)
+        .ok_or_else(|| ApiError::not_found("Project"))?;
+    let project = state.projects.remove(index);
+    state
+        .deployments
+        .retain(|deployment| deployment.project_id != id);
+    if let Some(tenant) = state
+        .tenants
+        .iter_mut()
+        .find(|tenant| Some(tenant.id) == project.tenant_id)
+    {
+        tenant.projects_count -= 1;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Newest first, optionally narrowed to one deploy environment.
+pub(crate) async fn deployments(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(project_id): Path<i32>,
+    Query(query): Query<DeploymentQuery>,
+    Query(page): Query<PageRequest>,
+) -> Result<Paged<Value>, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    if !state
+        .projects
+        .iter()
+        .any(|project| project.id == project_id)
+    {
+        return Err(ApiError::not_found("Project"));
+    }
+    let deployments = state
+        .deployments
+        .iter()
+        .rev()
+        .filter(|deployment| deployment.project_id == project_id)
+        .filter(|deployment| match &query.environment {
+            Some(environment) => deployment.environment.as_ref() == Some(environment),
+            None => true,
+        })
+        .map(deployment_json)
+        .collect();
+    paginate(deployments, &page)
+}
+
+pub(crate) async fn create_deployment(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(project_id): Path<i32>,
+    Json(payload): Json<DeploymentPayload>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    if !state
+        .projects
+        .iter()
+        .any(|project| project.id == project_id)
+    {
+        return Err(ApiError::not_found("Project"));
+    }
+    let frontend_app_name = payload
+        .frontend_app_id
+        .as_ref()
+        .and_then(|frontend_app_id| {
+            state
+                .frontend_apps
+                .iter()
+                .find(|frontend_app| &frontend_app.id == frontend_app_id)
+                .map(|frontend_app| frontend_app.name.clone())
+        });
+    let now = Utc::now();
+    let deployment = Deployment {
+        id: state.next_id() as i32,
+        project_id,
+        frontend_app_id: payload.frontend_app_id,
+        frontend_app_name,
+        commit_hash: payload.commit_hash,
+        status: payload.status,
+        environment: payload.environment,
+        created_at: now,
+        updated_at: now,
+    };
+    let json = deployment_json(&deployment);
+    state.deployments.push(deployment);
+    Ok((StatusCode::CREATED, Json(json)))
+}
+
+pub(crate) async fn show_deployment(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path((project_id, id)): Path<(i32, i32)>,
+) -> Result<Json<Value>, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    state
+        .deployments
+        .iter()
+        .find(|deployment| deployment.project_id == project_id && deployment.id == id)
+        .map(|deployment| Json(deployment_json(deployment)))
+        .ok_or_else(|| ApiError::not_found("Deployment"))
+}
+
+pub(crate) async fn update_deployment(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path((project_id, id)): Path<(i32, i32)>,
+    Json(payload): Json<DeploymentPayload>,
+) -> Result<Json<Value>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let deployment = state
+        .deployments
+        .iter_mut()
+        .find(|deployment| deployment.project_id == project_id && deployment.id == id)
+        .ok_or_else(|| ApiError::not_found("Deployment"))?;
+    deployment.status = payload.status;
+    deployment.commit_hash = payload.commit_hash;
+    deployment.updated_at = Utc::now();
+    Ok(Json(deployment_json(deployment)))
+}
+
+fn deployment_json(deployment: &Deployment) -> Value {
+    with_rails_timestamps(deployment, deployment.created_at, deployment.updated_at)
+}
@@ -0,0 +1,652 @@
+//! In-process mock of the smbCloud v1 API.
+//!
+//! [`MockServer::start`] binds an ephemeral loopback port and serves the routes
+//! the CLI and the networking crates call: sign-in, sign-up, `me`, account
+//! updates and deletion, two-factor authentication, the email lookup, the
+//! Google sign-in exchange, projects,
+//! frontend apps and their deploy config, deployments, tenants, mail apps/inboxes/
+//! messages, auth apps, personal access tokens (whose scopes are enforced)
+//! and device-code login, plus the transactional email API and the GresIQ
+//! document gateway the SDKs call with their own API keys. State lives in memory ([`MockState`]) and starts
+//! with one account ([`MOCK_EMAIL`] / [`MOCK_ACCESS_TOKEN`]) and its personal
+//! tenant; tests seed anything else through [`MockServer::state`].
+//!
+//! Point a client at it with `Environment::Custom` and
+//! `SMB_API_URL=<MockServer::url()>` ([`MockServer::shared`] sets that up
+//! once per test binary), or pass the URL to an SDK's `with_base_url`
+//! constructor. `client_id`/`client_secret` query params are
+//! accepted and ignored.
+
+mod access_tokens;
+mod account;
+mod auth_apps;
+mod device;
+mod email;
+mod error;
+mod extract;
+mod frontend_apps;
+mod gresiq;
+mod mail;
+mod pagination;
+mod projects;
+pub mod state;
+mod tenants;
+mod two_factor;
+
+pub use state::{
+    Caller, MockAccessToken, MockAccount, MockDeviceAuthorization, MockState, MockTwoFactor,
+    MOCK_ACCESS_TOKEN, MOCK_EMAIL, MOCK_EMAIL_API_KEY, MOCK_GRESIQ_API_KEY, MOCK_GRESIQ_API_SECRET,
+    MOCK_OTP, MOCK_PASSWORD,
+};
+
+use {
+    axum::{
+        routing::{delete, get, patch, post, put},
+        Router,
+    },
+    state::SharedState,
+    std::{
+        net::SocketAddr,
+        sync::{mpsc, Arc, Mutex, MutexGuard, OnceLock},
+        thread,
+    },
+    tokio::{net::TcpListener, sync::oneshot},
+};
+
+/// The variable `Environment::Custom` reads its API URL from, as
+/// `smbcloud_network::environment::API_URL_ENV` spells it.
+const API_URL_ENV: &str = "SMB_API_URL";
+
+/// A running mock API. Shuts down when dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    state: SharedState,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Start on an ephemeral port on 127.0.0.1 with a fresh [`MockState`].
+    /// Must be called inside a tokio runtime.
+    pub async fn start() -> std::io::Result<Self> {
+        Self::start_on(SocketAddr::from(([127, 0, 0, 1], 0)), MockState::default()).await
+    }
+
+    /// Start on `address` with pre-seeded `state`.
+    pub async fn start_on(address: SocketAddr, state: MockState) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(state));
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+        let app = router(state.clone());
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_signal.await;
+                })
+                .await;
+        });
+        Ok(MockServer {
+            address,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// One server for the whole test binary, with `SMB_API_URL` pointing at
+    /// it. Clients that find the API through that process-wide variable can
+    /// only ever talk to one server, so their tests share this one and seed
+    /// accounts of their own. It runs on a thread of its own, outliving each
+    /// test's runtime, and never shuts down.
+    pub fn shared() -> &'static MockServer {
+        static SHARED: OnceLock<MockServer> = OnceLock::new();
+        SHARED.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("mock server runtime");
+                runtime.block_on(async {
+                    let server = MockServer::start().await.expect("mock server");
+                    let _ = sender.send(MockServer {
+                        address: server.address,
+                        state: server.state.clone(),
+                        shutdown: None,
+                    });
+                    std::future::pending::<()>().await
+                })
+            });
+            let server = receiver.recv().expect("mock server thread");
+            std::env::set_var(API_URL_ENV, server.url());
+            server
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Base URL to use as `SMB_API_URL`, e.g. `http://127.0.0.1:49152`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Lock the in-memory state to seed or inspect it. Don't hold the guard
+    /// across a request to the server.
+    pub fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().expect("mock state lock")
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/v1/users/sign_in", post(account::sign_in))
+        .route("/v1/users/sign_out", delete(account::sign_out))
+        .route("/v1/me", get(account::me))
+        .route("/v1/check_email", get(account::check_email))
+        .route("/v1/authorize/google", post(account::authorize_google))
+        .route(
+            "/v1/users",
+            post(account::sign_up)
+                .put(account::update)
+                .delete(account::destroy),
+        )
+        .route(
+            "/v1/users/two_factor",
+            post(two_factor::setup)
+                .put(two_factor::enable)
+                .delete(two_factor::disable),
+        )
+        .route(
+            "/v1/users/two_factor/recovery_codes",
+            post(two_factor::recovery_codes),
+        )
+        .route(
+            "/v1/client/me/two_factor",
+            post(two_factor::setup)
+                .put(two_factor::enable)
+                .delete(two_factor::disable),
+        )
+        .route(
+            "/v1/client/me/two_factor/recovery_codes",
+            post(two_factor::recovery_codes),
+        )
+        .route("/v1/oauth/device/code", post(device::code))
+        .route("/v1/oauth/device/token", post(device::token))
+        .route(
+            "/v1/access_tokens",
+            get(access_tokens::index).post(access_tokens::create),
+        )
+        .route("/v1/access_tokens/{id}", delete(access_tokens::destroy))
+        .route("/v1/projects", get(projects::index).post(projects::create))
+        .route(
+            "/v1/projects/{id}",
+            get(projects::show)
+                .patch(projects::update)
+                .delete(projects::destroy),
+        )
+        .route(
+            "/v1/projects/{id}/deployment",
+            get(projects::deployments).post(projects::create_deployment),
+        )
+        .route(
+            "/v1/projects/{id}/deployment/{deployment_id}",
+            get(projects::show_deployment).put(projects::update_deployment),
+        )
+        .route(
+            "/v1/frontend_apps",
+            get(frontend_apps::index).post(frontend_apps::create),
+        )
+        .route(
+            "/v1/frontend_apps/{id}",
+            patch(frontend_apps::update_deploy_config),
+        )
+        .route(
+            "/v1/frontend_apps/{id}/deploy_config",
+            get(frontend_apps::deploy_config),
+        )
+        .route("/v1/deploy_repos", post(frontend_apps::create_deploy_repo))
+        .route("/v1/tenants", get(tenants::index).post(tenants::create))
+        .route(
+            "/v1/tenants/{id}",
+            get(tenants::show)
+                .patch(tenants::update)
+                .delete(tenants::destroy),
+        )
+        .route("/v1/mail_apps", get(mail::index).post(mail::create))
+        .route(
+            "/v1/mail_apps/{id}",
+            get(mail::show).put(mail::update).delete(mail::destroy),
+        )
+        .route("/v1/mail_apps/{id}/inboxes", post(mail::create_inbox))
+        .route(
+            "/v1/mail_apps/{id}/inboxes/{inbox_id}",
+            put(mail::update_inbox).delete(mail::destroy_inbox),
+        )
+        .route(
+            "/v1/mail_apps/{id}/inboxes/{inbox_id}/send_test_email",
+            post(mail::send_test_email),
+        )
+        .route(
+            "/v1/mail_apps/{id}/inboxes/{inbox_id}/messages",
+            get(mail::messages),
+        )
+        .route(
+            "/v1/mail_apps/{id}/inboxes/{inbox_id}/messages/{message_id}",
+            get(mail::message),
+        )
+        .route("/v1/email/messages", get(email::index).post(email::send))
+        .route("/v1/email/messages/{id}", get(email::show))
+        .route(
+            "/gresiq/v1/collections/{collection}",
+            get(gresiq::index).post(gresiq::upsert),
+        )
+        .route(
+            "/v1/auth_apps",
+            get(auth_apps::index).post(auth_apps::create),
+        )
+        .route(
+            "/v1/auth_apps/{id}",
+            get(auth_apps::show)
+                .put(auth_apps::update)
+                .delete(auth_apps::destroy),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        reqwest::{Client, StatusCode},
+        serde_json::{json, Value},
+        smbcloud_model::{
+            access_token::CreatedAccessToken,
+            account::User,
+            app_auth::AuthApp,
+            deploy_config::DeployConfig,
+            error_codes::{ErrorCode, ErrorResponse},
+            mail::{MailApp, MailInbox, MailMessage},
+            pagination::NEXT_CURSOR_HEADER,
+            project::{Deployment, Project},
+            runner::Runner,
+            tenant::Tenant,
+        },
+    };
+
+    fn authed(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        builder.header("Authorization", format!("Bearer {MOCK_ACCESS_TOKEN}"))
+    }
+
+    #[tokio::test]
+    async fn me_requires_a_known_token() {
+        let server = MockServer::start().await.unwrap();
+        let client = Client::new();
+        let url = format!("{}/v1/me?client_id=cli&client_secret=x", server.url());
+
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let ErrorResponse::Error { error_code, .. } = response.json().await.unwrap();
+        assert!(matches!(error_code, ErrorCode::Unauthorized));
+
+        let user: User = authed(client.get(&url))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(user.email, MOCK_EMAIL);
+    }
+
+    #[tokio::test]
+    async fn sign_in_returns_the_token_in_the_authorization_header() {
+        let server = MockServer::start().await.unwrap();
+        let url = format!("{}/v1/users/sign_in", server.url());
+        let body = json!({ "user": { "email": MOCK_EMAIL, "password": MOCK_PASSWORD } });
+
+        let response = Client::new().post(&url).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["Authorization"],
+            format!("Bearer {MOCK_ACCESS_TOKEN}").as_str()
+        );
+
+        let body = json!({ "user": { "email": "nobody@example.com", "password": "x" } });
+        let response = Client::new().post(&url).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn two_factor_sign_in_needs_a_code_once_enabled() {
+        let server = MockServer::start().await.unwrap();
+        let client = Client::new();
+        let two_factor = format!("{}/v1/users/two_factor", server.url());
+        let sign_in = format!("{}/v1/users/sign_in", server.url());
+        let otp = |code: &str| json!({ "otp_attempt": code });
+        let error_code = |body: Value| body["error_code"].as_i64();
+
+        let response = authed(client.post(&two_factor)).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = authed(client.put(&two_factor))
+            .json(&otp("000000"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let codes: Value = authed(client.put(&two_factor))
+            .json(&otp(MOCK_OTP))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let recovery_code = codes["recovery_codes"][0].as_str().unwrap().to_string();
+
+        let body = json!({ "user": { "email": MOCK_EMAIL, "password": MOCK_PASSWORD } });
+        let response = client.post(&sign_in).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_code(response.json().await.unwrap()), Some(1009));
+
+        let mut body = body;
+        body["user"]["otp_attempt"] = json!("000000");
+        let response = client.post(&sign_in).json(&body).send().await.unwrap();
+        assert_eq!(error_code(response.json().await.unwrap()), Some(1010));
+
+        // A recovery code logs in once.
+        body["user"]["otp_attempt"] = json!(recovery_code);
+        let response = client.post(&sign_in).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = client.post(&sign_in).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = authed(client.delete(&two_factor))
+            .json(&otp(MOCK_OTP))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        body["user"]["otp_attempt"] = json!(null);
+        let response = client.post(&sign_in).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn project_deploy_token_can_deploy_but_not_delete() {
+        let server = MockServer::start().await.unwrap();
+        let client = Client::new();
+        let base = server.url();
+        let project = server.state().add_project("web", "", None);
+        let other = server.state().add_project("api", "", None);
+
+        let created: CreatedAccessToken = authed(client.post(format!("{base}/v1/access_tokens")))
+            .json(&json!({ "access_token": {
+                    "name": "ci",
+                    "scopes": [format!("deploy:{}", project.id)],
+                    "expires_at": null,
+                } }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let with_token = |builder: reqwest::RequestBuilder| {
+            builder.header("Authorization", created.token.as_str())
+        };
+        let deployment = json!({ "commit_hash": "abc123", "status": 0 });
+
+        let response =
+            with_token(client.post(format!("{base}/v1/projects/{}/deployment", project.id)))
+                .json(&deployment)
+                .send()
+                .await
+                .unwrap();
+        assert!(response.status().is_success());
+
+        for request in [
+            client.post(format!("{base}/v1/projects/{}/deployment", other.id)),
+            client.delete(format!("{base}/v1/projects/{}", project.id)),
+            client.get(format!("{base}/v1/access_tokens")),
+        ] {
+            let response = with_token(request).json(&deployment).send().await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+
+        let response = authed(client.delete(format!(
+            "{base}/v1/access_tokens/{}",
+            created.access_token.id
+        )))
+        .send()
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = with_token(client.get(format!("{base}/v1/me")))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn projects_page_through_the_next_cursor_header() {
+        let server = MockServer::start().await.unwrap();
+        for name in ["a", "b", "c"] {
+            server.state().add_project(name, "", None);
+        }
+        let url = format!("{}/v1/projects", server.url());
+
+        let response = authed(Client::new().get(&url).query(&[("limit", "2")]))
+            .send()
+            .await
+            .unwrap();
+        let cursor = response.headers()[NEXT_CURSOR_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let first: Vec<Project> = response.json().await.unwrap();
+        assert_eq!(first.len(), 2);
+
+        let response = authed(
+            Client::new()
+                .get(&url)
+                .query(&[("limit", "2"), ("cursor", cursor.as_str())]),
+        )
+        .send()
+        .await
+        .unwrap();
+        assert!(response.headers().get(NEXT_CURSOR_HEADER).is_none());
+        let last: Vec<Project> = response.json().await.unwrap();
+        assert_eq!(last[0].name, "c");
+    }
+
+    #[tokio::test]
+    async fn deploy_flow_round_trips_through_the_models() {
+        let server = MockServer::start().await.unwrap();
+        let client = Client::new();
+        let base = server.url();
+
+        let project: Project = authed(client.post(format!("{base}/v1/projects")))
+            .json(&json!({ "name": "web", "description": "Web app" }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let frontend_app_id = server
+            .state()
+            .add_frontend_app(project.id, "web", Runner::NodeJs)
+            .id;
+
+        let deploy_config: DeployConfig =
+            authed(client.patch(format!("{base}/v1/frontend_apps/{frontend_app_id}")))
+                .json(&json!({ "remote_path": "apps/web/web", "port": 3001 }))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+        assert_eq!(deploy_config.remote_path.as_deref(), Some("apps/web/web"));
+        assert_eq!(deploy_config.port, Some(3001));
+
+        let deployments_url = format!("{base}/v1/projects/{}/deployment", project.id);
+        for environment in [None, Some("staging")] {
+            let created: Deployment = authed(client.post(&deployments_url))
+                .json(&json!({
+                    "commit_hash": "abc123",
+                    "status": 0,
+                    "frontend_app_id": frontend_app_id,
+                    "environment": environment,
+                }))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            assert_eq!(created.frontend_app_name.as_deref(), Some("web"));
+        }
+
+        let staging: Vec<Deployment> = authed(client.get(&deployments_url))
+            .query(&[("environment", "staging")])
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(staging.len(), 1);
+        assert_eq!(staging[0].environment.as_deref(), Some("staging"));
+        assert_eq!(server.state().deployments.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn personal_tenant_cannot_be_deleted() {
+        let server = MockServer::start().await.unwrap();
+        let client = Client::new();
+        let base = server.url();
+
+        let tenants: Vec<Tenant> = authed(client.get(format!("{base}/v1/tenants")))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let response = authed(client.delete(format!("{base}/v1/tenants/{}", tenants[0].id)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let acme: Tenant = authed(client.post(format!("{base}/v1/tenants")))
+            .json(&json!({ "tenant": { "name": "Acme Corp" } }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(acme.slug, "acme-corp");
+        let response = authed(client.delete(format!("{base}/v1/tenants/{}", acme.id)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn mail_messages_are_listed_newest_first() {
+        let server = MockServer::start().await.unwrap();
+        let client = Client::new();
+        let base = server.url();
+        let project = server.state().add_project("mail", "", None);
+
+        let mail_app: MailApp = authed(client.post(format!("{base}/v1/mail_apps")))
+            .json(&json!({ "mail_app": {
+                "name": "Mail",
+                "project_id": project.id.to_string(),
+                "domain": "example.com",
+            } }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let inbox: MailInbox =
+            authed(client.post(format!("{base}/v1/mail_apps/{}/inboxes", mail_app.id)))
+                .json(&json!({ "mail_inbox": {
+                    "local_part": "hello",
+                    "forward_to_email": "me@example.org",
+                } }))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+        assert_eq!(inbox.full_address, "hello@example.com");
+
+        for subject in ["first", "second"] {
+            server
+                .state()
+                .add_mail_message(inbox.id, "sender@example.org", subject, "Hi")
+                .unwrap();
+        }
+        let messages: Vec<MailMessage> = authed(client.get(format!(
+            "{base}/v1/mail_apps/{}/inboxes/{}/messages",
+            mail_app.id, inbox.id
+        )))
+        .query(&[("limit", "1")])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].subject.as_deref(), Some("second"));
+    }
+
+    #[tokio::test]
+    async fn auth_apps_use_rails_timestamps() {
+        let server = MockServer::start().await.unwrap();
+        let client = Client::new();
+        let base = server.url();
+        let project = server.state().add_project("auth", "", None);
+
+        let created: Value = authed(client.post(format!("{base}/v1/auth_apps")))
+            .json(&json!({ "auth_app": { "name": "Login", "project_id": project.id.to_string() } }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let auth_app: AuthApp = serde_json::from_value(created).unwrap();
+        assert!(auth_app.secret.is_some());
+
+        let listed: Vec<AuthApp> = authed(client.get(format!("{base}/v1/auth_apps")))
+            .query(&[("project_id", project.id.to_string())])
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, auth_app.id);
+    }
+}
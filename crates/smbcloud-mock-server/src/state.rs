@@ -0,0 +1,480 @@
+use {
+    chrono::{DateTime, Utc},
+    serde::de::DeserializeOwned,
+    serde_json::{json, Value},
+    smbcloud_model::{
+        access_token::{AccessToken, CreatedAccessToken, TokenScope},
+        account::User,
+        app_auth::AuthApp,
+        deploy_config::DeployConfig,
+        device_authorization::DEFAULT_POLL_INTERVAL,
+        frontend_app::{DeployRepo, FrontendApp},
+        mail::{MailApp, MailInbox, MailMessage},
+        project::{Deployment, Project},
+        runner::Runner,
+        tenant::{Tenant, TenantKind, TenantRole},
+    },
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
+};
+
+/// Email of the account every fresh [`MockState`] starts with.
+pub const MOCK_EMAIL: &str = "mock@smbcloud.xyz";
+/// Password of the seeded account.
+pub const MOCK_PASSWORD: &str = "mock-password";
+/// Access token of the seeded account. Send it as the `Authorization` header,
+/// with or without a `Bearer ` prefix.
+pub const MOCK_ACCESS_TOKEN: &str = "mock-access-token";
+/// The TOTP code the mock takes as current for any secret, since it has no
+/// clock-based codes of its own.
+pub const MOCK_OTP: &str = "123456";
+
+/// Mail app API key the transactional email routes accept, sent as
+/// `Authorization: Bearer <key>`.
+pub const MOCK_EMAIL_API_KEY: &str = "smb_mail_mock";
+/// App key and secret the GresIQ document routes accept, sent as
+/// `X-Gresiq-Api-Key` / `X-Gresiq-Api-Secret`.
+pub const MOCK_GRESIQ_API_KEY: &str = "gresiq-mock-key";
+pub const MOCK_GRESIQ_API_SECRET: &str = "gresiq-mock-secret";
+
+/// `EmailStatus` names in their numeric order, as `?status=` spells them.
+const EMAIL_STATUSES: [&str; 6] = [
+    "queued",
+    "sent",
+    "delivered",
+    "bounced",
+    "complained",
+    "failed",
+];
+
+pub type SharedState = Arc<Mutex<MockState>>;
+
+/// A user the mock can sign in, with the token it hands out on sign-in.
+#[derive(Debug, Clone)]
+pub struct MockAccount {
+    pub user: User,
+    pub password: String,
+    pub access_token: String,
+    /// A new email waiting to be verified, set by `PUT v1/users`.
+    pub unconfirmed_email: Option<String>,
+    pub two_factor: MockTwoFactor,
+}
+
+/// An account's two-factor authentication, see `v1/users/two_factor`.
+#[derive(Debug, Clone, Default)]
+pub struct MockTwoFactor {
+    /// Set by setup; only asked for on sign-in once `enabled`.
+    pub otp_secret: Option<String>,
+    pub enabled: bool,
+    /// Unused recovery codes. Each one works once.
+    pub recovery_codes: Vec<String>,
+}
+
+impl MockTwoFactor {
+    /// Whether `otp` is [`MOCK_OTP`] or an unused recovery code, which it
+    /// then uses up.
+    pub fn verify(&mut self, otp: &str) -> bool {
+        if otp == MOCK_OTP {
+            return true;
+        }
+        let before = self.recovery_codes.len();
+        self.recovery_codes.retain(|code| code != otp);
+        self.recovery_codes.len() < before
+    }
+}
+
+/// A personal access token and the secret that authenticates with it.
+#[derive(Debug, Clone)]
+pub struct MockAccessToken {
+    pub user_id: i32,
+    pub token: String,
+    pub record: AccessToken,
+}
+
+/// A device login waiting for the user, see `v1/oauth/device/*`.
+#[derive(Debug, Clone)]
+pub struct MockDeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub expires_at: DateTime<Utc>,
+    /// Set once the user approved; the token goes to this account.
+    pub approved_user_id: Option<i32>,
+    pub denied: bool,
+    pub last_polled_at: Option<DateTime<Utc>>,
+}
+
+/// Who a request's `Authorization` header belongs to.
+#[derive(Debug, Clone)]
+pub struct Caller {
+    pub user_id: i32,
+    /// `None` for a login session, which may do anything.
+    pub scopes: Option<Vec<TokenScope>>,
+}
+
+/// Everything the mock API knows, held in memory for the life of the server.
+///
+/// Fields are public so tests can seed records before a request and assert on
+/// them after. Ids come from one counter shared by every resource.
+#[derive(Debug)]
+pub struct MockState {
+    pub accounts: Vec<MockAccount>,
+    pub tenants: Vec<Tenant>,
+    pub projects: Vec<Project>,
+    pub deploy_repos: Vec<DeployRepo>,
+    pub frontend_apps: Vec<FrontendApp>,
+    /// Server deploy config keyed by frontend app id.
+    pub deploy_configs: HashMap<String, DeployConfig>,
+    pub deployments: Vec<Deployment>,
+    pub mail_apps: Vec<MailApp>,
+    pub mail_inboxes: Vec<MailInbox>,
+    pub mail_messages: Vec<MailMessage>,
+    pub auth_apps: Vec<AuthApp>,
+    pub access_tokens: Vec<MockAccessToken>,
+    /// Transactional emails, as the email SDK's `EmailMessage` JSON. The
+    /// model lives in that SDK, so they are kept untyped.
+    pub email_messages: Vec<Value>,
+    /// GresIQ documents, as the GresIQ SDK's `GresiqDocument` JSON.
+    pub gresiq_documents: Vec<Value>,
+    pub device_authorizations: Vec<MockDeviceAuthorization>,
+    /// Google ID tokens `v1/authorize/google` accepts, with the email each
+    /// one proves. See [`MockState::add_google_id_token`].
+    pub google_id_tokens: HashMap<String, String>,
+    /// Seconds a device login must wait between polls. Set to 0 in tests.
+    pub device_poll_interval: u64,
+    next_id: i64,
+}
+
+impl Default for MockState {
+    /// One signed-up account with its personal tenant, nothing else.
+    fn default() -> Self {
+        let now = Utc::now();
+        let mut state = MockState {
+            accounts: Vec::new(),
+            tenants: Vec::new(),
+            projects: Vec::new(),
+            deploy_repos: Vec::new(),
+            frontend_apps: Vec::new(),
+            deploy_configs: HashMap::new(),
+            deployments: Vec::new(),
+            mail_apps: Vec::new(),
+            mail_inboxes: Vec::new(),
+            mail_messages: Vec::new(),
+            auth_apps: Vec::new(),
+            access_tokens: Vec::new(),
+            email_messages: Vec::new(),
+            gresiq_documents: Vec::new(),
+            device_authorizations: Vec::new(),
+            google_id_tokens: HashMap::new(),
+            device_poll_interval: DEFAULT_POLL_INTERVAL,
+            next_id: 1,
+        };
+        state.add_account(MOCK_EMAIL, MOCK_PASSWORD, MOCK_ACCESS_TOKEN);
+        let tenant_id = state.next_id();
+        state.tenants.push(Tenant {
+            id: tenant_id,
+            name: "Personal".to_string(),
+            slug: "personal".to_string(),
+            kind: TenantKind::Personal,
+            role: TenantRole::Owner,
+            projects_count: 0,
+            default_project: None,
+            current: true,
+            created_at: now,
+        });
+        state
+    }
+}
+
+impl MockState {
+    pub fn next_id(&mut self) -> i64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    pub fn add_account(&mut self, email: &str, password: &str, access_token: &str) -> User {
+        let now = Utc::now();
+        let user = User {
+            id: self.next_id() as i32,
+            email: email.to_string(),
+            created_at: now,
+            updated_at: now,
+        };
+        self.accounts.push(MockAccount {
+            user: user.clone(),
+            password: password.to_string(),
+            access_token: access_token.to_string(),
+            unconfirmed_email: None,
+            two_factor: MockTwoFactor::default(),
+        });
+        user
+    }
+
+    /// Makes up an ID token that `v1/authorize/google` takes as Google's
+    /// word for `email`.
+    pub fn add_google_id_token(&mut self, email: &str) -> String {
+        let id_token = format!("mock-google-id-token-{}", self.next_id());
+        self.google_id_tokens
+            .insert(id_token.clone(), email.to_string());
+        id_token
+    }
+
+    pub fn account_for_token(&self, access_token: &str) -> Option<&MockAccount> {
+        self.accounts
+            .iter()
+            .find(|account| account.access_token == access_token)
+    }
+
+    /// Resolves a session token or an unexpired personal access token, and
+    /// stamps the latter as used.
+    pub fn authenticate(&mut self, token: &str) -> Option<Caller> {
+        if let Some(account) = self.account_for_token(token) {
+            return Some(Caller {
+                user_id: account.user.id,
+                scopes: None,
+            });
+        }
+        let now = Utc::now();
+        let access_token = self
+            .access_tokens
+            .iter_mut()
+            .find(|access_token| access_token.token == token)
+            .filter(|access_token| !access_token.record.is_expired(now))?;
+        access_token.record.last_used_at = Some(now);
+        Some(Caller {
+            user_id: access_token.user_id,
+            scopes: Some(access_token.record.scopes.clone()),
+        })
+    }
+
+    /// Issues a personal access token for `user_id`.
+    pub fn add_access_token(
+        &mut self,
+        user_id: i32,
+        name: &str,
+        scopes: Vec<TokenScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> CreatedAccessToken {
+        let id = self.next_id();
+        let token = format!("smb_pat_mock{id}");
+        let record = AccessToken {
+            id,
+            name: name.to_string(),
+            scopes,
+            token_prefix: token.chars().take(12).collect(),
+            expires_at,
+            last_used_at: None,
+            created_at: Utc::now(),
+        };
+        self.access_tokens.push(MockAccessToken {
+            user_id,
+            token: token.clone(),
+            record: record.clone(),
+        });
+        CreatedAccessToken {
+            access_token: record,
+            token,
+        }
+    }
+
+    /// Approves a pending device login as the seeded account, the way a user
+    /// would on the verification page. False for an unknown code.
+    pub fn approve_device(&mut self, user_code: &str) -> bool {
+        let user_id = self.accounts.first().map(|account| account.user.id);
+        self.device_authorizations
+            .iter_mut()
+            .find(|device| device.user_code == user_code)
+            .map(|device| device.approved_user_id = user_id)
+            .is_some()
+    }
+
+    /// Declines a pending device login. False for an unknown code.
+    pub fn deny_device(&mut self, user_code: &str) -> bool {
+        self.device_authorizations
+            .iter_mut()
+            .find(|device| device.user_code == user_code)
+            .map(|device| device.denied = true)
+            .is_some()
+    }
+
+    /// The tenant new records land in when no `X-Smbcloud-Tenant-Id` is sent.
+    pub fn personal_tenant_id(&self) -> i64 {
+        self.tenants
+            .iter()
+            .find(|tenant| tenant.kind == TenantKind::Personal)
+            .map(|tenant| tenant.id)
+            .unwrap_or_default()
+    }
+
+    pub fn add_project(
+        &mut self,
+        name: &str,
+        description: &str,
+        tenant_id: Option<i64>,
+    ) -> Project {
+        let id = self.next_id();
+        let tenant_id = tenant_id.unwrap_or_else(|| self.personal_tenant_id());
+        let project: Project = from_json(json!({
+            "id": id,
+            "tenant_id": tenant_id,
+            "name": name,
+            "description": description,
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+        }));
+        if let Some(tenant) = self
+            .tenants
+            .iter_mut()
+            .find(|tenant| tenant.id == tenant_id)
+        {
+            tenant.projects_count += 1;
+        }
+        self.projects.push(project.clone());
+        project
+    }
+
+    /// Adds a frontend app and the matching server deploy config.
+    pub fn add_frontend_app(&mut self, project_id: i32, name: &str, runner: Runner) -> FrontendApp {
+        let id = format!("fa-{}", self.next_id());
+        let frontend_app: FrontendApp = from_json(json!({
+            "id": id,
+            "name": name,
+            "app_type": 0,
+            "runner": runner,
+            "project_id": project_id,
+            "tenant_id": self.personal_tenant_id(),
+            "project_ids": [project_id],
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+        }));
+        let deploy_config: DeployConfig = from_json(json!({
+            "id": id,
+            "name": name,
+            "runner": runner,
+            "deployment_method": frontend_app.deployment_method,
+            "project_id": project_id,
+        }));
+        self.deploy_configs.insert(id, deploy_config);
+        self.frontend_apps.push(frontend_app.clone());
+        frontend_app
+    }
+
+    /// Drops an inbound message into an inbox. The real API only gets these
+    /// from SES, so tests have to seed them.
+    pub fn add_mail_message(
+        &mut self,
+        inbox_id: i32,
+        from_email: &str,
+        subject: &str,
+        text_body: &str,
+    ) -> Option<MailMessage> {
+        let inbox = self
+            .mail_inboxes
+            .iter()
+            .find(|inbox| inbox.id == inbox_id)?;
+        let recipient = inbox.full_address.clone();
+        let id = self.next_id();
+        let message: MailMessage = from_json(json!({
+            "id": id,
+            "mail_inbox_id": inbox_id,
+            "provider_message_id": format!("mock-{id}"),
+            "original_recipient_email": recipient,
+            "from_email": from_email,
+            "subject": subject,
+            "text_preview": text_body.chars().take(120).collect::<String>(),
+            "text_body": text_body,
+            "to_emails": [recipient],
+            "size_bytes": text_body.len(),
+            "received_at": Utc::now(),
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+            "status": 0,
+        }));
+        self.mail_messages.push(message.clone());
+        Some(message)
+    }
+
+    /// Records a transactional email with `status` (`"sent"`, `"bounced"`…).
+    pub fn add_email_message(
+        &mut self,
+        from_email: &str,
+        to_emails: &[&str],
+        subject: Option<&str>,
+        status: &str,
+    ) -> Value {
+        let now = Utc::now().to_rfc3339();
+        let message = json!({
+            "id": format!("em-{}", self.next_id()),
+            "status": email_status(status).unwrap_or_default(),
+            "provider_message_id": null,
+            "idempotency_key": null,
+            "from_email": from_email,
+            "to_emails": to_emails,
+            "subject": subject,
+            "sent_at": now,
+            "created_at": now,
+            "events": [],
+        });
+        self.email_messages.push(message.clone());
+        message
+    }
+
+    /// Upserts a GresIQ document on `(collection, key)`; without a key it is
+    /// appended under a new one.
+    pub fn add_document(&mut self, collection: &str, key: Option<&str>, doc: Value) -> Value {
+        let now = Utc::now().to_rfc3339();
+        if let Some(existing) = key.and_then(|key| {
+            self.gresiq_documents
+                .iter_mut()
+                .find(|document| document["collection"] == collection && document["key"] == key)
+        }) {
+            existing["doc"] = doc;
+            existing["updated_at"] = json!(now);
+            return existing.clone();
+        }
+        let id = self.next_id();
+        let document = json!({
+            "id": format!("doc-{id}"),
+            "key": key.map(str::to_string).unwrap_or_else(|| format!("key-{id}")),
+            "collection": collection,
+            "doc": doc,
+            "created_at": now,
+            "updated_at": now,
+        });
+        self.gresiq_documents.push(document.clone());
+        document
+    }
+}
+
+/// The `EmailStatus` number for a status name.
+pub(crate) fn email_status(name: &str) -> Option<usize> {
+    EMAIL_STATUSES.iter().position(|status| *status == name)
+}
+
+/// Builds a model from JSON so optional fields take their serde defaults.
+pub(crate) fn from_json<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).expect("mock record matches the smbcloud-model shape")
+}
+
+/// Serializes a model whose timestamps use `ar_date_format`.
+///
+/// That module writes timestamps without the `T` separator and offset, a shape
+/// its own deserializer rejects, so rewrite them the way Rails renders them.
+pub(crate) fn with_rails_timestamps<T: serde::Serialize>(
+    value: &T,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+) -> Value {
+    let mut json = serde_json::to_value(value).unwrap_or_default();
+    if let Some(object) = json.as_object_mut() {
+        object.insert("created_at".to_string(), json!(rails_timestamp(created_at)));
+        object.insert("updated_at".to_string(), json!(rails_timestamp(updated_at)));
+    }
+    json
+}
+
+fn rails_timestamp(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
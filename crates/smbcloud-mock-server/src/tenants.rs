@@ -0,0 +1,111 @@
+//! `v1/tenants`. Bodies are wrapped as `{ "tenant": { … } }`.
+
+use {
+    crate::{error::ApiError, extract::Authed, state::SharedState},
+    axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        Json,
+    },
+    chrono::Utc,
+    serde::Deserialize,
+    smbcloud_model::tenant::{Tenant, TenantCreate, TenantKind, TenantRole, TenantUpdate},
+};
+
+#[derive(Deserialize)]
+pub(crate) struct TenantEnvelope<T> {
+    tenant: T,
+}
+
+pub(crate) async fn index(State(state): State<SharedState>, _authed: Authed) -> Json<Vec<Tenant>> {
+    let state = state.lock().expect("mock state lock");
+    Json(state.tenants.clone())
+}
+
+pub(crate) async fn create(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Json(body): Json<TenantEnvelope<TenantCreate>>,
+) -> Result<(StatusCode, Json<Tenant>), ApiError> {
+    let name = body.tenant.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ApiError::invalid_params("Name can't be blank."));
+    }
+    let mut state = state.lock().expect("mock state lock");
+    let tenant = Tenant {
+        id: state.next_id(),
+        slug: slug(&name),
+        name,
+        kind: TenantKind::Organization,
+        role: TenantRole::Owner,
+        projects_count: 0,
+        default_project: None,
+        current: false,
+        created_at: Utc::now(),
+    };
+    state.tenants.push(tenant.clone());
+    Ok((StatusCode::CREATED, Json(tenant)))
+}
+
+pub(crate) async fn show(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i64>,
+) -> Result<Json<Tenant>, ApiError> {
+    let state = state.lock().expect("mock state lock");
+    state
+        .tenants
+        .iter()
+        .find(|tenant| tenant.id == id)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Tenant"))
+}
+
+pub(crate) async fn update(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i64>,
+    Json(body): Json<TenantEnvelope<TenantUpdate>>,
+) -> Result<Json<Tenant>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let tenant = state
+        .tenants
+        .iter_mut()
+        .find(|tenant| tenant.id == id)
+        .ok_or_else(|| ApiError::not_found("Tenant"))?;
+    if let Some(name) = body.tenant.name {
+        tenant.slug = slug(&name);
+        tenant.name = name;
+    }
+    Ok(Json(tenant.clone()))
+}
+
+/// Only organization tenants can be deleted; the personal one is permanent.
+pub(crate) async fn destroy(
+    State(state): State<SharedState>,
+    _authed: Authed,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let index = state
+        .tenants
+        .iter()
+        .position(|tenant| tenant.id == id)
+        .ok_or_else(|| ApiError::not_found("Tenant"))?;
+    if state.tenants[index].kind == TenantKind::Personal {
+        return Err(ApiError::invalid_params(
+            "The personal tenant can't be deleted.",
+        ));
+    }
+    state.tenants.remove(index);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|character: char| !character.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
@@ -0,0 +1,135 @@
+//! `v1/users/two_factor` (setup, enable, disable) and its `recovery_codes`.
+//! The auth SDK's `v1/client/me/two_factor` routes land here too. Any
+//! secret takes [`MOCK_OTP`] as its current code.
+
+use {
+    crate::{
+        error::ApiError,
+        extract::Authed,
+        state::{MockAccount, MockTwoFactor, SharedState, MOCK_OTP},
+    },
+    axum::{extract::State, http::StatusCode, Json},
+    smbcloud_model::{
+        error_codes::ErrorCode,
+        two_factor::{OtpParams, RecoveryCodes, TwoFactorSetup},
+    },
+};
+
+/// How many recovery codes enabling or regenerating hands out.
+const RECOVERY_CODES: usize = 10;
+
+/// A new secret, replacing one that was never confirmed. Once two-factor
+/// authentication is on it has to be turned off first.
+pub(crate) async fn setup(
+    State(state): State<SharedState>,
+    authed: Authed,
+) -> Result<Json<TwoFactorSetup>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let secret = format!("MOCKSECRET{}", state.next_id());
+    let account = state
+        .accounts
+        .iter_mut()
+        .find(|account| account.user.id == authed.user_id)
+        .ok_or_else(ApiError::unauthorized)?;
+    if account.two_factor.enabled {
+        return Err(ApiError::invalid_params(
+            "Two-factor authentication is already on.",
+        ));
+    }
+    account.two_factor.otp_secret = Some(secret.clone());
+    Ok(Json(TwoFactorSetup {
+        provisioning_uri: format!(
+            "otpauth://totp/smbCloud:{}?secret={secret}&issuer=smbCloud",
+            account.user.email
+        ),
+        secret,
+    }))
+}
+
+/// Confirms the pending secret with a code from it.
+pub(crate) async fn enable(
+    State(state): State<SharedState>,
+    authed: Authed,
+    Json(body): Json<OtpParams>,
+) -> Result<Json<RecoveryCodes>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let id = state.next_id();
+    let two_factor = two_factor(&mut state.accounts, authed.user_id)?;
+    if two_factor.enabled || two_factor.otp_secret.is_none() {
+        return Err(ApiError::invalid_params(
+            "Set up two-factor authentication first.",
+        ));
+    }
+    if body.otp_attempt != MOCK_OTP {
+        return Err(invalid_otp());
+    }
+    two_factor.enabled = true;
+    Ok(Json(new_recovery_codes(two_factor, id)))
+}
+
+/// Turns two-factor authentication off with a current or recovery code.
+pub(crate) async fn disable(
+    State(state): State<SharedState>,
+    authed: Authed,
+    Json(body): Json<OtpParams>,
+) -> Result<StatusCode, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let two_factor = enabled_two_factor(&mut state.accounts, authed.user_id, &body)?;
+    *two_factor = MockTwoFactor::default();
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(crate) async fn recovery_codes(
+    State(state): State<SharedState>,
+    authed: Authed,
+    Json(body): Json<OtpParams>,
+) -> Result<Json<RecoveryCodes>, ApiError> {
+    let mut state = state.lock().expect("mock state lock");
+    let id = state.next_id();
+    let two_factor = enabled_two_factor(&mut state.accounts, authed.user_id, &body)?;
+    Ok(Json(new_recovery_codes(two_factor, id)))
+}
+
+fn two_factor(accounts: &mut [MockAccount], user_id: i32) -> Result<&mut MockTwoFactor, ApiError> {
+    accounts
+        .iter_mut()
+        .find(|account| account.user.id == user_id)
+        .map(|account| &mut account.two_factor)
+        .ok_or_else(ApiError::unauthorized)
+}
+
+/// The caller's two-factor state, if it is on and `body` holds a code for it.
+fn enabled_two_factor<'a>(
+    accounts: &'a mut [MockAccount],
+    user_id: i32,
+    body: &OtpParams,
+) -> Result<&'a mut MockTwoFactor, ApiError> {
+    let two_factor = two_factor(accounts, user_id)?;
+    if !two_factor.enabled {
+        return Err(ApiError::invalid_params(
+            "Two-factor authentication is off.",
+        ));
+    }
+    if !two_factor.verify(&body.otp_attempt) {
+        return Err(invalid_otp());
+    }
+    Ok(two_factor)
+}
+
+/// Replaces the recovery codes, used or not. `id` keeps them unique.
+fn new_recovery_codes(two_factor: &mut MockTwoFactor, id: i64) -> RecoveryCodes {
+    two_factor.recovery_codes = (0..RECOVERY_CODES)
+        .map(|index| format!("mock-recovery-{id}-{index}"))
+        .collect();
+    RecoveryCodes {
+        recovery_codes: two_factor.recovery_codes.clone(),
+    }
+}
+
+fn invalid_otp() -> ApiError {
+    ApiError::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        ErrorCode::InvalidOtp,
+        ErrorCode::InvalidOtp.message(None),
+    )
+}
@@ -0,0 +1,172 @@
+use {
+    chrono::{DateTime, Utc},
+    serde::{Deserialize, Serialize},
+    std::{fmt::Display, str::FromStr},
+};
+
+/// What a request needs from the token that sent it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Any `GET`.
+    Read,
+    /// Creating or updating a deployment of a project.
+    Deploy,
+    /// Everything else: creating, changing and deleting resources.
+    Write,
+}
+
+/// One permission of a personal access token, written `read`, `deploy`,
+/// `deploy:<project_id>` or `write`.
+///
+/// `deploy` implies `read`, and `write` implies both. A token can never
+/// manage access tokens; that takes a login session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenScope {
+    Read,
+    /// Deploy any project, or only `project_id`. Can't delete anything.
+    Deploy {
+        project_id: Option<i32>,
+    },
+    Write,
+}
+
+impl TokenScope {
+    /// Whether this scope allows `access` to `project_id`. Requests that are
+    /// not about one project pass `None`.
+    pub fn permits(&self, access: Access, project_id: Option<i32>) -> bool {
+        match self {
+            TokenScope::Write => true,
+            TokenScope::Read => access == Access::Read,
+            TokenScope::Deploy { project_id: scoped } => match access {
+                Access::Read => true,
+                Access::Deploy => scoped.is_none() || *scoped == project_id,
+                Access::Write => false,
+            },
+        }
+    }
+}
+
+impl Display for TokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenScope::Read => write!(f, "read"),
+            TokenScope::Deploy { project_id: None } => write!(f, "deploy"),
+            TokenScope::Deploy {
+                project_id: Some(project_id),
+            } => write!(f, "deploy:{project_id}"),
+            TokenScope::Write => write!(f, "write"),
+        }
+    }
+}
+
+impl FromStr for TokenScope {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid scope `{value}`. Use read, deploy, deploy:<project_id> or write.");
+        match value.trim().split_once(':') {
+            None => match value.trim() {
+                "read" => Ok(TokenScope::Read),
+                "deploy" => Ok(TokenScope::Deploy { project_id: None }),
+                "write" => Ok(TokenScope::Write),
+                _ => Err(invalid()),
+            },
+            Some(("deploy", project_id)) => project_id
+                .parse()
+                .map(|project_id| TokenScope::Deploy {
+                    project_id: Some(project_id),
+                })
+                .map_err(|_| invalid()),
+            Some(_) => Err(invalid()),
+        }
+    }
+}
+
+impl Serialize for TokenScope {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenScope {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A personal access token as listed. The secret itself is only returned
+/// once, by [`CreatedAccessToken`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccessToken {
+    pub id: i64,
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    /// The first characters of the secret, to tell tokens apart.
+    pub token_prefix: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AccessToken {
+    /// Whether the token still works, given `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// The response to creating a token: the record plus the secret, which the
+/// API won't show again.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreatedAccessToken {
+    #[serde(flatten)]
+    pub access_token: AccessToken,
+    pub token: String,
+}
+
+/// Payload for creating a personal access token.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccessTokenCreate {
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    /// `None` creates a token that never expires.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_round_trip_through_strings() {
+        for value in ["read", "deploy", "deploy:42", "write"] {
+            let scope: TokenScope = value.parse().unwrap();
+            assert_eq!(scope.to_string(), value);
+        }
+        assert!("deploy:web".parse::<TokenScope>().is_err());
+        assert!("admin".parse::<TokenScope>().is_err());
+        assert_eq!(
+            serde_json::to_string(&TokenScope::Deploy {
+                project_id: Some(7)
+            })
+            .unwrap(),
+            "\"deploy:7\""
+        );
+    }
+
+    #[test]
+    fn project_deploy_scope_cannot_delete() {
+        let scope = TokenScope::Deploy {
+            project_id: Some(7),
+        };
+        assert!(scope.permits(Access::Read, Some(7)));
+        assert!(scope.permits(Access::Deploy, Some(7)));
+        assert!(!scope.permits(Access::Deploy, Some(8)));
+        assert!(!scope.permits(Access::Write, Some(7)));
+        assert!(!TokenScope::Read.permits(Access::Deploy, None));
+        assert!(TokenScope::Write.permits(Access::Write, Some(8)));
+    }
+}
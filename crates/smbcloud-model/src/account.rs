@@ -8,7 +8,7 @@ use {
 };
 
 // smbcloud Users.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[tsync]
 pub struct User {
     pub id: i32,
@@ -61,6 +61,8 @@ pub enum ErrorCode {
     EmailAlreadyExist = 1005,
     InvalidPassword = 1006,
     HostedMailAccountUnverified = 1007,
+    OtpRequired = 1009,
+    InvalidOtp = 1010,
 }
 
 impl Display for ErrorCode {
@@ -76,6 +78,8 @@ impl Display for ErrorCode {
             ErrorCode::HostedMailAccountUnverified => {
                 write!(f, "Hosted mail account not fully activated yet.")
             }
+            ErrorCode::OtpRequired => write!(f, "Two-factor code required."),
+            ErrorCode::InvalidOtp => write!(f, "Invalid two-factor code."),
         }
     }
 }
@@ -88,6 +92,25 @@ impl Clone for ErrorCode {
     }
 }
 
+/// Body of `PUT v1/users`, which changes the email or password of the
+/// signed-in account. Either change needs the current password.
+#[derive(Debug, Serialize)]
+pub struct AccountUpdateParams {
+    pub user: AccountUpdate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountUpdate {
+    pub current_password: String,
+    /// Takes effect once the new address is verified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_confirmation: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GithubInfo {
     pub id: i64,
@@ -122,4 +145,23 @@ mod tests {
         });
         assert_eq!(serde_json::to_value(smb_authorization).unwrap(), json);
     }
+
+    #[test]
+    fn test_account_update_sends_only_what_changes() {
+        let params = AccountUpdateParams {
+            user: AccountUpdate {
+                current_password: "old".to_owned(),
+                email: Some("new@example.com".to_owned()),
+                password: None,
+                password_confirmation: None,
+            },
+        };
+        let json = json!({
+            "user": {
+                "current_password": "old",
+                "email": "new@example.com",
+            },
+        });
+        assert_eq!(serde_json::to_value(params).unwrap(), json);
+    }
 }
@@ -16,6 +16,43 @@ pub struct AuthApp {
     pub updated_at: DateTime<Utc>,
 }
 
+impl AuthApp {
+    /// The app with its secret masked, for anywhere it is shown by default.
+    pub fn masked(mut self) -> Self {
+        self.secret = self.secret.map(|secret| mask_secret(&secret));
+        self
+    }
+}
+
+/// Hides all but the last four characters of a secret, enough to tell two
+/// secrets apart. Short secrets are hidden entirely.
+pub fn mask_secret(secret: &str) -> String {
+    const MASK: &str = "••••••••";
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 12 {
+        return MASK.to_string();
+    }
+    let last_four: String = chars[chars.len() - 4..].iter().collect();
+    format!("{MASK}{last_four}")
+}
+
+/// An Auth app's current secret, revealed on request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthAppSecret {
+    pub secret: String,
+    /// After a rotation, the previous secret keeps working until this time.
+    #[serde(default)]
+    pub previous_secret_expires_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for rotating an Auth app's secret. The old secret stays valid
+/// for `grace_period_seconds` so deployments can switch over; `0` revokes it
+/// at once.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthAppSecretRotate {
+    pub grace_period_seconds: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AuthAppCreate {
     pub name: String,
@@ -70,6 +107,41 @@ pub struct AuthAppClientCreate {
     pub redirect_uris: String,
 }
 
+/// Request body for updating an OAuth client. `redirect_uris`, when set,
+/// replaces the whole allowlist.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[tsync]
+pub struct AuthAppClientUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_uris: Option<String>,
+}
+
+impl AuthAppClientUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.redirect_uris.is_none()
+    }
+}
+
+/// An end user who signs in through an AuthApp, as the app's owner sees them.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthAppUser {
+    pub id: i64,
+    pub email: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub email_verified: bool,
+    /// Disabled users can't sign in; their sessions are revoked.
+    pub disabled: bool,
+    #[serde(default)]
+    pub last_sign_in_at: Option<DateTime<Utc>>,
+    #[serde(with = "ar_date_format")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "ar_date_format")]
+    pub updated_at: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +159,26 @@ mod tests {
         });
         assert_eq!(serde_json::to_value(auth_app_create).unwrap(), json);
     }
+
+    #[test]
+    fn test_auth_app_user_without_sign_in() {
+        let user: AuthAppUser = serde_json::from_value(json!({
+            "id": 7,
+            "email": "user@example.com",
+            "email_verified": true,
+            "disabled": false,
+            "last_sign_in_at": null,
+            "created_at": "2026-01-01T00:00:00.000Z",
+            "updated_at": "2026-01-01T00:00:00.000Z",
+        }))
+        .unwrap();
+        assert_eq!(user.name, None);
+        assert_eq!(user.last_sign_in_at, None);
+    }
+
+    #[test]
+    fn test_mask_secret_keeps_only_the_last_four() {
+        assert_eq!(mask_secret("sk_live_0123456789abcd"), "••••••••abcd");
+        assert_eq!(mask_secret("short"), "••••••••");
+    }
 }
@@ -0,0 +1,86 @@
+//! OAuth 2.0 device authorization grant (RFC 8628), used by `smb login
+//! --device` where no browser can reach a local callback.
+
+use serde::{Deserialize, Serialize};
+
+/// `grant_type` sent when polling the token endpoint.
+pub const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Seconds between polls when the server doesn't say, per RFC 8628.
+pub const DEFAULT_POLL_INTERVAL: u64 = 5;
+
+fn default_poll_interval() -> u64 {
+    DEFAULT_POLL_INTERVAL
+}
+
+/// The response to starting a device login. Show `user_code` and
+/// `verification_uri` to the user, then poll with `device_code`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    /// `verification_uri` with the code filled in, when the server offers it.
+    #[serde(default)]
+    pub verification_uri_complete: Option<String>,
+    /// Seconds until `device_code` expires.
+    pub expires_in: u64,
+    /// Seconds to wait between polls.
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+}
+
+/// Body of a token poll. RFC 8628 §3.4 sends it form-urlencoded, not JSON.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeviceTokenRequest {
+    pub grant_type: String,
+    pub device_code: String,
+    pub client_id: String,
+}
+
+impl DeviceTokenRequest {
+    pub fn new(device_code: &str, client_id: &str) -> Self {
+        DeviceTokenRequest {
+            grant_type: DEVICE_CODE_GRANT_TYPE.to_string(),
+            device_code: device_code.to_string(),
+            client_id: client_id.to_string(),
+        }
+    }
+}
+
+/// Returned once the user approved the login.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeviceToken {
+    pub access_token: String,
+    pub token_type: String,
+}
+
+impl DeviceToken {
+    /// The `Authorization` header value, the same shape `sign_in` returns.
+    pub fn authorization(&self) -> String {
+        format!("{} {}", self.token_type, self.access_token)
+    }
+}
+
+/// The `error` of a `400` from the token endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceTokenErrorCode {
+    /// The user hasn't approved yet. Keep polling.
+    AuthorizationPending,
+    /// Polling too fast. Wait 5 more seconds between polls from now on.
+    SlowDown,
+    /// `device_code` expired. Start over.
+    ExpiredToken,
+    /// The user declined.
+    AccessDenied,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeviceTokenError {
+    pub error: DeviceTokenErrorCode,
+    #[serde(default)]
+    pub error_description: Option<String>,
+}
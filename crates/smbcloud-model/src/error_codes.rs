@@ -46,6 +46,8 @@ pub enum ErrorCode {
     Unauthorized = 100,
     #[error("Invalid params.")]
     InvalidParams = 101,
+    #[error("Forbidden.")]
+    Forbidden = 102,
     // Account not ready errors.
     #[error("Email not found.")]
     EmailNotFound = 1000,
@@ -61,6 +63,14 @@ pub enum ErrorCode {
     EmailAlreadyExist = 1005,
     #[error("Invalid password.")]
     InvalidPassword = 1006,
+    #[error("The login code expired.")]
+    DeviceCodeExpired = 1007,
+    #[error("The login request was denied.")]
+    DeviceAccessDenied = 1008,
+    #[error("Two-factor code required.")]
+    OtpRequired = 1009,
+    #[error("Invalid two-factor code.")]
+    InvalidOtp = 1010,
     // Projects
     #[error("Project not found.")]
     ProjectNotFound = 2000,
@@ -76,6 +86,7 @@ impl ErrorCode {
             // Generic
             100 => ErrorCode::Unauthorized,
             101 => ErrorCode::InvalidParams,
+            102 => ErrorCode::Forbidden,
             // Account not ready errors
             1000 => ErrorCode::EmailNotFound,
             1001 => ErrorCode::EmailNotVerified,
@@ -84,6 +95,10 @@ impl ErrorCode {
             1004 => ErrorCode::GitHubEmailNotConnected,
             1005 => ErrorCode::EmailAlreadyExist,
             1006 => ErrorCode::InvalidPassword,
+            1007 => ErrorCode::DeviceCodeExpired,
+            1008 => ErrorCode::DeviceAccessDenied,
+            1009 => ErrorCode::OtpRequired,
+            1010 => ErrorCode::InvalidOtp,
             // Projects
             2000 => ErrorCode::ProjectNotFound, // Projects
             2001 => ErrorCode::UnsupportedRunner,
@@ -111,6 +126,7 @@ impl ErrorCode {
             // Generic
             ErrorCode::Unauthorized => "Unauthorized access.",
             ErrorCode::InvalidParams => "Invalid parameters.",
+            ErrorCode::Forbidden => "Your access token's scopes don't allow this.",
             // Account not ready errors
             ErrorCode::EmailNotFound => "Email not found.",
             ErrorCode::EmailNotVerified => "Email not verified.",
@@ -119,6 +135,10 @@ impl ErrorCode {
             ErrorCode::GitHubEmailNotConnected => "GitHub email is not connected.",
             ErrorCode::EmailAlreadyExist => "Email already exists.",
             ErrorCode::InvalidPassword => "Invalid password.",
+            ErrorCode::DeviceCodeExpired => "The login code expired. Start the login again.",
+            ErrorCode::DeviceAccessDenied => "The login request was denied.",
+            ErrorCode::OtpRequired => "Enter the code from your authenticator app.",
+            ErrorCode::InvalidOtp => "Invalid two-factor code.",
             // CLI Generic errors
             ErrorCode::InputError => "Input error.",
             ErrorCode::MissingConfig => "Missing config.",
@@ -1,19 +1,23 @@
+pub mod access_token;
 pub mod account;
 pub mod app_auth;
 pub mod deploy_config;
 pub mod deploy_config_update;
+pub mod device_authorization;
 pub mod error_codes;
 pub mod forgot;
 pub mod frontend_app;
 pub mod login;
 pub mod mail;
 pub mod oauth;
+pub mod pagination;
 pub mod project;
 pub mod repository;
 pub mod reset_password_response;
 pub mod runner;
 pub mod signup;
 pub mod tenant;
+pub mod two_factor;
 
 pub mod ar_date_format {
     use chrono::{DateTime, Utc};
@@ -19,6 +19,10 @@ pub struct LoginParams {
 pub struct UserParam {
     pub email: String,
     pub password: String,
+    /// A TOTP code or an unused recovery code, for accounts with two-factor
+    /// authentication on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub otp_attempt: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,8 +36,15 @@ pub struct LoginResult {
 #[tsync]
 pub enum AccountStatus {
     NotFound,
-    Ready { access_token: String },
-    Incomplete { status: ErrorCode },
+    Ready {
+        access_token: String,
+    },
+    Incomplete {
+        status: ErrorCode,
+    },
+    /// The password was right, but the account has two-factor authentication
+    /// on. Log in again with `otp_attempt` set.
+    OtpRequired,
 }
 
 #[cfg(test)]
@@ -52,4 +63,22 @@ mod tests {
         });
         assert_eq!(serde_json::to_value(args).unwrap(), json);
     }
+
+    #[test]
+    fn otp_attempt_is_only_sent_when_set() {
+        let mut user = UserParam {
+            email: "a@b.c".to_owned(),
+            password: "secret".to_owned(),
+            otp_attempt: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&user).unwrap(),
+            json!({ "email": "a@b.c", "password": "secret" })
+        );
+        user.otp_attempt = Some("123456".to_owned());
+        assert_eq!(
+            serde_json::to_value(&user).unwrap()["otp_attempt"],
+            json!("123456")
+        );
+    }
 }
@@ -114,7 +114,7 @@ pub struct MailMessage {
     pub s3_key: Option<String>,
 }
 
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MailAppCreate {
     pub name: String,
     pub project_id: String,
@@ -123,7 +123,7 @@ pub struct MailAppCreate {
     pub aws_region: Option<String>,
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct MailAppUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -139,7 +139,7 @@ impl MailAppUpdate {
     }
 }
 
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MailInboxCreate {
     pub local_part: String,
     pub forward_to_email: String,
@@ -147,7 +147,7 @@ pub struct MailInboxCreate {
     pub sender_email: Option<String>,
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct MailInboxUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub local_part: Option<String>,
@@ -163,7 +163,7 @@ impl MailInboxUpdate {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct MailTestEmailRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recipient_email: Option<String>,
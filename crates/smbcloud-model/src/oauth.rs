@@ -18,6 +18,17 @@ pub struct TokenResponse {
     pub id_token: Option<String>,
 }
 
+/// Body of `POST v1/authorize/google`. The server verifies `id_token` with
+/// Google and finds the account by the email inside it, so a client can't
+/// name an account it hasn't signed in to.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GoogleAuthorization {
+    pub id_token: String,
+    /// Create the account when no account has the token's email yet.
+    #[serde(default)]
+    pub sign_up: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[tsync]
 pub struct OauthRedirect {
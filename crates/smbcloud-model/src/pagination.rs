@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// Response header carrying the cursor for the next page of a list endpoint.
+///
+/// List bodies stay bare JSON arrays so older clients keep working; only
+/// paging clients look at this header. It is absent on the last page.
+pub const NEXT_CURSOR_HEADER: &str = "X-Next-Cursor";
+
+/// Query parameters for one page of a list endpoint: `?cursor=&limit=`.
+///
+/// `cursor` is opaque; pass back whatever the previous page returned. Without
+/// a `limit` the API keeps its old behaviour and returns everything in one
+/// page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl PageRequest {
+    /// The first page, `limit` records at a time.
+    pub fn first(limit: Option<u32>) -> Self {
+        Self {
+            cursor: None,
+            limit,
+        }
+    }
+
+    /// The page after one that returned `cursor`, with the same limit.
+    pub fn next(&self, cursor: String) -> Self {
+        Self {
+            cursor: Some(cursor),
+            limit: self.limit,
+        }
+    }
+}
+
+/// One page of a list endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// `None` once there is nothing left to fetch.
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn is_last(&self) -> bool {
+        self.next_cursor.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_page_request_skips_unset_params() {
+        assert_eq!(
+            serde_json::to_value(PageRequest::first(Some(50))).unwrap(),
+            json!({ "limit": 50 })
+        );
+        assert_eq!(
+            serde_json::to_value(PageRequest::first(None).next("abc".to_string())).unwrap(),
+            json!({ "cursor": "abc" })
+        );
+    }
+}
@@ -130,6 +130,11 @@ pub struct Project {
     /// id like "org.swift.632202605101a". Unnecessary when `swift` is already a
     /// swift.org toolchain (e.g. via swiftly).
     pub swift_toolchain: Option<String>,
+    /// Named deploy environments layered over this project, declared as
+    /// `[project.environments.<name>]` in `.smb/config.toml` and selected with
+    /// `smb deploy --env <name>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environments: Option<std::collections::HashMap<String, ProjectEnvironment>>,
 }
 
 impl Display for Project {
@@ -137,6 +142,61 @@ impl Display for Project {
         write!(f, "ID: {}, Name: {}", self.id, self.name,)
     }
 }
+
+impl Project {
+    /// Look up a named deploy environment.
+    pub fn environment(&self, name: &str) -> Option<&ProjectEnvironment> {
+        self.environments.as_ref()?.get(name)
+    }
+
+    /// Names of the declared deploy environments, sorted for display.
+    pub fn environment_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .environments
+            .iter()
+            .flat_map(|environments| environments.keys().map(String::as_str))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Overlay the populated fields of `environment` onto this project.
+    /// `env` entries are merged over any existing `pm2_env` so an environment
+    /// only needs to list the variables that differ.
+    pub fn apply_environment(&mut self, environment: &ProjectEnvironment) {
+        if let Some(frontend_app_id) = &environment.frontend_app_id {
+            self.frontend_app_id = Some(frontend_app_id.clone());
+        }
+        if let Some(path) = &environment.path {
+            self.path = Some(path.clone());
+        }
+        if let Some(port) = environment.port {
+            self.port = Some(port);
+        }
+        if let Some(pm2_app) = &environment.pm2_app {
+            self.pm2_app = Some(pm2_app.clone());
+        }
+        if let Some(env) = &environment.env {
+            self.pm2_env
+                .get_or_insert_with(Default::default)
+                .extend(env.iter().map(|(key, value)| (key.clone(), value.clone())));
+        }
+    }
+}
+
+/// A named deploy target inside one project, e.g. `staging` or `production`.
+/// Each environment usually points at its own frontend app; every field left
+/// out falls back to the project's value.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[tsync]
+pub struct ProjectEnvironment {
+    pub frontend_app_id: Option<String>,
+    pub path: Option<String>,
+    pub port: Option<u16>,
+    pub pm2_app: Option<String>,
+    /// Environment variables merged over the app's `pm2_env`.
+    pub env: Option<std::collections::HashMap<String, serde_json::Value>>,
+}
 /// Payload for creating the umbrella workspace. Deploy concerns (runner,
 /// repository, deployment method) live on the App, not the Project.
 #[derive(Serialize, Debug, Deserialize, Clone)]
@@ -146,7 +206,7 @@ pub struct ProjectCreate {
     pub description: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[tsync]
 pub struct Deployment {
     pub id: i32,
@@ -155,6 +215,10 @@ pub struct Deployment {
     pub frontend_app_name: Option<String>,
     pub commit_hash: String,
     pub status: DeploymentStatus,
+    /// Deploy environment name (`smb deploy --env`), absent for deployments
+    /// made without one.
+    #[serde(default)]
+    pub environment: Option<String>,
     #[serde(with = "ar_date_format")]
     pub created_at: DateTime<Utc>,
     #[serde(with = "ar_date_format")]
@@ -166,6 +230,8 @@ pub struct DeploymentPayload {
     pub commit_hash: String,
     pub status: DeploymentStatus,
     pub frontend_app_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
 }
 
 #[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy)] // Added Clone, Copy
@@ -215,4 +281,35 @@ mod tests {
         assert_eq!(format!("{}", DeploymentStatus::Done), "✅");
         assert_eq!(DeploymentStatus::Done.to_string(), "✅");
     }
+
+    #[test]
+    fn test_apply_environment_overlays_populated_fields() {
+        let mut project: Project = serde_json::from_value(json!({
+            "id": 1,
+            "name": "web",
+            "frontend_app_id": "app-production",
+            "path": "apps/web/web",
+            "port": 3000,
+            "pm2_env": { "LOG_LEVEL": "info", "API_URL": "https://api.example.com" },
+            "environments": {
+                "staging": {
+                    "frontend_app_id": "app-staging",
+                    "port": 3001,
+                    "env": { "API_URL": "https://staging.example.com" }
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(project.environment_names(), vec!["staging"]);
+        let staging = project.environment("staging").unwrap().clone();
+        project.apply_environment(&staging);
+
+        assert_eq!(project.frontend_app_id.as_deref(), Some("app-staging"));
+        assert_eq!(project.path.as_deref(), Some("apps/web/web"));
+        assert_eq!(project.port, Some(3001));
+        let pm2_env = project.pm2_env.unwrap();
+        assert_eq!(pm2_env["API_URL"], json!("https://staging.example.com"));
+        assert_eq!(pm2_env["LOG_LEVEL"], json!("info"));
+    }
 }
@@ -83,6 +83,10 @@ impl Runner {
 
     /// Returns the explicit hostname used for rsync SSH connections.
     /// e.g. `api.smbcloud.xyz` or `api-1.smbcloud.xyz`
+    ///
+    /// This is the hosted smbCloud tier; deploys should go through
+    /// `Environment::deploy_host`, which swaps in `SMB_DEPLOY_HOST` for
+    /// self-hosted and staging targets.
     pub fn rsync_host(&self) -> String {
         format!("{}.smbcloud.xyz", self.api())
     }
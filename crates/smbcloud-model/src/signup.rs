@@ -18,6 +18,10 @@ pub struct Provider {
     pub provider: i8,
 }
 
+impl Provider {
+    pub const GITHUB: i8 = 0;
+}
+
 #[derive(Debug, Serialize)]
 pub struct SignupGithubParams {
     pub user: SignupUserGithub,
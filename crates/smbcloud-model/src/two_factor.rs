@@ -0,0 +1,51 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// A new TOTP secret, not active until a code from it is confirmed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[tsync]
+pub struct TwoFactorSetup {
+    /// Base32 secret, for authenticator apps that can't scan a QR code.
+    pub secret: String,
+    /// `otpauth://totp/...` URI to show as a QR code.
+    pub provisioning_uri: String,
+}
+
+/// Body of the requests that need a current code: confirming the setup,
+/// turning two-factor authentication off and regenerating recovery codes.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OtpParams {
+    pub otp_attempt: String,
+}
+
+/// One-time codes that stand in for a TOTP code when the authenticator is
+/// lost. Only shown when they are generated.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[tsync]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    #[test]
+    fn test_two_factor_setup() {
+        let setup: TwoFactorSetup = serde_json::from_value(json!({
+            "secret": "JBSWY3DPEHPK3PXP",
+            "provisioning_uri": "otpauth://totp/smbCloud:a%40b.c?secret=JBSWY3DPEHPK3PXP&issuer=smbCloud",
+        }))
+        .unwrap();
+        assert_eq!(setup.secret, "JBSWY3DPEHPK3PXP");
+        assert_eq!(
+            serde_json::to_value(OtpParams {
+                otp_attempt: "123456".to_owned()
+            })
+            .unwrap(),
+            json!({ "otp_attempt": "123456" })
+        );
+    }
+}
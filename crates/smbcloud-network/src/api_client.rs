@@ -0,0 +1,592 @@
+use {
+    crate::{
+        environment::Environment,
+        network::{parse_empty_response, parse_response},
+        pagination::parse_page,
+        session::session_renewer,
+    },
+    log::{debug, error, warn},
+    reqwest::{
+        Client, Method, RequestBuilder, Response, StatusCode,
+        header::{AUTHORIZATION, HeaderValue},
+    },
+    serde::de::DeserializeOwned,
+    smbcloud_model::{
+        error_codes::{ErrorCode, ErrorResponse},
+        pagination::Page,
+    },
+    std::{sync::OnceLock, time::Duration},
+};
+
+/// Header the API reads to pick the tenant a request acts on.
+pub const TENANT_HEADER: &str = "X-Smbcloud-Tenant-Id";
+
+/// Whole-request timeout for API calls. Uploads go over rsync or git, so no
+/// API call should come close to this.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long to wait for the TCP and TLS handshake.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The pooled client behind every API call in the process.
+///
+/// `reqwest::Client` keeps its connection pool behind an `Arc`, so clones are
+/// cheap and share the same keep-alive connections.
+pub fn shared_client() -> Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            build_http_client(DEFAULT_TIMEOUT, DEFAULT_CONNECT_TIMEOUT).unwrap_or_else(|e| {
+                error!("Failed to build the shared HTTP client: {:?}", e);
+                Client::new()
+            })
+        })
+        .clone()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn build_http_client(timeout: Duration, connect_timeout: Duration) -> reqwest::Result<Client> {
+    Client::builder()
+        .timeout(timeout)
+        .connect_timeout(connect_timeout)
+        .build()
+}
+
+/// The browser owns timeouts and pooling on wasm.
+#[cfg(target_arch = "wasm32")]
+fn build_http_client(_timeout: Duration, _connect_timeout: Duration) -> reqwest::Result<Client> {
+    Client::builder().build()
+}
+
+/// When and how long to back off before sending a request again.
+///
+/// Idempotent requests (`GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS`) are retried
+/// on `5xx` and on transport failures. Any request is retried on `429` and when
+/// the connection could not be opened, since the server never saw it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt. `0` disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each one after.
+    pub base_delay: Duration,
+    /// Upper bound for any single delay, including one asked for by `Retry-After`.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `attempt` (starting at 0). A `Retry-After`
+    /// from the server wins over the backoff, capped at `max_delay`.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = retry_after.unwrap_or_else(|| {
+            self.base_delay
+                .saturating_mul(2u32.saturating_pow(attempt.min(16)))
+        });
+        delay.min(self.max_delay)
+    }
+}
+
+/// One client for the smbCloud API: a pooled connection, the caller's token
+/// and tenant, and the timeout and retry settings every request goes out with.
+///
+/// ```ignore
+/// let api = SmbApiClient::new(env).with_access_token(token);
+/// let tenants: Vec<Tenant> = api.send(api.get(url)).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SmbApiClient {
+    environment: Environment,
+    http: Client,
+    user_agent: Option<String>,
+    access_token: Option<String>,
+    tenant_id: Option<String>,
+    retry: RetryPolicy,
+}
+
+impl SmbApiClient {
+    /// Uses the process-wide pool, the default timeouts and [`RetryPolicy::default`].
+    pub fn new(environment: Environment) -> Self {
+        Self {
+            environment,
+            http: shared_client(),
+            user_agent: None,
+            access_token: None,
+            tenant_id: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Starts a client with its own pool and timeouts.
+    pub fn builder(environment: Environment) -> SmbApiClientBuilder {
+        SmbApiClientBuilder {
+            environment,
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_access_token(mut self, access_token: impl Into<String>) -> Self {
+        self.access_token = Some(access_token.into());
+        self
+    }
+
+    /// Sends `X-Smbcloud-Tenant-Id` on every request. `None` leaves it off, so
+    /// the API falls back to the user's personal tenant.
+    pub fn with_tenant_id(mut self, tenant_id: Option<String>) -> Self {
+        self.tenant_id = tenant_id;
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn environment(&self) -> Environment {
+        self.environment
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    /// A request to `url` carrying the token, tenant and user agent headers.
+    pub fn request(&self, method: Method, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        let mut builder = self.http.request(method, url);
+        if let Some(access_token) = &self.access_token {
+            builder = builder.header("Authorization", access_token);
+        }
+        if let Some(tenant_id) = &self.tenant_id {
+            builder = builder.header(TENANT_HEADER, tenant_id);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.header("User-agent", user_agent);
+        }
+        builder
+    }
+
+    pub fn get(&self, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    pub fn put(&self, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.request(Method::PUT, url)
+    }
+
+    pub fn patch(&self, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.request(Method::PATCH, url)
+    }
+
+    pub fn delete(&self, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+
+    /// Sends with retries and parses a `200`/`201` JSON body into `R`.
+    pub async fn send<R: DeserializeOwned>(
+        &self,
+        builder: RequestBuilder,
+    ) -> Result<R, ErrorResponse> {
+        parse_response(send(builder, &self.retry).await?).await
+    }
+
+    /// Sends with retries for endpoints that reply with no body, such as a
+    /// `DELETE` answering `204 No Content`.
+    pub async fn send_empty(&self, builder: RequestBuilder) -> Result<(), ErrorResponse> {
+        parse_empty_response(send(builder, &self.retry).await?).await
+    }
+
+    /// Sends a list request for one page, like
+    /// [`request_page`](crate::pagination::request_page), with this client's
+    /// retry policy.
+    pub async fn send_page<R: DeserializeOwned>(
+        &self,
+        builder: RequestBuilder,
+    ) -> Result<Page<R>, ErrorResponse> {
+        parse_page(send(builder, &self.retry).await?).await
+    }
+}
+
+pub struct SmbApiClientBuilder {
+    environment: Environment,
+    timeout: Duration,
+    connect_timeout: Duration,
+    retry: RetryPolicy,
+}
+
+impl SmbApiClientBuilder {
+    /// Whole-request timeout. Ignored on wasm, where the browser decides.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Handshake timeout. Ignored on wasm, where the browser decides.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn build(self) -> Result<SmbApiClient, ErrorResponse> {
+        let http = build_http_client(self.timeout, self.connect_timeout).map_err(|e| {
+            error!("Failed to build HTTP client: {:?}", e);
+            ErrorResponse::Error {
+                error_code: ErrorCode::NetworkError,
+                message: ErrorCode::NetworkError.message(None).to_string(),
+            }
+        })?;
+        Ok(SmbApiClient {
+            http,
+            retry: self.retry,
+            ..SmbApiClient::new(self.environment)
+        })
+    }
+}
+
+/// Sends `builder`, retrying per `retry`, and returns whatever response came
+/// back last. Only transport failures become an `Err`, already classified.
+///
+/// A `401` to a request with an `Authorization` header is sent once more if
+/// the installed [`crate::session::SessionRenewer`] renews the session.
+pub(crate) async fn send(
+    builder: RequestBuilder,
+    retry: &RetryPolicy,
+) -> Result<Response, ErrorResponse> {
+    let Some(renewer) = session_renewer() else {
+        return send_with_retries(builder, retry).await;
+    };
+    let again = builder.try_clone();
+    let response = send_with_retries(builder, retry).await?;
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+    let Some((client, Ok(mut request))) = again.map(RequestBuilder::build_split) else {
+        return Ok(response);
+    };
+    let Some(rejected) = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned)
+    else {
+        return Ok(response);
+    };
+    let Some(renewed) = renewer
+        .renew(&rejected)
+        .await
+        .and_then(|renewed| HeaderValue::from_str(&renewed).ok())
+    else {
+        return Ok(response);
+    };
+    debug!("Session renewed, sending the request again.");
+    request.headers_mut().insert(AUTHORIZATION, renewed);
+    send_with_retries(RequestBuilder::from_parts(client, request), retry).await
+}
+
+async fn send_with_retries(
+    builder: RequestBuilder,
+    retry: &RetryPolicy,
+) -> Result<Response, ErrorResponse> {
+    let idempotent = builder
+        .try_clone()
+        .and_then(|builder| builder.build().ok())
+        .is_some_and(|request| is_idempotent(request.method()));
+
+    let mut builder = builder;
+    let mut attempt = 0;
+    loop {
+        // Streaming bodies can't be cloned, so those only go out once.
+        let next = (attempt < retry.max_retries && can_sleep())
+            .then(|| builder.try_clone())
+            .flatten();
+        let outcome = builder.send().await;
+        let Some(next) = next else {
+            return outcome.map_err(classify_send_error);
+        };
+
+        let delay = match &outcome {
+            Ok(response) if should_retry_status(response.status(), idempotent) => {
+                retry.delay(attempt, retry_after(response))
+            }
+            Err(e) if should_retry_error(e, idempotent) => retry.delay(attempt, None),
+            _ => return outcome.map_err(classify_send_error),
+        };
+        match &outcome {
+            Ok(response) => warn!(
+                "{} from {:?}, retrying in {:?}",
+                response.status(),
+                response.url().host_str(),
+                delay
+            ),
+            Err(e) => warn!("Request failed ({:?}), retrying in {:?}", e, delay),
+        }
+        sleep(delay).await;
+        builder = next;
+        attempt += 1;
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+fn should_retry_status(status: StatusCode, idempotent: bool) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || (idempotent && status.is_server_error())
+}
+
+fn should_retry_error(error: &reqwest::Error, idempotent: bool) -> bool {
+    idempotent || is_connect_error(error)
+}
+
+/// `Retry-After` in seconds. The HTTP-date form is rare enough from our API
+/// that it falls back to the normal backoff.
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Turns a failed send into a message that says what went wrong: the host
+/// could not be reached, or it did not answer in time.
+pub(crate) fn classify_send_error(error: reqwest::Error) -> ErrorResponse {
+    error!("Failed to get response: {:?}", error);
+    let host = error
+        .url()
+        .and_then(|url| url.host_str())
+        .unwrap_or("the smbCloud API")
+        .to_string();
+    let message = if error.is_timeout() {
+        format!("{host} did not respond in time. Please try again.")
+    } else if is_connect_error(&error) {
+        format!("Could not reach {host}. Please check your internet connection and try again.")
+    } else {
+        ErrorCode::NetworkError.message(None).to_string()
+    };
+    ErrorResponse::Error {
+        error_code: ErrorCode::NetworkError,
+        message,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect_error(error: &reqwest::Error) -> bool {
+    error.is_connect()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn is_connect_error(_error: &reqwest::Error) -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn can_sleep() -> bool {
+    true
+}
+
+/// There is no timer to back off with on wasm, so requests go out once.
+#[cfg(target_arch = "wasm32")]
+fn can_sleep() -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(delay: Duration) {
+    tokio::time::sleep(delay).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(_delay: Duration) {}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde::Deserialize,
+        std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        },
+    };
+
+    #[test]
+    fn delay_doubles_and_caps() {
+        let retry = RetryPolicy::default();
+        assert_eq!(retry.delay(0, None), Duration::from_millis(250));
+        assert_eq!(retry.delay(2, None), Duration::from_secs(1));
+        assert_eq!(retry.delay(10, None), retry.max_delay);
+        assert_eq!(
+            retry.delay(0, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            retry.delay(0, Some(Duration::from_secs(600))),
+            retry.max_delay
+        );
+    }
+
+    #[test]
+    fn only_idempotent_requests_retry_on_server_errors() {
+        assert!(should_retry_status(StatusCode::SERVICE_UNAVAILABLE, true));
+        assert!(!should_retry_status(StatusCode::SERVICE_UNAVAILABLE, false));
+        assert!(should_retry_status(StatusCode::TOO_MANY_REQUESTS, false));
+        assert!(!should_retry_status(StatusCode::NOT_FOUND, true));
+    }
+
+    /// Serves one canned response per connection, in order.
+    async fn serve(responses: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = [0; 4096];
+                let _ = socket.read(&mut buffer).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, hits)
+    }
+
+    #[derive(Deserialize)]
+    struct Pong {
+        ok: bool,
+    }
+
+    #[tokio::test]
+    async fn retries_get_after_unavailable() {
+        let (url, hits) = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: 11\r\n\r\n{\"ok\":true}",
+        ])
+        .await;
+        let api = SmbApiClient::new(Environment::Dev).with_access_token("token");
+
+        let pong: Pong = api.send(api.get(&url)).await.unwrap();
+
+        assert!(pong.ok);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_post_after_server_error() {
+        let (url, hits) = serve(vec![
+            "HTTP/1.1 500 Internal Server Error\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: 38\r\n\r\n{\"error_code\":0,\"message\":\"Exploded.\"}",
+        ])
+        .await;
+        let api = SmbApiClient::new(Environment::Dev);
+
+        let result = api.send_empty(api.post(&url)).await;
+
+        assert!(matches!(
+            result,
+            Err(ErrorResponse::Error { ref message, .. }) if message == "Exploded."
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    struct RenewOld;
+
+    impl crate::session::SessionRenewer for RenewOld {
+        fn renew<'a>(&'a self, rejected: &'a str) -> crate::session::RenewFuture<'a> {
+            Box::pin(async move { (rejected == "Bearer old").then(|| "Bearer new".to_string()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn renews_the_session_once_after_unauthorized() {
+        crate::session::set_session_renewer(RenewOld);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = [0; 4096];
+                let read = socket.read(&mut buffer).await.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                let request = String::from_utf8_lossy(&buffer[..read]).to_ascii_lowercase();
+                let response = if request.contains("authorization: bearer new") {
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: 11\r\n\r\n{\"ok\":true}"
+                } else {
+                    "HTTP/1.1 401 Unauthorized\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: 51\r\n\r\n{\"error_code\":100,\"message\":\"Unauthorized access.\"}"
+                };
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let api = SmbApiClient::new(Environment::Dev).with_access_token("Bearer old");
+        let pong: Pong = api.send(api.post(&url)).await.unwrap();
+        assert!(pong.ok);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // A token the renewer can't help with fails after one attempt.
+        let api = SmbApiClient::new(Environment::Dev).with_access_token("Bearer stale");
+        assert!(api.send::<Pong>(api.get(&url)).await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn unreachable_host_names_the_host() {
+        // Bind then drop, so the port is very likely closed.
+        let address = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let api = SmbApiClient::new(Environment::Dev).with_retry_policy(RetryPolicy::none());
+
+        let result = api.send_empty(api.get(format!("http://{address}"))).await;
+
+        assert!(matches!(
+            result,
+            Err(ErrorResponse::Error { error_code: ErrorCode::NetworkError, ref message })
+                if message.starts_with("Could not reach 127.0.0.1")
+        ));
+    }
+}
@@ -1,13 +1,28 @@
 use {
     serde::{Deserialize, Serialize},
+    smbcloud_model::runner::Runner,
     wasm_bindgen::prelude::wasm_bindgen,
 };
 
-#[derive(clap::ValueEnum, Clone, Copy, Serialize, Deserialize)]
+/// Base URL of the API used by [`Environment::Custom`], e.g.
+/// `https://api.staging.example.com` or `http://127.0.0.1:4010`.
+pub const API_URL_ENV: &str = "SMB_API_URL";
+
+/// SSH host that rsync/git deploys connect to under [`Environment::Custom`].
+/// Defaults to the hostname of [`API_URL_ENV`].
+pub const DEPLOY_HOST_ENV: &str = "SMB_DEPLOY_HOST";
+
+/// Used by [`Environment::Custom`] when `SMB_API_URL` is not set.
+const CUSTOM_DEFAULT_API_URL: &str = "http://localhost:8088";
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Serialize, Deserialize)]
 #[wasm_bindgen]
 pub enum Environment {
     Dev,
     Production,
+    /// A staging API, a self-hosted instance or a local mock server, resolved
+    /// from `SMB_API_URL` and `SMB_DEPLOY_HOST`.
+    Custom,
 }
 
 impl std::fmt::Display for Environment {
@@ -23,6 +38,7 @@ impl std::str::FromStr for Environment {
         match s.to_lowercase().as_str() {
             "dev" => Ok(Environment::Dev),
             "production" => Ok(Environment::Production),
+            "custom" => Ok(Environment::Custom),
             _ => Err(()),
         }
     }
@@ -33,12 +49,14 @@ impl Environment {
         match self {
             Environment::Dev => "dev",
             Environment::Production => "production",
+            Environment::Custom => "custom",
         }
     }
     pub fn smb_dir(&self) -> String {
         match self {
             Environment::Dev => ".smb-dev".to_string(),
             Environment::Production => ".smb".to_string(),
+            Environment::Custom => ".smb-custom".to_string(),
         }
     }
 
@@ -46,12 +64,88 @@ impl Environment {
         match self {
             Environment::Dev => "http".to_string(),
             Environment::Production => "https".to_string(),
+            Environment::Custom => split_url(&custom_api_url()).0,
         }
     }
     pub fn api_host(&self) -> String {
         match self {
             Environment::Dev => "localhost:8088".to_string(),
             Environment::Production => "api.smbcloud.xyz".to_string(),
+            Environment::Custom => split_url(&custom_api_url()).1,
+        }
+    }
+
+    /// `<protocol>://<host>` without a trailing slash, the base every SDK
+    /// client and URL builder hangs its routes off.
+    pub fn api_base_url(&self) -> String {
+        format!("{}://{}", self.api_protocol(), self.api_host())
+    }
+
+    /// SSH host that rsync deploys for `runner` connect to.
+    pub fn deploy_host(&self, runner: &Runner) -> String {
+        match self {
+            Environment::Dev | Environment::Production => runner.rsync_host(),
+            Environment::Custom => std::env::var(DEPLOY_HOST_ENV)
+                .ok()
+                .filter(|host| !host.trim().is_empty())
+                .unwrap_or_else(|| {
+                    let host = self.api_host();
+                    let host = host.split('/').next().unwrap_or_default();
+                    host.split(':').next().unwrap_or_default().to_string()
+                }),
+        }
+    }
+
+    /// Git remote host (`git@<host>`) for git-push deploys of `runner`.
+    pub fn git_host(&self, runner: &Runner) -> String {
+        match self {
+            Environment::Dev | Environment::Production => runner.git_host(),
+            Environment::Custom => format!("git@{}", self.deploy_host(runner)),
         }
     }
 }
+
+fn custom_api_url() -> String {
+    std::env::var(API_URL_ENV)
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| CUSTOM_DEFAULT_API_URL.to_string())
+}
+
+/// Splits `scheme://host[:port][/path]` into `(scheme, host[:port][/path])`.
+/// A URL without a scheme is taken as https.
+fn split_url(url: &str) -> (String, String) {
+    let url = url.trim().trim_end_matches('/');
+    match url.split_once("://") {
+        Some((protocol, host)) => (protocol.to_lowercase(), host.to_string()),
+        None => ("https".to_string(), url.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_url_keeps_port_and_path() {
+        assert_eq!(
+            split_url("http://127.0.0.1:4010/"),
+            ("http".to_string(), "127.0.0.1:4010".to_string())
+        );
+        assert_eq!(
+            split_url("HTTPS://smb.example.com/api"),
+            ("https".to_string(), "smb.example.com/api".to_string())
+        );
+        assert_eq!(
+            split_url("api.staging.example.com"),
+            ("https".to_string(), "api.staging.example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn custom_round_trips_through_from_str() {
+        let env: Environment = "custom".parse().unwrap();
+        assert_eq!(env.to_str(), "custom");
+        assert_eq!(env.smb_dir(), ".smb-custom");
+    }
+}
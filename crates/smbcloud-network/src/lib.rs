@@ -1,2 +1,5 @@
+pub mod api_client;
 pub mod environment;
 pub mod network;
+pub mod pagination;
+pub mod session;
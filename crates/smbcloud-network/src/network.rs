@@ -1,61 +1,20 @@
 use {
+    crate::api_client::{RetryPolicy, send},
     log::{debug, error},
     reqwest::{RequestBuilder, Response, StatusCode},
     serde::de::DeserializeOwned,
     smbcloud_model::{
-        account::SmbAuthorization,
+        account::{ErrorCode as AccountErrorCode, SmbAuthorization},
         error_codes::{ErrorCode, ErrorResponse},
         login::AccountStatus,
     },
 };
 
-//use std::time::Duration;
 #[cfg(debug_assertions)]
 const LOG_RESPONSE_BODY: bool = true; // You know what to do here.
 #[cfg(not(debug_assertions))]
 const LOG_RESPONSE_BODY: bool = false;
 
-/// Check if there is an active internet connection.
-///
-/// Native clients can afford a preflight connectivity check. Browser/wasm
-/// clients should skip it and rely on the real request, otherwise the check
-/// itself becomes a separate cross-origin failure surface.
-#[cfg(not(target_arch = "wasm32"))]
-pub async fn check_internet_connection() -> bool {
-    debug!("Checking internet connection");
-    let client = reqwest::Client::builder()
-        //.timeout(Duration::from_secs(5)) Does not work on wasm32
-        .build();
-
-    if let Err(e) = client {
-        error!("Failed to create client for connectivity check: {:?}", e);
-        return false;
-    }
-
-    if let Ok(client) = client {
-        match client.get("https://dns.google").send().await {
-            Ok(response) => {
-                debug!(
-                    "Internet connection check successful: {}",
-                    response.status()
-                );
-                response.status().is_success()
-            }
-            Err(e) => {
-                error!("Internet connection check failed: {:?}", e);
-                false
-            }
-        }
-    } else {
-        false
-    }
-}
-
-#[cfg(target_arch = "wasm32")]
-pub async fn check_internet_connection() -> bool {
-    true
-}
-
 pub async fn parse_error_response<T: DeserializeOwned>(
     response: Response,
 ) -> Result<T, ErrorResponse> {
@@ -93,17 +52,7 @@ pub async fn parse_error_response<T: DeserializeOwned>(
 }
 
 pub async fn request_login(builder: RequestBuilder) -> Result<AccountStatus, ErrorResponse> {
-    let response = builder.send().await;
-    let response = match response {
-        Ok(response) => response,
-        Err(e) => {
-            error!("request_login: Failed to get response: {:?}", e);
-            return Err(ErrorResponse::Error {
-                error_code: ErrorCode::NetworkError,
-                message: ErrorCode::NetworkError.message(None).to_string(),
-            });
-        }
-    };
+    let response = send(builder, &RetryPolicy::default()).await?;
 
     if LOG_RESPONSE_BODY {
         debug!("request_login: Parse >>>> {:?}", response.status());
@@ -125,9 +74,10 @@ pub async fn request_login(builder: RequestBuilder) -> Result<AccountStatus, Err
         }
         (StatusCode::OK, None) => {
             // Silent login from oauth. Need improvement.
+            // `parse_error_response` hands a parsed body back as its error;
+            // a body it can't parse comes back as `ParseError`, i.e. `NotFound`.
             let error_response = match parse_error_response::<ErrorResponse>(response).await {
-                Ok(error) => error,
-                Err(_) => return Ok(AccountStatus::NotFound),
+                Ok(error) | Err(error) => error,
             };
             match error_response {
                 ErrorResponse::Error {
@@ -140,6 +90,7 @@ pub async fn request_login(builder: RequestBuilder) -> Result<AccountStatus, Err
                     ErrorCode::PasswordNotSet => Ok(AccountStatus::Incomplete {
                         status: smbcloud_model::account::ErrorCode::PasswordNotSet,
                     }),
+                    ErrorCode::OtpRequired => Ok(AccountStatus::OtpRequired),
                     ErrorCode::Unknown => Ok(AccountStatus::Ready {
                         access_token: "tokenization".to_string(),
                     }),
@@ -192,7 +143,10 @@ pub async fn request_login(builder: RequestBuilder) -> Result<AccountStatus, Err
                     });
                 }
             };
-            Ok(AccountStatus::Incomplete { status: error_code })
+            match error_code {
+                AccountErrorCode::OtpRequired => Ok(AccountStatus::OtpRequired),
+                _ => Ok(AccountStatus::Incomplete { status: error_code }),
+            }
         }
         (StatusCode::UNAUTHORIZED, _) => {
             let body_text = match response.text().await {
@@ -208,6 +162,8 @@ pub async fn request_login(builder: RequestBuilder) -> Result<AccountStatus, Err
             // Try parsing as SmbAuthorization first.
             if let Ok(result) = serde_json::from_str::<SmbAuthorization>(&body_text) {
                 let error_code = match result.error_code {
+                    // Right password, but a TOTP code has to come with it.
+                    Some(AccountErrorCode::OtpRequired) => return Ok(AccountStatus::OtpRequired),
                     Some(code) => code,
                     None => {
                         // Response parsed but no error_code — surface the server's message.
@@ -250,28 +206,23 @@ pub async fn request_login(builder: RequestBuilder) -> Result<AccountStatus, Err
     }
 }
 
+/// Sends `builder` with the default [`RetryPolicy`] and parses a `200`/`201`
+/// JSON body into `R`.
 pub async fn request<R: DeserializeOwned>(builder: RequestBuilder) -> Result<R, ErrorResponse> {
-    // Check internet connection before making the request
-    if !check_internet_connection().await {
-        error!("No internet connection available");
-        return Err(ErrorResponse::Error {
-            error_code: ErrorCode::NetworkError,
-            message: "No internet connection. Please check your network settings and try again."
-                .to_string(),
-        });
-    }
+    parse_response(send(builder, &RetryPolicy::default()).await?).await
+}
 
-    let response = builder.send().await;
-    let response = match response {
-        Ok(response) => response,
-        Err(e) => {
-            error!("Failed to get response: {:?}", e);
-            return Err(ErrorResponse::Error {
-                error_code: ErrorCode::NetworkError,
-                message: ErrorCode::NetworkError.message(None).to_string(),
-            });
-        }
-    };
+/// Like [`request`], but for endpoints that reply with an empty body (e.g.
+/// `DELETE` returning `204 No Content`). [`request`] only treats `200`/`201`
+/// as success and then tries to parse the body as JSON, so it fails on a bare
+/// `204`.
+pub async fn request_empty(builder: RequestBuilder) -> Result<(), ErrorResponse> {
+    parse_empty_response(send(builder, &RetryPolicy::default()).await?).await
+}
+
+pub(crate) async fn parse_response<R: DeserializeOwned>(
+    response: Response,
+) -> Result<R, ErrorResponse> {
     let response = match response.status() {
         reqwest::StatusCode::OK | reqwest::StatusCode::CREATED => response,
         status => {
@@ -280,7 +231,6 @@ pub async fn request<R: DeserializeOwned>(builder: RequestBuilder) -> Result<R,
             return parse_error_response(response).await;
         }
     };
-
     let response_body = match response.text().await {
         Ok(body) => body,
         Err(e) => {
@@ -312,3 +262,73 @@ pub async fn request<R: DeserializeOwned>(builder: RequestBuilder) -> Result<R,
 
     Ok(response)
 }
+
+pub(crate) async fn parse_empty_response(response: Response) -> Result<(), ErrorResponse> {
+    match response.status() {
+        StatusCode::OK | StatusCode::CREATED | StatusCode::NO_CONTENT => Ok(()),
+        _ => match parse_error_response::<serde_json::Value>(response).await {
+            Ok(_) => Err(ErrorResponse::Error {
+                error_code: ErrorCode::ParseError,
+                message: ErrorCode::ParseError.message(None).to_string(),
+            }),
+            Err(error_response) => Err(error_response),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::api_client::shared_client,
+        tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        },
+    };
+
+    /// Answers one request with `status` and the JSON `body`.
+    async fn serve_once(status: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 4096];
+            let _ = socket.read(&mut buffer).await;
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn otp_required_is_an_account_status_on_every_status_code() {
+        let body = r#"{"message":"Two-factor code required.","error_code":1009}"#;
+        for status in ["200 OK", "422 Unprocessable Entity", "401 Unauthorized"] {
+            let url = serve_once(status, body).await;
+            let account_status = request_login(shared_client().post(&url)).await;
+            assert!(
+                matches!(account_status, Ok(AccountStatus::OtpRequired)),
+                "{status}: {account_status:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn a_wrong_otp_is_an_unauthorized_error() {
+        let url = serve_once(
+            "401 Unauthorized",
+            r#"{"message":"Invalid two-factor code.","error_code":1010}"#,
+        )
+        .await;
+        let Err(ErrorResponse::Error { error_code, .. }) =
+            request_login(shared_client().post(&url)).await
+        else {
+            panic!("a wrong code must not log in");
+        };
+        assert!(matches!(error_code, ErrorCode::Unauthorized));
+    }
+}
@@ -0,0 +1,104 @@
+use {
+    crate::{
+        api_client::{RetryPolicy, send},
+        network::parse_response,
+    },
+    reqwest::{RequestBuilder, Response},
+    serde::de::DeserializeOwned,
+    smbcloud_model::{
+        error_codes::ErrorResponse,
+        pagination::{NEXT_CURSOR_HEADER, Page, PageRequest},
+    },
+    std::future::Future,
+};
+
+/// Sends a list request for one page and reads the next cursor from the
+/// `X-Next-Cursor` header. Add the [`PageRequest`] to the builder with
+/// `.query(page)` first.
+pub async fn request_page<R: DeserializeOwned>(
+    builder: RequestBuilder,
+) -> Result<Page<R>, ErrorResponse> {
+    parse_page(send(builder, &RetryPolicy::default()).await?).await
+}
+
+pub(crate) async fn parse_page<R: DeserializeOwned>(
+    response: Response,
+) -> Result<Page<R>, ErrorResponse> {
+    let next_cursor = response
+        .headers()
+        .get(NEXT_CURSOR_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|cursor| !cursor.is_empty())
+        .map(str::to_string);
+    let items = parse_response(response).await?;
+    Ok(Page { items, next_cursor })
+}
+
+/// Fetches every page, `page_size` records at a time, and returns them in
+/// order. `fetch` is called with each [`PageRequest`] in turn.
+pub async fn collect_pages<T, F, Fut>(
+    page_size: Option<u32>,
+    mut fetch: F,
+) -> Result<Vec<T>, ErrorResponse>
+where
+    F: FnMut(PageRequest) -> Fut,
+    Fut: Future<Output = Result<Page<T>, ErrorResponse>>,
+{
+    let mut items = Vec::new();
+    let mut page_request = PageRequest::first(page_size);
+    loop {
+        let page = fetch(page_request.clone()).await?;
+        items.extend(page.items);
+        match page.next_cursor {
+            // A server handing back the same cursor would loop forever.
+            Some(cursor) if page_request.cursor.as_ref() != Some(&cursor) => {
+                page_request = page_request.next(cursor);
+            }
+            _ => return Ok(items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn collects_until_the_last_page() {
+        let pages = [vec![1, 2], vec![3, 4], vec![5]];
+        let mut requests = Vec::new();
+
+        let items = collect_pages(Some(2), |page_request| {
+            requests.push(page_request.clone());
+            let index = page_request
+                .cursor
+                .map(|cursor| cursor.parse::<usize>().unwrap())
+                .unwrap_or_default();
+            let page = Page {
+                items: pages[index].clone(),
+                next_cursor: (index + 1 < pages.len()).then(|| (index + 1).to_string()),
+            };
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3, 4, 5]);
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|request| request.limit == Some(2)));
+    }
+
+    #[tokio::test]
+    async fn stops_on_a_repeated_cursor() {
+        let items = collect_pages(None, |_| async {
+            Ok(Page {
+                items: vec![1],
+                next_cursor: Some("same".to_string()),
+            })
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(items, vec![1, 1]);
+    }
+}
@@ -0,0 +1,28 @@
+//! Renewing an expired session mid-flight.
+//!
+//! When a request that carried an `Authorization` header comes back `401`,
+//! the renewer installed here gets one chance to hand out a fresh value, and
+//! the request is sent once more with it. Without a renewer, or when it
+//! returns `None`, the `401` goes back to the caller as before.
+
+use std::{future::Future, pin::Pin, sync::OnceLock};
+
+pub type RenewFuture<'a> = Pin<Box<dyn Future<Output = Option<String>> + Send + 'a>>;
+
+pub trait SessionRenewer: Send + Sync {
+    /// A new `Authorization` value to send instead of `rejected`, or `None`
+    /// when the session can't be renewed and the user has to log in again.
+    fn renew<'a>(&'a self, rejected: &'a str) -> RenewFuture<'a>;
+}
+
+static RENEWER: OnceLock<Box<dyn SessionRenewer>> = OnceLock::new();
+
+/// Installs the process-wide renewer. Only the first call takes effect; it
+/// returns `false` when a renewer was already installed.
+pub fn set_session_renewer(renewer: impl SessionRenewer + 'static) -> bool {
+    RENEWER.set(Box::new(renewer)).is_ok()
+}
+
+pub(crate) fn session_renewer() -> Option<&'static dyn SessionRenewer> {
+    RENEWER.get().map(|renewer| renewer.as_ref())
+}
@@ -1,12 +1,11 @@
 use {
     crate::url_builder::build_frontend_apps_url,
-    reqwest::Client,
     smbcloud_model::{
         error_codes::ErrorResponse,
         frontend_app::{FrontendApp, FrontendAppCreate},
     },
-    smbcloud_network::{environment::Environment, network::request},
-    smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient},
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::{api_client, smb_client::SmbClient},
 };
 
 pub async fn create_frontend_app(
@@ -15,10 +14,9 @@ pub async fn create_frontend_app(
     access_token: String,
     payload: FrontendAppCreate,
 ) -> Result<FrontendApp, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .post(build_frontend_apps_url(env, client))
-        .json(&payload)
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&payload);
+    api.send(builder).await
 }
@@ -1,9 +1,8 @@
 use {
     crate::url_builder::build_frontend_app_deploy_config_url,
-    reqwest::Client,
     smbcloud_model::{deploy_config::DeployConfig, error_codes::ErrorResponse},
-    smbcloud_network::{environment::Environment, network::request},
-    smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient},
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::{api_client, smb_client::SmbClient},
 };
 
 pub async fn get_deploy_config(
@@ -13,9 +12,6 @@ pub async fn get_deploy_config(
     frontend_app_id: &str,
 ) -> Result<DeployConfig, ErrorResponse> {
     let url = build_frontend_app_deploy_config_url(environment, client, frontend_app_id);
-    let builder = Client::new()
-        .get(url)
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(environment, access_token);
+    api.send(api.get(url)).await
 }
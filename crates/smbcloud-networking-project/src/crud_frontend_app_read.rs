@@ -1,9 +1,8 @@
 use {
     crate::url_builder::build_frontend_apps_url,
-    reqwest::Client,
     smbcloud_model::{error_codes::ErrorResponse, frontend_app::FrontendApp},
-    smbcloud_network::{environment::Environment, network::request},
-    smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient},
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::{api_client, smb_client::SmbClient},
 };
 
 pub async fn get_frontend_apps_by_project(
@@ -17,9 +16,6 @@ pub async fn get_frontend_apps_by_project(
         build_frontend_apps_url(env, client),
         project_id
     );
-    let builder = Client::new()
-        .get(url)
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    api.send(api.get(url)).await
 }
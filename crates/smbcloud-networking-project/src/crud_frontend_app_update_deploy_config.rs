@@ -1,12 +1,11 @@
 use {
     crate::url_builder::build_frontend_app_update_deploy_config_url,
-    reqwest::Client,
     smbcloud_model::{
         deploy_config::DeployConfig, deploy_config_update::DeployConfigUpdate,
         error_codes::ErrorResponse,
     },
-    smbcloud_network::{environment::Environment, network::request},
-    smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient},
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::{api_client, smb_client::SmbClient},
 };
 
 pub async fn update_deploy_config(
@@ -17,10 +16,7 @@ pub async fn update_deploy_config(
     payload: &DeployConfigUpdate,
 ) -> Result<DeployConfig, ErrorResponse> {
     let url = build_frontend_app_update_deploy_config_url(environment, client, frontend_app_id);
-    let builder = Client::new()
-        .patch(url)
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT)
-        .json(payload);
-    request(builder).await
+    let api = api_client(environment, access_token);
+    let builder = api.patch(url).json(payload);
+    api.send(builder).await
 }
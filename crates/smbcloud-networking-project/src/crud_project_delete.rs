@@ -1,8 +1,7 @@
-use crate::{request::request_empty, url_builder::build_project_url_with_id};
-use reqwest::Client;
+use crate::url_builder::build_project_url_with_id;
 use smbcloud_model::error_codes::ErrorResponse;
 use smbcloud_network::environment::Environment;
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 pub async fn delete_project(
     env: Environment,
@@ -10,9 +9,7 @@ pub async fn delete_project(
     access_token: String,
     id: String,
 ) -> Result<(), ErrorResponse> {
-    let builder = Client::new()
-        .delete(build_project_url_with_id(env, client, id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request_empty(builder).await
+    let api = api_client(env, access_token);
+    api.send_empty(api.delete(build_project_url_with_id(env, client, id)))
+        .await
 }
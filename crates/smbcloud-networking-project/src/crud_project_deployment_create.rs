@@ -1,12 +1,11 @@
 use crate::url_builder::build_project_deployment_index;
 use anyhow::Result;
-use reqwest::Client;
 use smbcloud_model::{
     error_codes::ErrorResponse,
     project::{Deployment, DeploymentPayload},
 };
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 pub async fn create_deployment(
     env: Environment,
@@ -15,14 +14,14 @@ pub async fn create_deployment(
     project_id: i32,
     payload: DeploymentPayload,
 ) -> Result<Deployment, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .post(build_project_deployment_index(
             env,
             client,
             project_id.to_string(),
+            None,
         ))
-        .json(&payload)
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .json(&payload);
+    api.send(builder).await
 }
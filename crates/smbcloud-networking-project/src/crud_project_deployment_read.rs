@@ -1,25 +1,51 @@
 use crate::url_builder::{build_project_deployment, build_project_deployment_index};
 use anyhow::Result;
-use reqwest::Client;
-use smbcloud_model::{error_codes::ErrorResponse, project::Deployment};
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_model::{
+    error_codes::ErrorResponse,
+    pagination::{Page, PageRequest},
+    project::Deployment,
+};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
+/// List a project's deployments, optionally only those made to one deploy
+/// environment (`smb deploy --env`).
 pub async fn get_deployments(
     env: Environment,
     client: (&SmbClient, &str),
     access_token: String,
     project_id: i32,
+    environment: Option<&str>,
 ) -> Result<Vec<Deployment>, ErrorResponse> {
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api.get(build_project_deployment_index(
+        env,
+        client,
+        project_id.to_string(),
+        environment,
+    ));
+    api.send(builder).await
+}
+
+/// One page of a project's deployments, newest first.
+pub async fn get_deployments_page(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    project_id: i32,
+    environment: Option<&str>,
+    page: &PageRequest,
+) -> Result<Page<Deployment>, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api
         .get(build_project_deployment_index(
             env,
             client,
             project_id.to_string(),
+            environment,
         ))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+        .query(page);
+    api.send_page(builder).await
 }
 
 pub async fn get_deployment(
@@ -29,14 +55,12 @@ pub async fn get_deployment(
     project_id: i32,
     deployment_id: i32,
 ) -> Result<Deployment, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_project_deployment(
-            env,
-            client,
-            project_id.to_string(),
-            deployment_id.to_string(),
-        ))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.get(build_project_deployment(
+        env,
+        client,
+        project_id.to_string(),
+        deployment_id.to_string(),
+    ));
+    api.send(builder).await
 }
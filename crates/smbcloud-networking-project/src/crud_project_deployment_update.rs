@@ -1,11 +1,10 @@
 use anyhow::Result;
-use reqwest::Client;
 use smbcloud_model::{
     error_codes::ErrorResponse,
     project::{Deployment, DeploymentPayload},
 };
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 use crate::url_builder::build_project_deployment;
 
@@ -23,10 +22,7 @@ pub async fn update(
         project_id.to_string(),
         deployment_id.to_string(),
     );
-    let builder = Client::new()
-        .put(url)
-        .json(&status)
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    let builder = api.put(url).json(&status);
+    api.send(builder).await
 }
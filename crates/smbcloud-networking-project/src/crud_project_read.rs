@@ -1,9 +1,12 @@
 use {
     crate::url_builder::{build_project_url, build_project_url_with_id},
-    reqwest::Client,
-    smbcloud_model::{error_codes::ErrorResponse, project::Project},
-    smbcloud_network::{environment::Environment, network::request},
-    smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient},
+    smbcloud_model::{
+        error_codes::ErrorResponse,
+        pagination::{Page, PageRequest},
+        project::Project,
+    },
+    smbcloud_network::environment::Environment,
+    smbcloud_networking::{api_client, smb_client::SmbClient},
 };
 
 pub async fn get_project(
@@ -12,11 +15,9 @@ pub async fn get_project(
     access_token: String,
     id: String,
 ) -> Result<Project, ErrorResponse> {
-    let builder: reqwest::RequestBuilder = Client::new()
-        .get(build_project_url_with_id(env, client, id))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    api.send(api.get(build_project_url_with_id(env, client, id)))
+        .await
 }
 
 pub async fn get_projects(
@@ -24,9 +25,109 @@ pub async fn get_projects(
     client: (&SmbClient, &str),
     access_token: String,
 ) -> Result<Vec<Project>, ErrorResponse> {
-    let builder = Client::new()
-        .get(build_project_url(env, client))
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT);
-    request(builder).await
+    let api = api_client(env, access_token);
+    api.send(api.get(build_project_url(env, client))).await
+}
+
+/// One page of the user's projects. Use
+/// `smbcloud_network::pagination::collect_pages` to walk all of them.
+pub async fn get_projects_page(
+    env: Environment,
+    client: (&SmbClient, &str),
+    access_token: String,
+    page: &PageRequest,
+) -> Result<Page<Project>, ErrorResponse> {
+    let api = api_client(env, access_token);
+    let builder = api.get(build_project_url(env, client)).query(page);
+    api.send_page(builder).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            crud_project_create::create_project, crud_project_delete::delete_project,
+            crud_project_deployment_create::create_deployment,
+            crud_project_deployment_read::get_deployments_page,
+            crud_project_update::update_project,
+        },
+        smbcloud_mock_server::{MockServer, MOCK_ACCESS_TOKEN},
+        smbcloud_model::{
+            project::{DeploymentPayload, DeploymentStatus, ProjectCreate},
+            runner::Runner,
+        },
+        smbcloud_network::{environment::API_URL_ENV, pagination::collect_pages},
+    };
+
+    const CLIENT: (&SmbClient, &str) = (&SmbClient::Cli, "secret");
+
+    fn token() -> String {
+        MOCK_ACCESS_TOKEN.to_string()
+    }
+
+    /// The only test in this crate, since it points `SMB_API_URL` at its own
+    /// mock.
+    #[tokio::test]
+    async fn project_lifecycle_against_the_mock() {
+        let server = MockServer::start().await.unwrap();
+        std::env::set_var(API_URL_ENV, server.url());
+        let env = Environment::Custom;
+
+        for name in ["one", "two", "three"] {
+            let project = ProjectCreate {
+                name: name.to_string(),
+                description: String::new(),
+            };
+            create_project(env, CLIENT, token(), project, None)
+                .await
+                .unwrap();
+        }
+        let projects = collect_pages(Some(2), |page| async move {
+            get_projects_page(env, CLIENT, token(), &page).await
+        })
+        .await
+        .unwrap();
+        let names: Vec<_> = projects
+            .iter()
+            .map(|project| project.name.as_str())
+            .collect();
+        assert_eq!(names, ["one", "two", "three"]);
+        assert_eq!(get_projects(env, CLIENT, token()).await.unwrap().len(), 3);
+
+        let id = projects[0].id;
+        let updated = update_project(
+            env,
+            CLIENT,
+            token(),
+            id.to_string(),
+            "First",
+            Runner::NodeJs,
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.description.as_deref(), Some("First"));
+
+        let payload = DeploymentPayload {
+            commit_hash: "abc123".to_string(),
+            status: DeploymentStatus::Started,
+            frontend_app_id: None,
+            environment: None,
+        };
+        create_deployment(env, CLIENT, MOCK_ACCESS_TOKEN, id, payload)
+            .await
+            .unwrap();
+        let deployments = get_deployments_page(env, CLIENT, token(), id, None, &Default::default())
+            .await
+            .unwrap();
+        assert_eq!(deployments.items.len(), 1);
+        assert!(deployments.next_cursor.is_none());
+
+        delete_project(env, CLIENT, token(), id.to_string())
+            .await
+            .unwrap();
+        assert!(get_project(env, CLIENT, token(), id.to_string())
+            .await
+            .is_err());
+    }
 }
@@ -1,8 +1,7 @@
 use crate::url_builder::build_project_url_with_id;
-use reqwest::Client;
 use smbcloud_model::{error_codes::ErrorResponse, project::Project, runner::Runner};
-use smbcloud_network::{environment::Environment, network::request};
-use smbcloud_networking::{constants::SMB_USER_AGENT, smb_client::SmbClient};
+use smbcloud_network::environment::Environment;
+use smbcloud_networking::{api_client, smb_client::SmbClient};
 
 pub async fn update_project(
     env: Environment,
@@ -14,10 +13,9 @@ pub async fn update_project(
 ) -> Result<Project, ErrorResponse> {
     // PATCH is correct for partial update of description
     let url = build_project_url_with_id(env, client, project_id.to_string());
-    let builder = Client::new()
+    let api = api_client(env, access_token);
+    let builder = api
         .patch(url)
-        .header("Authorization", access_token)
-        .header("User-agent", SMB_USER_AGENT)
         .json(&serde_json::json!({ "description": new_description, "runner": runner }));
-    request(builder).await
+    api.send(builder).await
 }
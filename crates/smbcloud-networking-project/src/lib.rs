@@ -10,5 +10,4 @@ pub mod crud_project_deployment_read;
 pub mod crud_project_deployment_update;
 pub mod crud_project_read;
 pub mod crud_project_update;
-mod request;
 mod url_builder;
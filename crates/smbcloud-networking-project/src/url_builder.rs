@@ -61,11 +61,15 @@ pub(crate) fn build_project_deployment_index(
     env: Environment,
     client: (&SmbClient, &str),
     project_id: String,
+    environment: Option<&str>,
 ) -> String {
     let mut url_builder = smb_base_url_builder(env, client);
     url_builder.add_route("v1/projects");
     url_builder.add_route(project_id.as_str());
     url_builder.add_route("deployment");
+    if let Some(environment) = environment {
+        url_builder.add_param("environment", environment);
+    }
     url_builder.build()
 }
 
@@ -6,6 +6,10 @@ pub const GH_OAUTH_CLIENT_ID: &str = "bf1f12d97659a6495e43";
 pub const GH_OAUTH_REDIRECT_HOST: &str = "http://localhost";
 pub const GH_OAUTH_REDIRECT_PORT: &str = "8808";
 
+/// OIDC client the CLI authorizes as in `smb login --sso`. A public client:
+/// PKCE stands in for a secret.
+pub const CLI_OIDC_CLIENT_ID: &str = "smbcloud-cli";
+
 pub const SMB_USER_AGENT: &str = "smbcloud-cli";
 
 // Paths
@@ -16,7 +20,13 @@ pub const PATH_USERS: &str = "v1/users";
 pub const PATH_USERS_PASSWORD: &str = "v1/users/password";
 pub const PATH_USERS_SIGN_OUT: &str = "v1/users/sign_out";
 pub const PATH_USERS_SIGN_IN: &str = "v1/users/sign_in";
+pub const PATH_USERS_TWO_FACTOR: &str = "v1/users/two_factor";
+pub const PATH_USERS_TWO_FACTOR_RECOVERY_CODES: &str = "v1/users/two_factor/recovery_codes";
 pub const PATH_LINK_GITHUB_ACCOUNT: &str = "v1/link_github_account";
 pub const PATH_RESET_PASSWORD_INSTRUCTIONS: &str = "v1/resend_reset_password_instructions";
 pub const PATH_RESEND_CONFIRMATION: &str = "v1/resend_confirmation";
 pub const PATH_AUTHORIZE: &str = "v1/authorize";
+pub const PATH_AUTHORIZE_GOOGLE: &str = "v1/authorize/google";
+pub const PATH_ACCESS_TOKENS: &str = "v1/access_tokens";
+pub const PATH_DEVICE_CODE: &str = "v1/oauth/device/code";
+pub const PATH_DEVICE_TOKEN: &str = "v1/oauth/device/token";
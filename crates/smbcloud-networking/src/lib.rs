@@ -2,10 +2,19 @@ pub mod constants;
 pub mod smb_client;
 
 use {
-    crate::smb_client::SmbClient, smbcloud_network::environment::Environment,
+    crate::{constants::SMB_USER_AGENT, smb_client::SmbClient},
+    smbcloud_network::{api_client::SmbApiClient, environment::Environment},
     url_builder::URLBuilder,
 };
 
+/// The client for requests made with a user's token: the token and the CLI
+/// user agent on the shared pool.
+pub fn api_client(env: Environment, access_token: impl Into<String>) -> SmbApiClient {
+    SmbApiClient::new(env)
+        .with_access_token(access_token)
+        .with_user_agent(SMB_USER_AGENT)
+}
+
 pub fn smb_base_url_builder(env: Environment, client: (&SmbClient, &str)) -> URLBuilder {
     let mut url_builder = URLBuilder::new();
     url_builder
@@ -10,6 +10,10 @@ pub struct Config {
     pub description: Option<String>,
     pub project: Project,
     pub projects: Option<Vec<Project>>,
+    /// Deploy environment picked with `smb deploy --env`, already applied to
+    /// `project`. Runtime-only: never read from or written to the file.
+    #[serde(skip)]
+    pub environment: Option<String>,
 }
 
 impl Config {
@@ -22,6 +22,7 @@ module SmbCloud
     module Environment
       DEV = 'dev'
       PRODUCTION = 'production'
+      CUSTOM = 'custom'
     end
 
     class Client
@@ -6,7 +6,7 @@ fn parse_environment(value: String) -> Result<Environment, Error> {
     value.parse().map_err(|_| {
         Error::new(
             magnus::exception::arg_error(),
-            format!("invalid environment `{value}`, expected `dev` or `production`"),
+            format!("invalid environment `{value}`, expected `dev`, `production` or `custom`"),
         )
     })
 }
@@ -34,6 +34,7 @@ module SmbCloud
     module Environment
       DEV = 'dev'
       PRODUCTION = 'production'
+      CUSTOM = 'custom'
     end
 
     class Client
@@ -0,0 +1,159 @@
+use magnus::{function, prelude::*, Error, Ruby};
+use serde::Deserialize;
+use serde_json::Value;
+use smbcloud_gresiq_sdk::{DocumentQuery, Environment, GresiqClient, GresiqCredentials, GresiqError};
+use tokio::runtime::Runtime;
+
+/// The `DocumentQuery` fields as the Ruby side sends them.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct QueryArgs {
+    filter: Option<Value>,
+    order: Option<String>,
+    dir: Option<String>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+impl From<QueryArgs> for DocumentQuery {
+    fn from(args: QueryArgs) -> Self {
+        DocumentQuery {
+            filter: args.filter,
+            order: args.order,
+            dir: args.dir,
+            limit: args.limit,
+            cursor: args.cursor,
+        }
+    }
+}
+
+fn parse_environment(value: String) -> Result<Environment, Error> {
+    value.parse().map_err(|_| {
+        Error::new(
+            magnus::exception::arg_error(),
+            format!("invalid environment `{value}`, expected `dev`, `production` or `custom`"),
+        )
+    })
+}
+
+fn parse_json<T: for<'de> Deserialize<'de>>(name: &str, json: &str) -> Result<T, Error> {
+    serde_json::from_str(json)
+        .map_err(|err| Error::new(magnus::exception::arg_error(), format!("invalid {name}: {err}")))
+}
+
+fn with_runtime<T, F>(f: F) -> Result<T, Error>
+where
+    F: FnOnce(&Runtime) -> Result<T, Error>,
+{
+    let runtime = Runtime::new().map_err(|err| {
+        Error::new(
+            magnus::exception::runtime_error(),
+            format!("failed to initialize tokio runtime: {err}"),
+        )
+    })?;
+    f(&runtime)
+}
+
+fn runtime_error(message: impl std::fmt::Display) -> Error {
+    Error::new(magnus::exception::runtime_error(), message.to_string())
+}
+
+/// Raised as JSON so the Ruby side can expose the HTTP status on `#payload`.
+fn gresiq_error(error: GresiqError) -> Error {
+    let payload = match error {
+        GresiqError::Api { status, message } => {
+            serde_json::json!({ "status": status, "message": message })
+        }
+        GresiqError::Http(error) => serde_json::json!({ "message": error.to_string() }),
+    };
+    runtime_error(payload)
+}
+
+fn client(environment: Environment, api_key: &str, api_secret: &str) -> GresiqClient {
+    GresiqClient::from_credentials(
+        environment,
+        GresiqCredentials {
+            api_key,
+            api_secret,
+        },
+    )
+}
+
+/// `record_json` is the record built on the Ruby side, sent as `{ record }`.
+fn insert_json(
+    environment: String,
+    api_key: String,
+    api_secret: String,
+    table: String,
+    record_json: String,
+) -> Result<(), Error> {
+    let env = parse_environment(environment)?;
+    let record: Value = parse_json("record", &record_json)?;
+
+    with_runtime(|runtime| {
+        runtime
+            .block_on(client(env, &api_key, &api_secret).insert(&table, &record))
+            .map_err(gresiq_error)
+    })
+}
+
+/// `key` empty string means "append under a server-generated key".
+fn upsert_document_json(
+    environment: String,
+    api_key: String,
+    api_secret: String,
+    collection: String,
+    key: String,
+    doc_json: String,
+) -> Result<(), Error> {
+    let env = parse_environment(environment)?;
+    let doc: Value = parse_json("doc", &doc_json)?;
+    let key = if key.trim().is_empty() { None } else { Some(key) };
+
+    with_runtime(|runtime| {
+        runtime
+            .block_on(client(env, &api_key, &api_secret).upsert_document(
+                &collection,
+                key.as_deref(),
+                &doc,
+            ))
+            .map_err(gresiq_error)
+    })
+}
+
+/// `query_json` carries the `DocumentQuery` fields. Returns
+/// `{ documents, next_cursor }` as JSON.
+fn get_collection_page_json(
+    environment: String,
+    api_key: String,
+    api_secret: String,
+    collection: String,
+    query_json: String,
+) -> Result<String, Error> {
+    let env = parse_environment(environment)?;
+    let query: DocumentQuery = parse_json::<QueryArgs>("query", &query_json)?.into();
+
+    with_runtime(|runtime| {
+        match runtime.block_on(
+            client(env, &api_key, &api_secret).get_collection_page::<Value>(&collection, &query),
+        ) {
+            Ok(page) => serde_json::to_string(&page).map_err(runtime_error),
+            Err(error) => Err(gresiq_error(error)),
+        }
+    })
+}
+
+#[magnus::init]
+fn init(ruby: &Ruby) -> Result<(), Error> {
+    let smbcloud = ruby.define_module("SmbCloud")?;
+    let gresiq = smbcloud.define_module("Gresiq")?;
+
+    gresiq.define_singleton_method("__insert", function!(insert_json, 5))?;
+    gresiq.define_singleton_method("__upsert_document", function!(upsert_document_json, 6))?;
+    gresiq.define_singleton_method(
+        "__get_collection_page",
+        function!(get_collection_page_json, 5),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+# frozen_string_literal: true
+
+require 'json'
+require_relative 'gresiq/version'
+require_relative 'gresiq/gresiq'
+
+module Gresiq
+end
+
+module SmbCloud
+  # Ruby bindings for the smbCloud GresIQ gateway, powered by the shared Rust
+  # SDK (smbcloud-gresiq-sdk) and a native Magnus extension.
+  #
+  # @example
+  #   client = SmbCloud::Gresiq.client(environment: "production", api_key: "…", api_secret: "…")
+  #   client.upsert_document("profiles", { plan: "pro" }, key: "user-42")
+  #   client.get_collection("profiles", filter: { plan: "pro" }, limit: 50)
+  module Gresiq
+    class Error < StandardError
+      attr_reader :payload
+
+      def initialize(message = nil, payload: nil)
+        super(message)
+        @payload = payload
+      end
+    end
+
+    module Environment
+      DEV = 'dev'
+      PRODUCTION = 'production'
+      CUSTOM = 'custom'
+    end
+
+    class Client
+      attr_reader :environment, :api_key, :api_secret
+
+      def initialize(environment:, api_key:, api_secret:)
+        @environment = environment
+        @api_key = api_key
+        @api_secret = api_secret
+      end
+
+      # POST a record into a GresIQ-managed table, e.g. "pulse_inference_events".
+      # The gateway resolves the tenant prefix from the api key.
+      def insert(table, record)
+        Gresiq.__insert(environment, api_key, api_secret, table, JSON.generate(record))
+        nil
+      rescue RuntimeError => e
+        raise Gresiq.send(:normalize_error, e)
+      end
+
+      # Upsert a document on `key`, or append it under a server-generated key
+      # when `key` is nil.
+      def upsert_document(collection, doc, key: nil)
+        Gresiq.__upsert_document(environment, api_key, api_secret, collection, key.to_s,
+                                 JSON.generate(doc))
+        nil
+      rescue RuntimeError => e
+        raise Gresiq.send(:normalize_error, e)
+      end
+
+      # Documents in `collection`. `filter` is matched by JSON containment;
+      # `order` is "created_at" or "updated_at", `dir` "asc" or "desc"; `limit`
+      # is clamped server-side to 1..=1000. Only the first page comes back.
+      def get_collection(collection, filter: nil, order: nil, dir: nil, limit: nil)
+        get_collection_page(collection, filter:, order:, dir:, limit:)[:documents]
+      end
+
+      # One page of `get_collection`: { documents:, next_cursor: }. Pass
+      # `next_cursor` back as `cursor` for the next page; it is nil on the last.
+      def get_collection_page(collection, filter: nil, order: nil, dir: nil, limit: nil, cursor: nil)
+        query = { filter:, order:, dir:, limit:, cursor: }.compact
+        Gresiq.send(:parse_json,
+                    Gresiq.__get_collection_page(environment, api_key, api_secret, collection,
+                                                 JSON.generate(query)))
+      rescue RuntimeError => e
+        raise Gresiq.send(:normalize_error, e)
+      end
+    end
+
+    class << self
+      def client(environment:, api_key:, api_secret:)
+        Client.new(environment:, api_key:, api_secret:)
+      end
+
+      def insert_with_client(environment:, api_key:, api_secret:, table:, record:)
+        client(environment:, api_key:, api_secret:).insert(table, record)
+      end
+
+      def upsert_document_with_client(environment:, api_key:, api_secret:, collection:, doc:, key: nil)
+        client(environment:, api_key:, api_secret:).upsert_document(collection, doc, key:)
+      end
+
+      def get_collection_with_client(environment:, api_key:, api_secret:, collection:, **query)
+        client(environment:, api_key:, api_secret:).get_collection(collection, **query)
+      end
+
+      private
+
+      def parse_json(payload)
+        return payload unless payload.is_a?(String)
+
+        JSON.parse(payload, symbolize_names: true)
+      end
+
+      def normalize_error(error)
+        payload = parse_json(error.message)
+        message = payload.is_a?(Hash) ? (payload[:message] || error.message) : error.message
+        Error.new(message, payload: payload)
+      rescue JSON::ParserError, NoMethodError, TypeError
+        Error.new(error.message)
+      end
+    end
+  end
+end
@@ -0,0 +1,5 @@
+# frozen_string_literal: true
+
+module Gresiq
+  VERSION = '0.4.13'
+end
@@ -1,30 +1,80 @@
 from __future__ import annotations
 
+import asyncio
+import functools
 import json
 from dataclasses import dataclass
 from enum import Enum
 from importlib.metadata import PackageNotFoundError, version
-from typing import Any, Dict
+from typing import Any, Dict, List, Optional
 
 from ._native import NativeSdkError
-from ._native import login_with_client as _login_with_client
+from ._native import build_authorization_request as _build_authorization_request
+from ._native import email_get_message as _email_get_message
+from ._native import email_list_messages_page as _email_list_messages_page
+from ._native import email_send as _email_send
+from ._native import exchange_code as _exchange_code
+from ._native import get_userinfo as _get_userinfo
+from ._native import gresiq_get_collection_page as _gresiq_get_collection_page
+from ._native import gresiq_insert as _gresiq_insert
+from ._native import gresiq_upsert_document as _gresiq_upsert_document
+from ._native import login_with_client_otp as _login_with_client_otp
 from ._native import logout_with_client as _logout_with_client
 from ._native import me_with_client as _me_with_client
+from ._native import parse_callback_url as _parse_callback_url
 from ._native import remove_with_client as _remove_with_client
+from ._native import reset_password_with_client as _reset_password_with_client
 from ._native import signup_with_client as _signup_with_client
 
 
 class Environment(str, Enum):
     DEV = "dev"
     PRODUCTION = "production"
+    CUSTOM = "custom"
 
 
 class SdkError(Exception):
-    def __init__(self, error_code: int, error_name: str, message: str) -> None:
+    def __init__(
+        self,
+        error_code: int,
+        error_name: str,
+        message: str,
+        status: Optional[int] = None,
+    ) -> None:
         super().__init__(message)
         self.error_code = error_code
         self.error_name = error_name
         self.message = message
+        # HTTP status, set for Email and GresIQ API errors.
+        self.status = status
+
+
+class ClientError(SdkError):
+    """Codes 0-99: the call failed on this side (input, config, cancelled)."""
+
+
+class ParseError(ClientError):
+    """The reply could not be read."""
+
+
+class NetworkError(ClientError):
+    """The request never got a reply."""
+
+
+class AuthError(SdkError):
+    """Codes 100-199: unauthorized, invalid parameters or forbidden."""
+
+
+class AccountError(SdkError):
+    """Codes 1000-1999: email, password and login-flow errors."""
+
+
+class ProjectError(SdkError):
+    """Codes 2000-2999: project and deployment errors."""
+
+
+class ApiError(SdkError):
+    """An Email or GresIQ API reply with an unexpected HTTP status."""
 
 
 @dataclass(frozen=True)
@@ -36,8 +86,14 @@ class AuthClient:
     def signup(self, email: str, password: str) -> Dict[str, Any]:
         return signup_with_client(self.env, self.app_id, self.app_secret, email, password)
 
-    def login(self, email: str, password: str) -> Dict[str, Any]:
-        return login_with_client(self.env, self.app_id, self.app_secret, email, password)
+    def login(
+        self, email: str, password: str, otp: Optional[str] = None
+    ) -> Dict[str, Any]:
+        """Pass `otp`, a TOTP or recovery code, after a login came back with
+        `kind` `otp_required`."""
+        return login_with_client(
+            self.env, self.app_id, self.app_secret, email, password, otp
+        )
 
     def logout(self, access_token: str) -> None:
         logout_with_client(self.env, self.app_id, self.app_secret, access_token)
@@ -48,6 +104,197 @@ class AuthClient:
     def remove(self, access_token: str) -> None:
         remove_with_client(self.env, self.app_id, self.app_secret, access_token)
 
+    def reset_password(self, email: str) -> Dict[str, Any]:
+        return reset_password_with_client(self.env, self.app_id, self.app_secret, email)
+
+    async def signup_async(self, email: str, password: str) -> Dict[str, Any]:
+        return await _run_async(self.signup, email, password)
+
+    async def login_async(
+        self, email: str, password: str, otp: Optional[str] = None
+    ) -> Dict[str, Any]:
+        return await _run_async(self.login, email, password, otp)
+
+    async def logout_async(self, access_token: str) -> None:
+        await _run_async(self.logout, access_token)
+
+    async def me_async(self, access_token: str) -> Dict[str, Any]:
+        return await _run_async(self.me, access_token)
+
+    async def remove_async(self, access_token: str) -> None:
+        await _run_async(self.remove, access_token)
+
+    async def reset_password_async(self, email: str) -> Dict[str, Any]:
+        return await _run_async(self.reset_password, email)
+
+
+@dataclass(frozen=True)
+class EmailClient:
+    """Sends and reads transactional email with a Mail app API key.
+
+    `base_url` overrides the environment's API, e.g. for a local mock.
+    """
+
+    env: Environment
+    api_key: str
+    base_url: Optional[str] = None
+
+    def send(self, message: Dict[str, Any]) -> Dict[str, Any]:
+        """`message` takes `from`, `to` and at least one of `html` or `text`,
+        plus optional `cc`, `bcc`, `reply_to`, `subject`, `attachments`,
+        `headers`, `tags` and `idempotency_key`."""
+        return _run(_email_send, self.env, self.api_key, message, self.base_url)
+
+    def get_message(self, id: str) -> Dict[str, Any]:
+        return _run(_email_get_message, self.env, self.api_key, id, self.base_url)
+
+    def list_messages(
+        self, status: Optional[str] = None, limit: Optional[int] = None
+    ) -> List[Dict[str, Any]]:
+        return self.list_messages_page(status, limit)["messages"]
+
+    def list_messages_page(
+        self,
+        status: Optional[str] = None,
+        limit: Optional[int] = None,
+        cursor: Optional[str] = None,
+    ) -> Dict[str, Any]:
+        """Returns `{"messages": [...], "next_cursor": ...}`; pass
+        `next_cursor` back as `cursor` for the next page."""
+        return _run(
+            _email_list_messages_page,
+            self.env,
+            self.api_key,
+            status,
+            limit,
+            cursor,
+            self.base_url,
+        )
+
+    async def send_async(self, message: Dict[str, Any]) -> Dict[str, Any]:
+        return await _run_async(self.send, message)
+
+    async def get_message_async(self, id: str) -> Dict[str, Any]:
+        return await _run_async(self.get_message, id)
+
+    async def list_messages_async(
+        self, status: Optional[str] = None, limit: Optional[int] = None
+    ) -> List[Dict[str, Any]]:
+        return await _run_async(self.list_messages, status, limit)
+
+    async def list_messages_page_async(
+        self,
+        status: Optional[str] = None,
+        limit: Optional[int] = None,
+        cursor: Optional[str] = None,
+    ) -> Dict[str, Any]:
+        return await _run_async(self.list_messages_page, status, limit, cursor)
+
+
+@dataclass(frozen=True)
+class GresiqClient:
+    """Reads and writes documents in a GresIQ database.
+
+    `base_url` overrides the environment's API, e.g. for a local mock.
+    """
+
+    env: Environment
+    api_key: str
+    api_secret: str
+    base_url: Optional[str] = None
+
+    def insert(self, table: str, record: Dict[str, Any]) -> None:
+        _run(
+            _gresiq_insert,
+            self.env,
+            self.api_key,
+            self.api_secret,
+            table,
+            record,
+            self.base_url,
+        )
+
+    def upsert_document(
+        self, collection: str, doc: Any, key: Optional[str] = None
+    ) -> None:
+        """Upserts on `key` when given; appends under a new key otherwise."""
+        _run(
+            _gresiq_upsert_document,
+            self.env,
+            self.api_key,
+            self.api_secret,
+            collection,
+            doc,
+            key,
+            self.base_url,
+        )
+
+    def get_collection(
+        self,
+        collection: str,
+        filter: Optional[Dict[str, Any]] = None,
+        order: Optional[str] = None,
+        dir: Optional[str] = None,
+        limit: Optional[int] = None,
+    ) -> List[Dict[str, Any]]:
+        return self.get_collection_page(collection, filter, order, dir, limit)["documents"]
+
+    def get_collection_page(
+        self,
+        collection: str,
+        filter: Optional[Dict[str, Any]] = None,
+        order: Optional[str] = None,
+        dir: Optional[str] = None,
+        limit: Optional[int] = None,
+        cursor: Optional[str] = None,
+    ) -> Dict[str, Any]:
+        """Returns `{"documents": [...], "next_cursor": ...}`. `filter` is
+        matched by JSON containment; `order` is `created_at` or `updated_at`."""
+        return _run(
+            _gresiq_get_collection_page,
+            self.env,
+            self.api_key,
+            self.api_secret,
+            collection,
+            filter,
+            order,
+            dir,
+            limit,
+            cursor,
+            self.base_url,
+        )
+
+    async def insert_async(self, table: str, record: Dict[str, Any]) -> None:
+        await _run_async(self.insert, table, record)
+
+    async def upsert_document_async(
+        self, collection: str, doc: Any, key: Optional[str] = None
+    ) -> None:
+        await _run_async(self.upsert_document, collection, doc, key)
+
+    async def get_collection_async(
+        self,
+        collection: str,
+        filter: Optional[Dict[str, Any]] = None,
+        order: Optional[str] = None,
+        dir: Optional[str] = None,
+        limit: Optional[int] = None,
+    ) -> List[Dict[str, Any]]:
+        return await _run_async(self.get_collection, collection, filter, order, dir, limit)
+
+    async def get_collection_page_async(
+        self,
+        collection: str,
+        filter: Optional[Dict[str, Any]] = None,
+        order: Optional[str] = None,
+        dir: Optional[str] = None,
+        limit: Optional[int] = None,
+        cursor: Optional[str] = None,
+    ) -> Dict[str, Any]:
+        return await _run_async(
+            self.get_collection_page, collection, filter, order, dir, limit, cursor
+        )
+
 
 def signup_with_client(
     env: Environment | str,
@@ -65,8 +312,9 @@ def login_with_client(
     app_secret: str,
     email: str,
     password: str,
+    otp: Optional[str] = None,
 ) -> Dict[str, Any]:
-    return _run(_login_with_client, env, app_id, app_secret, email, password)
+    return _run(_login_with_client_otp, env, app_id, app_secret, email, password, otp)
 
 
 def logout_with_client(
@@ -96,29 +344,183 @@ def remove_with_client(
     _run(_remove_with_client, env, app_id, app_secret, access_token)
 
 
-def _run(func: Any, env: Environment | str, *args: str) -> Any:
+def reset_password_with_client(
+    env: Environment | str,
+    app_id: str,
+    app_secret: str,
+    email: str,
+) -> Dict[str, Any]:
+    return _run(_reset_password_with_client, env, app_id, app_secret, email)
+
+
+def build_authorization_request(
+    env: Environment | str,
+    oidc_client_id: str,
+    redirect_uri: str,
+) -> Dict[str, Any]:
+    """Starts a hosted login: `authorize_url` to redirect to, plus the
+    `state` and `code_verifier` to keep until the browser returns."""
+    return _run(_build_authorization_request, env, oidc_client_id, redirect_uri)
+
+
+def parse_callback_url(callback_url: str) -> Dict[str, Any]:
+    """Reads `code`, `state` and `tenant_id` off the redirect URL. Compare
+    `state` with the one from `build_authorization_request`."""
+    try:
+        return _parse_callback_url(callback_url)
+    except NativeSdkError as exc:
+        raise _sdk_error_from_native(exc) from exc
+
+
+def exchange_code(
+    env: Environment | str,
+    oidc_client_id: str,
+    redirect_uri: str,
+    code: str,
+    code_verifier: str,
+) -> Dict[str, Any]:
+    return _run(_exchange_code, env, oidc_client_id, redirect_uri, code, code_verifier)
+
+
+def get_userinfo(
+    env: Environment | str,
+    access_token: str,
+    tenant_id: Optional[str] = None,
+) -> Dict[str, Any]:
+    return _run(_get_userinfo, env, access_token, tenant_id)
+
+
+async def signup_with_client_async(
+    env: Environment | str,
+    app_id: str,
+    app_secret: str,
+    email: str,
+    password: str,
+) -> Dict[str, Any]:
+    return await _run_async(signup_with_client, env, app_id, app_secret, email, password)
+
+
+async def login_with_client_async(
+    env: Environment | str,
+    app_id: str,
+    app_secret: str,
+    email: str,
+    password: str,
+    otp: Optional[str] = None,
+) -> Dict[str, Any]:
+    return await _run_async(
+        login_with_client, env, app_id, app_secret, email, password, otp
+    )
+
+
+async def logout_with_client_async(
+    env: Environment | str,
+    app_id: str,
+    app_secret: str,
+    access_token: str,
+) -> None:
+    await _run_async(logout_with_client, env, app_id, app_secret, access_token)
+
+
+async def me_with_client_async(
+    env: Environment | str,
+    app_id: str,
+    app_secret: str,
+    access_token: str,
+) -> Dict[str, Any]:
+    return await _run_async(me_with_client, env, app_id, app_secret, access_token)
+
+
+async def remove_with_client_async(
+    env: Environment | str,
+    app_id: str,
+    app_secret: str,
+    access_token: str,
+) -> None:
+    await _run_async(remove_with_client, env, app_id, app_secret, access_token)
+
+
+async def reset_password_with_client_async(
+    env: Environment | str,
+    app_id: str,
+    app_secret: str,
+    email: str,
+) -> Dict[str, Any]:
+    return await _run_async(reset_password_with_client, env, app_id, app_secret, email)
+
+
+async def exchange_code_async(
+    env: Environment | str,
+    oidc_client_id: str,
+    redirect_uri: str,
+    code: str,
+    code_verifier: str,
+) -> Dict[str, Any]:
+    return await _run_async(
+        exchange_code, env, oidc_client_id, redirect_uri, code, code_verifier
+    )
+
+
+async def get_userinfo_async(
+    env: Environment | str,
+    access_token: str,
+    tenant_id: Optional[str] = None,
+) -> Dict[str, Any]:
+    return await _run_async(get_userinfo, env, access_token, tenant_id)
+
+
+def _run(func: Any, env: Environment | str, *args: Any) -> Any:
     try:
         return func(_normalize_env(env), *args)
     except NativeSdkError as exc:
         raise _sdk_error_from_native(exc) from exc
 
 
+async def _run_async(func: Any, *args: Any) -> Any:
+    # The native call releases the GIL while it waits on the shared tokio
+    # runtime, so executor threads don't block the event loop or each other.
+    loop = asyncio.get_running_loop()
+    return await loop.run_in_executor(None, functools.partial(func, *args))
+
+
 def _normalize_env(env: Environment | str) -> str:
     if isinstance(env, Environment):
         return env.value
     return Environment(env).value
 
 
+def _error_class(error_code: int, status: Optional[int]) -> type[SdkError]:
+    if error_code == 1:
+        return ParseError
+    if error_code == 2:
+        return NetworkError
+    if status is not None and error_code == 0:
+        return ApiError
+    if 0 <= error_code < 100:
+        return ClientError
+    if 100 <= error_code < 200:
+        return AuthError
+    if 1000 <= error_code < 2000:
+        return AccountError
+    if 2000 <= error_code < 3000:
+        return ProjectError
+    return SdkError
+
+
 def _sdk_error_from_native(exc: NativeSdkError) -> SdkError:
     try:
         payload = json.loads(str(exc))
     except json.JSONDecodeError:
         return SdkError(0, "Unknown", str(exc))
 
-    return SdkError(
-        int(payload.get("error_code", 0)),
+    error_code = int(payload.get("error_code", 0))
+    status = payload.get("status")
+    status = int(status) if status is not None else None
+    return _error_class(error_code, status)(
+        error_code,
         str(payload.get("error_name", "Unknown")),
         str(payload.get("message", str(exc))),
+        status,
     )
 
 
@@ -129,13 +531,35 @@ except PackageNotFoundError:
 
 
 __all__ = [
+    "AccountError",
+    "ApiError",
     "AuthClient",
+    "AuthError",
+    "ClientError",
+    "EmailClient",
     "Environment",
+    "GresiqClient",
+    "NetworkError",
+    "ParseError",
+    "ProjectError",
     "SdkError",
     "__version__",
+    "build_authorization_request",
+    "exchange_code",
+    "exchange_code_async",
+    "get_userinfo",
+    "get_userinfo_async",
     "login_with_client",
+    "login_with_client_async",
     "logout_with_client",
+    "logout_with_client_async",
     "me_with_client",
+    "me_with_client_async",
+    "parse_callback_url",
     "remove_with_client",
+    "remove_with_client_async",
+    "reset_password_with_client",
+    "reset_password_with_client_async",
     "signup_with_client",
+    "signup_with_client_async",
 ]